@@ -0,0 +1,35 @@
+//! Parse + interface extraction throughput.
+//!
+//! Run with `cargo bench`. The input is `tests/fixtures/sample.rs` repeated
+//! `COPIES` times, so numbers stay comparable across changes.
+
+use std::fs;
+use std::io::Write;
+
+const COPIES: usize = 50;
+const ITERATIONS: usize = 20;
+
+fn main() {
+    let fixture = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sample.rs"))
+        .expect("read fixture");
+    let source = fixture.repeat(COPIES);
+    let mut file = tempfile::Builder::new().suffix(".rs").tempfile().expect("create temp file");
+    file.write_all(source.as_bytes()).expect("write temp file");
+    file.flush().expect("flush temp file");
+    let path = file.path().to_str().expect("utf-8 temp path");
+
+    // Warm-up round so the first timed iteration doesn't pay one-time costs
+    codeview::bench_extract(path, 1).expect("extract");
+    let elapsed = codeview::bench_extract(path, ITERATIONS).expect("extract");
+
+    let per_iter = elapsed.as_secs_f64() / ITERATIONS as f64;
+    let lines = source.lines().count();
+    println!(
+        "extract/sample.rs x{}: {:.3} ms/iter, {:.0} lines/s ({} lines, {} iterations)",
+        COPIES,
+        per_iter * 1000.0,
+        lines as f64 / per_iter,
+        lines,
+        ITERATIONS
+    );
+}
@@ -0,0 +1,136 @@
+//! Call graph between the top-level functions of one file.
+//!
+//! Heuristic and purely syntactic: a call whose callee is a bare identifier
+//! naming another top-level function of the same file is an edge. Method
+//! calls, calls through paths (`module::f`) and calls to functions defined
+//! elsewhere are not followed.
+
+use crate::error::CodeviewError;
+use crate::languages;
+use crate::parser;
+use crate::read_source;
+use std::fmt::Write;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// A top-level function and the top-level functions it calls, in order of first call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallNode {
+    pub name: String,
+    pub line: usize,
+    pub calls: Vec<String>,
+}
+
+/// The call graph of the top-level functions in the file at `path_str`, in source order.
+pub fn call_graph(path_str: &str) -> Result<Vec<CallNode>, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    if path.is_dir() {
+        return Err(CodeviewError::InvalidPath(
+            "--callgraph only works on single files, not directories".to_string(),
+        ));
+    }
+    let language = languages::detect_language(path)?;
+    let source = read_source(path, false)?;
+    let tree = parser::parse(&source, language)?;
+
+    let functions = top_level_functions(tree.root_node(), &source);
+    let names: Vec<&str> = functions.iter().map(|(name, _)| name.as_str()).collect();
+    let graph = functions
+        .iter()
+        .map(|(name, node)| {
+            let mut calls = Vec::new();
+            collect_calls(*node, &source, &names, &mut calls);
+            CallNode { name: name.clone(), line: node.start_position().row + 1, calls }
+        })
+        .collect();
+    Ok(graph)
+}
+
+/// One `caller -> callee` line per edge.
+pub fn format_edges(graph: &[CallNode]) -> String {
+    let mut out = String::new();
+    for node in graph {
+        for callee in &node.calls {
+            writeln!(out, "{} -> {}", node.name, callee).unwrap();
+        }
+    }
+    out
+}
+
+/// A Graphviz `digraph` with every function as a node, including those without edges.
+pub fn format_dot(graph: &[CallNode]) -> String {
+    let mut out = String::from("digraph callgraph {\n");
+    for node in graph {
+        writeln!(out, "    \"{}\";", node.name).unwrap();
+    }
+    for node in graph {
+        for callee in &node.calls {
+            writeln!(out, "    \"{}\" -> \"{}\";", node.name, callee).unwrap();
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Named function definitions directly under the root, looking through
+/// `export` and decorator wrappers.
+fn top_level_functions<'a>(root: Node<'a>, source: &str) -> Vec<(String, Node<'a>)> {
+    let mut functions = Vec::new();
+    let mut cursor = root.walk();
+    for child in root.children(&mut cursor) {
+        let node = child
+            .child_by_field_name("declaration")
+            .or_else(|| child.child_by_field_name("definition"))
+            .unwrap_or(child);
+        if !matches!(
+            node.kind(),
+            "function_item" | "function_declaration" | "generator_function_declaration" | "function_definition"
+        ) {
+            continue;
+        }
+        if let Some(name) = node.child_by_field_name("name") {
+            functions.push((source[name.byte_range()].to_string(), node));
+        }
+    }
+    functions
+}
+
+/// Append to `calls` each name in `names` called by a bare identifier within `node`.
+fn collect_calls(node: Node, source: &str, names: &[&str], calls: &mut Vec<String>) {
+    let mut stack = vec![node];
+    let mut in_order = Vec::new();
+    while let Some(n) = stack.pop() {
+        if matches!(n.kind(), "call_expression" | "call") {
+            let callee = n.child_by_field_name("function").filter(|f| f.kind() == "identifier");
+            if let Some(name) = callee.map(|f| &source[f.byte_range()]).filter(|name| names.contains(name)) {
+                in_order.push((n.start_byte(), name.to_string()));
+            }
+        }
+        let mut cursor = n.walk();
+        stack.extend(n.children(&mut cursor));
+    }
+    in_order.sort();
+    for (_, name) in in_order {
+        if !calls.contains(&name) {
+            calls.push(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_lists_isolated_functions() {
+        let graph = vec![
+            CallNode { name: "a".to_string(), line: 1, calls: vec!["b".to_string()] },
+            CallNode { name: "b".to_string(), line: 5, calls: Vec::new() },
+        ];
+        assert_eq!(format_dot(&graph), "digraph callgraph {\n    \"a\";\n    \"b\";\n    \"a\" -> \"b\";\n}\n");
+        assert_eq!(format_edges(&graph), "a -> b\n");
+    }
+}
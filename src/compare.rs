@@ -0,0 +1,126 @@
+//! Structural (interface-level) diff between two versions of a file.
+//!
+//! Items are matched by kind and name (methods by `Parent::name`); repeated
+//! keys such as several `impl User` blocks are matched in source order.
+
+use crate::error::CodeviewError;
+use crate::extractor::{interface, Item, ItemKind};
+use crate::hotspots::one_line_signature;
+use crate::languages;
+use crate::output::plain::kind_label;
+use crate::parser;
+use crate::read_source;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// (kind label, name, occurrence) identifying an item within one file.
+type SymbolKey = (String, String, usize);
+
+/// A named interface item of one file.
+struct Symbol {
+    key: SymbolKey,
+    kind: ItemKind,
+    signature: String,
+}
+
+/// How a symbol differs between the old and new file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// Present in both, but its collapsed signature differs
+    Changed,
+}
+
+/// One structural difference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub change: ChangeKind,
+    pub kind: ItemKind,
+    pub name: String,
+}
+
+/// Compare the interface items of `old_path` and `new_path`. Removed symbols
+/// come first (in old-file order), then added and changed ones (in new-file order).
+pub fn compare_files(old_path: &str, new_path: &str) -> Result<Vec<Change>, CodeviewError> {
+    let old = symbols(old_path)?;
+    let new = symbols(new_path)?;
+
+    let old_by_key: HashMap<&SymbolKey, &Symbol> = old.iter().map(|s| (&s.key, s)).collect();
+    let new_keys: HashSet<&SymbolKey> = new.iter().map(|s| &s.key).collect();
+
+    let mut changes: Vec<Change> = old
+        .iter()
+        .filter(|s| !new_keys.contains(&s.key))
+        .map(|s| Change { change: ChangeKind::Removed, kind: s.kind.clone(), name: s.key.1.clone() })
+        .collect();
+    for s in &new {
+        let change = match old_by_key.get(&s.key) {
+            None => ChangeKind::Added,
+            Some(o) if o.signature != s.signature => ChangeKind::Changed,
+            Some(_) => continue,
+        };
+        changes.push(Change { change, kind: s.kind.clone(), name: s.key.1.clone() });
+    }
+    Ok(changes)
+}
+
+/// Format changes as `+ fn name`, `- struct Name`, `~ fn name (signature)` lines.
+pub fn format_changes(changes: &[Change]) -> String {
+    if changes.is_empty() {
+        return "No interface changes\n".to_string();
+    }
+    let mut output = String::new();
+    for c in changes {
+        let label = kind_label(&c.kind);
+        match c.change {
+            ChangeKind::Added => writeln!(output, "+ {} {}", label, c.name).unwrap(),
+            ChangeKind::Removed => writeln!(output, "- {} {}", label, c.name).unwrap(),
+            ChangeKind::Changed => writeln!(output, "~ {} {} (signature)", label, c.name).unwrap(),
+        }
+    }
+    output
+}
+
+/// Named interface items of a file, in source order.
+fn symbols(path_str: &str) -> Result<Vec<Symbol>, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.is_file() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    let source = read_source(path, false)?;
+    let language = languages::detect_language(path)?;
+    let tree = parser::parse(&source, language)?;
+
+    let mut seen: HashMap<(String, String), usize> = HashMap::new();
+    let mut result = Vec::new();
+    for item in interface::extract(&source, &tree, language) {
+        let Some(name) = display_name(&item) else { continue };
+        let label = kind_label(&item.kind).to_string();
+        let occurrence = seen.entry((label.clone(), name.clone())).or_insert(0);
+        *occurrence += 1;
+        let signature = signature_of(&item);
+        result.push(Symbol { key: (label, name, *occurrence), kind: item.kind, signature });
+    }
+    Ok(result)
+}
+
+fn display_name(item: &Item) -> Option<String> {
+    let name = item.name.as_deref()?;
+    Some(match (&item.kind, &item.parent) {
+        (ItemKind::Method | ItemKind::Variant | ItemKind::Field, Some(parent)) => format!("{}::{}", parent, name),
+        _ => name.to_string(),
+    })
+}
+
+/// Block items (impls, traits, classes) compare by header only; their methods
+/// are compared as separate items.
+fn signature_of(item: &Item) -> String {
+    match item.kind {
+        ItemKind::Impl | ItemKind::Trait | ItemKind::Class => {
+            one_line_signature(item.content.lines().find(|l| !l.trim_start().starts_with(['#', '@', '/'])).unwrap_or(""))
+        }
+        _ => one_line_signature(&item.content),
+    }
+}
@@ -0,0 +1,149 @@
+//! Trait-vs-impl contract view for Rust.
+//!
+//! Lists a trait's methods next to the methods of one type's impl of that trait,
+//! marking trait methods the impl does not define. Matching is by method name only.
+
+use crate::error::CodeviewError;
+use crate::languages::{self, Language};
+use crate::parser;
+use crate::read_source;
+use std::fmt::Write;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// A method declared in a trait or defined in an impl.
+struct Method {
+    name: String,
+    signature: String,
+    line: usize,
+    has_body: bool,
+}
+
+/// Render `trait_name`'s methods and the methods of `impl trait_name for type_name`
+/// from the Rust file at `path_str`. Required trait methods missing from the impl
+/// are marked `<- missing`; provided ones it leaves out are marked `(default)`.
+pub fn format_contract(path_str: &str, type_name: &str, trait_name: &str) -> Result<String, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    if path.is_dir() {
+        return Err(CodeviewError::InvalidPath(
+            "--contract only works on single files, not directories".to_string(),
+        ));
+    }
+    let language = languages::detect_language(path)?;
+    if language != Language::Rust {
+        return Err(CodeviewError::InvalidPath(
+            "--contract only works on Rust files".to_string(),
+        ));
+    }
+    let source = read_source(path, false)?;
+    let tree = parser::parse(&source, language)?;
+
+    let mut trait_node = None;
+    let mut impl_node = None;
+    find_nodes(tree.root_node(), &source, type_name, trait_name, &mut trait_node, &mut impl_node);
+    let trait_node = trait_node
+        .ok_or_else(|| CodeviewError::ParseError(format!("Trait not found: {}", trait_name)))?;
+    let impl_node = impl_node.ok_or_else(|| {
+        CodeviewError::ParseError(format!("No impl {} for {} found", trait_name, type_name))
+    })?;
+
+    let trait_methods = methods(trait_node, &source);
+    let impl_methods = methods(impl_node, &source);
+    let width = trait_methods
+        .iter()
+        .chain(impl_methods.iter())
+        .map(|m| m.signature.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = String::new();
+    writeln!(output, "trait {}  L{}", trait_name, trait_node.start_position().row + 1).unwrap();
+    for method in &trait_methods {
+        let implemented = impl_methods.iter().any(|m| m.name == method.name);
+        let marker = match (implemented, method.has_body) {
+            (true, _) => "",
+            (false, true) => "  (default)",
+            (false, false) => "  <- missing",
+        };
+        writeln!(output, "  {:<width$}  L{}{}", method.signature, method.line, marker).unwrap();
+    }
+
+    writeln!(output).unwrap();
+    writeln!(output, "impl {} for {}  L{}", trait_name, type_name, impl_node.start_position().row + 1).unwrap();
+    for method in &impl_methods {
+        writeln!(output, "  {:<width$}  L{}", method.signature, method.line).unwrap();
+    }
+
+    let missing: Vec<&str> = trait_methods
+        .iter()
+        .filter(|m| !m.has_body && !impl_methods.iter().any(|i| i.name == m.name))
+        .map(|m| m.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        writeln!(output).unwrap();
+        writeln!(output, "missing: {}", missing.join(", ")).unwrap();
+    }
+
+    Ok(output)
+}
+
+/// Find the first trait named `trait_name` and the first `impl trait_name for type_name`
+/// anywhere in the tree (including inside modules). Generic arguments and path
+/// prefixes are ignored when comparing names.
+fn find_nodes<'a>(
+    node: Node<'a>,
+    source: &str,
+    type_name: &str,
+    trait_name: &str,
+    trait_node: &mut Option<Node<'a>>,
+    impl_node: &mut Option<Node<'a>>,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "trait_item" if trait_node.is_none() => {
+                let name = child.child_by_field_name("name").map(|n| &source[n.byte_range()]);
+                if name == Some(trait_name) {
+                    *trait_node = Some(child);
+                }
+            }
+            "impl_item" if impl_node.is_none() => {
+                let field = |f: &str| child.child_by_field_name(f).map(|n| base_name(&source[n.byte_range()]));
+                if field("trait") == Some(trait_name) && field("type") == Some(type_name) {
+                    *impl_node = Some(child);
+                }
+            }
+            _ => find_nodes(child, source, type_name, trait_name, trait_node, impl_node),
+        }
+    }
+}
+
+/// `fmt::Display` → `Display`, `Wrapper<T>` → `Wrapper`.
+fn base_name(text: &str) -> &str {
+    let text = text.split('<').next().unwrap_or(text).trim();
+    text.rsplit("::").next().unwrap_or(text)
+}
+
+fn methods(block: Node, source: &str) -> Vec<Method> {
+    let Some(body) = block.child_by_field_name("body") else { return Vec::new() };
+    let mut cursor = body.walk();
+    body.children(&mut cursor)
+        .filter(|c| matches!(c.kind(), "function_item" | "function_signature_item"))
+        .filter_map(|c| {
+            let name = source[c.child_by_field_name("name")?.byte_range()].to_string();
+            let body = c.child_by_field_name("body");
+            // Header text up to the body (or `;`), joined onto one line
+            let header = &source[c.start_byte()..body.map_or(c.end_byte(), |b| b.start_byte())];
+            let signature = header.trim_end().trim_end_matches(';').split_whitespace().collect::<Vec<_>>().join(" ");
+            Some(Method {
+                name,
+                signature,
+                line: c.start_position().row + 1,
+                has_body: body.is_some(),
+            })
+        })
+        .collect()
+}
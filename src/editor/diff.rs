@@ -0,0 +1,170 @@
+//! Unified diffs of edit results.
+//!
+//! Lines are matched with a longest-common-subsequence table over the region
+//! between the unchanged prefix and suffix, which for symbol edits is small.
+
+use std::fmt::Write;
+
+/// Lines of context around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Unified diff from `old` to `new`, with `---`/`+++` headers naming `label`.
+/// Empty when the texts have the same lines.
+pub fn unified_diff(old: &str, new: &str, label: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&a, &b);
+    if ops.iter().all(|&op| op == Op::Equal) {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let label = label.trim_start_matches('/');
+    writeln!(out, "--- a/{}", label).unwrap();
+    writeln!(out, "+++ b/{}", label).unwrap();
+
+    // (old line index, new line index) at every op
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut i, mut j) = (0, 0);
+    for &op in &ops {
+        positions.push((i, j));
+        match op {
+            Op::Equal => {
+                i += 1;
+                j += 1;
+            }
+            Op::Delete => i += 1,
+            Op::Insert => j += 1,
+        }
+    }
+
+    let mut k = 0;
+    while k < ops.len() {
+        if ops[k] == Op::Equal {
+            k += 1;
+            continue;
+        }
+        // Grow the hunk until a run of more than 2 * CONTEXT equal lines
+        let start = k.saturating_sub(CONTEXT);
+        let mut end = k;
+        let mut equal_run = 0;
+        while end < ops.len() {
+            equal_run = if ops[end] == Op::Equal { equal_run + 1 } else { 0 };
+            end += 1;
+            if equal_run > 2 * CONTEXT {
+                break;
+            }
+        }
+        let end = (end - equal_run + CONTEXT.min(equal_run)).min(ops.len());
+
+        let (old_start, new_start) = positions[start];
+        let old_len = ops[start..end].iter().filter(|&&op| op != Op::Insert).count();
+        let new_len = ops[start..end].iter().filter(|&&op| op != Op::Delete).count();
+        writeln!(
+            out,
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_len),
+            hunk_range(new_start, new_len)
+        )
+        .unwrap();
+        for (n, &op) in ops[start..end].iter().enumerate() {
+            let (i, j) = positions[start + n];
+            match op {
+                Op::Equal => writeln!(out, " {}", a[i]).unwrap(),
+                Op::Delete => writeln!(out, "-{}", a[i]).unwrap(),
+                Op::Insert => writeln!(out, "+{}", b[j]).unwrap(),
+            }
+        }
+        k = end;
+    }
+    out
+}
+
+/// `start,len` of a hunk side, 1-based. An empty side names the line before it.
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+/// Edit script turning `a` into `b`.
+fn diff_ops(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    // lcs[i][j]: length of the LCS of a_mid[i..] and b_mid[j..]
+    let mut lcs = vec![vec![0usize; b_mid.len() + 1]; a_mid.len() + 1];
+    for i in (0..a_mid.len()).rev() {
+        for j in (0..b_mid.len()).rev() {
+            lcs[i][j] = if a_mid[i] == b_mid[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let mut ops = vec![Op::Equal; prefix];
+    let (mut i, mut j) = (0, 0);
+    while i < a_mid.len() || j < b_mid.len() {
+        if i < a_mid.len() && j < b_mid.len() && a_mid[i] == b_mid[j] {
+            ops.push(Op::Equal);
+            i += 1;
+            j += 1;
+        // Deletions come first within a changed run, as in diff(1)
+        } else if j < b_mid.len() && (i == a_mid.len() || lcs[i][j + 1] > lcs[i + 1][j]) {
+            ops.push(Op::Insert);
+            j += 1;
+        } else {
+            ops.push(Op::Delete);
+            i += 1;
+        }
+    }
+    ops.extend(std::iter::repeat_n(Op::Equal, suffix));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_line_change_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "a\nb\nc\nd\nE\nf\ng\nh\n";
+        assert_eq!(
+            unified_diff(old, new, "x.rs"),
+            "--- a/x.rs\n+++ b/x.rs\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let old: String = (1..=20).map(|n| format!("{}\n", n)).collect();
+        let new: String = (1..=20)
+            .filter(|&n| n != 19)
+            .map(|n| if n == 2 { "two\n".to_string() } else { format!("{}\n", n) })
+            .collect();
+        let diff = unified_diff(&old, &new, "f");
+        assert_eq!(diff.matches("@@ -").count(), 2, "{}", diff);
+        assert!(diff.contains("@@ -1,5 +1,5 @@\n 1\n-2\n+two\n"), "{}", diff);
+        assert!(diff.contains("-19\n"), "{}", diff);
+    }
+
+    #[test]
+    fn pure_insertion_and_identical() {
+        assert_eq!(unified_diff("a\n", "a\nb\n", "f"), "--- a/f\n+++ b/f\n@@ -1 +1,2 @@\n a\n+b\n");
+        assert_eq!(unified_diff("same\n", "same\n", "f"), "");
+    }
+}
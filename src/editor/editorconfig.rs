@@ -0,0 +1,180 @@
+//! Minimal `.editorconfig` support for edit indentation.
+//!
+//! Only the keys that affect re-indentation are understood: `indent_style`,
+//! `indent_size`, and `tab_width`. Files are read from the target file's
+//! directory upward until one declares `root = true`.
+
+use globset::GlobBuilder;
+use std::fs;
+use std::path::Path;
+
+/// Default indentation unit when no `.editorconfig` applies.
+pub const DEFAULT_INDENT: &str = "    ";
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct IndentProps {
+    style: Option<String>,
+    size: Option<String>,
+    tab_width: Option<usize>,
+}
+
+impl IndentProps {
+    fn merge(&mut self, other: IndentProps) {
+        if other.style.is_some() {
+            self.style = other.style;
+        }
+        if other.size.is_some() {
+            self.size = other.size;
+        }
+        if other.tab_width.is_some() {
+            self.tab_width = other.tab_width;
+        }
+    }
+
+    fn unit(&self) -> Option<String> {
+        if self.style.is_none() && self.size.is_none() {
+            return None;
+        }
+        if self.style.as_deref() == Some("tab") {
+            return Some("\t".to_string());
+        }
+        let size = match self.size.as_deref() {
+            Some("tab") => self.tab_width.unwrap_or(4),
+            Some(s) => s.parse().unwrap_or(4),
+            None => self.tab_width.unwrap_or(4),
+        };
+        Some(" ".repeat(size))
+    }
+}
+
+/// Resolve the indentation unit (one nesting level) for `path` from the nearest
+/// `.editorconfig` files. Returns `None` when no applicable settings are found.
+pub fn indent_unit_for(path: &Path) -> Option<String> {
+    let abs = fs::canonicalize(path).ok()?;
+    let mut configs = Vec::new();
+    let mut dir = abs.parent();
+    while let Some(d) = dir {
+        let candidate = d.join(".editorconfig");
+        if let Ok(text) = fs::read_to_string(&candidate) {
+            let is_root = is_root_config(&text);
+            configs.push((d.to_path_buf(), text));
+            if is_root {
+                break;
+            }
+        }
+        dir = d.parent();
+    }
+
+    // Farthest config first so nearer files override it
+    let mut props = IndentProps::default();
+    for (config_dir, text) in configs.iter().rev() {
+        let rel = abs.strip_prefix(config_dir).ok()?;
+        props.merge(parse_for_file(text, &rel.to_string_lossy()));
+    }
+    props.unit()
+}
+
+fn is_root_config(text: &str) -> bool {
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            return false;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim().eq_ignore_ascii_case("root") && value.trim().eq_ignore_ascii_case("true") {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Collect indentation properties from all sections of `text` matching `rel_path`.
+fn parse_for_file(text: &str, rel_path: &str) -> IndentProps {
+    let mut props = IndentProps::default();
+    let mut in_matching_section = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            in_matching_section = section_matches(&line[1..line.len() - 1], rel_path);
+            continue;
+        }
+        if !in_matching_section {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        let value = value.trim().to_lowercase();
+        match key.trim().to_lowercase().as_str() {
+            "indent_style" => props.style = Some(value),
+            "indent_size" => props.size = Some(value),
+            "tab_width" => props.tab_width = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    props
+}
+
+/// Match an editorconfig section glob against a path relative to the config's directory.
+/// Globs without a `/` match the file name at any depth.
+fn section_matches(pattern: &str, rel_path: &str) -> bool {
+    let pattern = pattern.trim_start_matches('/');
+    let pattern = if pattern.contains('/') {
+        pattern.to_string()
+    } else {
+        format!("**/{}", pattern)
+    };
+    GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .build()
+        .map(|g| g.compile_matcher().is_match(rel_path))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_matches_extension_any_depth() {
+        assert!(section_matches("*.rs", "src/deep/lib.rs"));
+        assert!(!section_matches("*.rs", "src/lib.ts"));
+    }
+
+    #[test]
+    fn section_matches_alternatives() {
+        assert!(section_matches("*.{ts,tsx}", "app.tsx"));
+        assert!(section_matches("*", "any/file.py"));
+    }
+
+    #[test]
+    fn parse_later_sections_override() {
+        let text = "[*]\nindent_style = space\nindent_size = 4\n\n[*.rs]\nindent_size = 2\n";
+        let props = parse_for_file(text, "main.rs");
+        assert_eq!(props.unit(), Some("  ".to_string()));
+        let props = parse_for_file(text, "main.py");
+        assert_eq!(props.unit(), Some("    ".to_string()));
+    }
+
+    #[test]
+    fn parse_tab_style() {
+        let props = parse_for_file("[*]\nindent_style = tab\n", "main.rs");
+        assert_eq!(props.unit(), Some("\t".to_string()));
+    }
+
+    #[test]
+    fn no_matching_section_yields_none() {
+        let props = parse_for_file("[*.py]\nindent_size = 2\n", "main.rs");
+        assert_eq!(props.unit(), None);
+    }
+
+    #[test]
+    fn root_detection() {
+        assert!(is_root_config("root = true\n[*]\n"));
+        assert!(!is_root_config("[*]\nroot = true\n"));
+    }
+}
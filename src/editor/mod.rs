@@ -1,3 +1,6 @@
+pub mod diff;
+pub mod editorconfig;
+
 use crate::error::CodeviewError;
 use crate::extractor::find_attr_start;
 use crate::languages::{ts_language, Language};
@@ -28,6 +31,149 @@ pub fn replace(
     Ok(result)
 }
 
+/// Replace lines `start_line..=end_line` (1-indexed) with `new_content`.
+/// The line break ending the range is kept. Returns the modified source code.
+pub fn replace_range(
+    source: &str,
+    start_line: usize,
+    end_line: usize,
+    new_content: &str,
+    language: Language,
+) -> Result<String, CodeviewError> {
+    let total_lines = source.lines().count();
+    if start_line == 0 || start_line > end_line {
+        return Err(CodeviewError::ParseError(format!(
+            "Invalid line range {}-{}", start_line, end_line
+        )));
+    }
+    if end_line > total_lines {
+        return Err(CodeviewError::ParseError(format!(
+            "Line range {}-{} is beyond end of file ({} lines)",
+            start_line, end_line, total_lines
+        )));
+    }
+
+    let line_start_byte = |line: usize| -> usize {
+        source.split_inclusive('\n').take(line - 1).map(str::len).sum()
+    };
+    let start_byte = line_start_byte(start_line);
+    let through_end = &source[..line_start_byte(end_line + 1)];
+    let end_byte = through_end
+        .strip_suffix("\r\n")
+        .or_else(|| through_end.strip_suffix('\n'))
+        .unwrap_or(through_end)
+        .len();
+
+    let mut result = String::new();
+    result.push_str(&source[..start_byte]);
+    result.push_str(new_content.strip_suffix('\n').unwrap_or(new_content));
+    result.push_str(&source[end_byte..]);
+
+    validate_result(&result, language)?;
+
+    Ok(result)
+}
+
+/// Insert `content` on its own line directly before a symbol (and its attributes).
+/// Returns the modified source code.
+pub fn insert_before(
+    source: &str,
+    symbol_name: &str,
+    content: &str,
+    language: Language,
+) -> Result<String, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    let (start_byte, _) = find_symbol_range(source, &tree, symbol_name, language)?;
+    let (at, text) = insertion_before(source, start_byte, content);
+    insert_at(source, at, &text, language)
+}
+
+/// Insert `content` on its own line directly after a symbol.
+/// Returns the modified source code.
+pub fn insert_after(
+    source: &str,
+    symbol_name: &str,
+    content: &str,
+    language: Language,
+) -> Result<String, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    let (_, end_byte) = find_symbol_range(source, &tree, symbol_name, language)?;
+    let (at, text) = insertion_after(source, end_byte, content);
+    insert_at(source, at, &text, language)
+}
+
+/// Where and what to insert for `content` before the symbol at `start_byte`:
+/// at the start of its line when only indentation precedes it.
+fn insertion_before(source: &str, start_byte: usize, content: &str) -> (usize, String) {
+    let line_start = source[..start_byte].rfind('\n').map_or(0, |i| i + 1);
+    let at = if source[line_start..start_byte].trim().is_empty() { line_start } else { start_byte };
+    (at, format!("{}\n", content.trim_end_matches('\n')))
+}
+
+/// Where and what to insert for `content` after the symbol ending at `end_byte`.
+/// A symbol ending the file also gets the trailing newline the file may lack.
+fn insertion_after(source: &str, end_byte: usize, content: &str) -> (usize, String) {
+    let content = content.trim_end_matches('\n');
+    if end_byte < source.len() {
+        (end_byte, format!("\n{}", content))
+    } else {
+        (end_byte, format!("\n{}\n", content))
+    }
+}
+
+/// Splice `text` into `source` at byte `at`, then validate.
+fn insert_at(source: &str, at: usize, text: &str, language: Language) -> Result<String, CodeviewError> {
+    let result = format!("{}{}{}", &source[..at], text, &source[at..]);
+    validate_result(&result, language)?;
+    Ok(result)
+}
+
+/// Rename a symbol's definition and every identifier with the same name in
+/// the file. Renaming is file-local and textual: same-named locals and fields
+/// are renamed too, and other files are not touched. Strings and comments are
+/// left alone, as they hold no identifier nodes.
+/// Returns the modified source code.
+pub fn rename(
+    source: &str,
+    old_name: &str,
+    new_name: &str,
+    language: Language,
+) -> Result<String, CodeviewError> {
+    let valid = new_name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && new_name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if !valid {
+        return Err(CodeviewError::ParseError(format!("Invalid identifier: {}", new_name)));
+    }
+    let tree = parser::parse(source, language)?;
+    // Fails with "Symbol not found" when there is no definition to rename
+    find_symbol_node(source, &tree, old_name, language)?;
+
+    let mut ranges = Vec::new();
+    collect_identifier_ranges(tree.root_node(), source, old_name, &mut ranges);
+    let mut result = source.to_string();
+    for (start, end) in ranges.into_iter().rev() {
+        result.replace_range(start..end, new_name);
+    }
+
+    validate_result(&result, language)?;
+    Ok(result)
+}
+
+/// Byte ranges of the identifier leaves (`identifier`, `type_identifier`,
+/// `field_identifier`, ...) spelled `name`, in source order.
+fn collect_identifier_ranges(node: Node, source: &str, name: &str, ranges: &mut Vec<(usize, usize)>) {
+    if node.child_count() == 0 {
+        if node.is_named() && node.kind().ends_with("identifier") && &source[node.byte_range()] == name {
+            ranges.push((node.start_byte(), node.end_byte()));
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_identifier_ranges(child, source, name, ranges);
+    }
+}
+
 /// Delete a symbol (including attributes).
 /// Returns the modified source code.
 pub fn delete(
@@ -37,7 +183,40 @@ pub fn delete(
 ) -> Result<String, CodeviewError> {
     let tree = parser::parse(source, language)?;
     let (start_byte, end_byte) = find_symbol_range(source, &tree, symbol_name, language)?;
-    
+    delete_span(source, start_byte, end_byte, language)
+}
+
+/// Delete a symbol but keep its leading attributes/decorators: the deletion
+/// starts at the item itself rather than at its first attribute.
+/// Returns the modified source code.
+pub fn delete_keep_attributes(
+    source: &str,
+    symbol_name: &str,
+    language: Language,
+) -> Result<String, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    let node = find_symbol_node(source, &tree, symbol_name, language)?;
+    let own = match node.kind() {
+        "decorated_definition" => node.child_by_field_name("definition").unwrap_or(node),
+        _ => node,
+    };
+    // Take the item's indentation too, so the following line keeps its own
+    let line_start = source[..own.start_byte()].rfind('\n').map_or(0, |i| i + 1);
+    let start_byte = if source[line_start..own.start_byte()].trim().is_empty() {
+        line_start
+    } else {
+        own.start_byte()
+    };
+    delete_span(source, start_byte, node.end_byte(), language)
+}
+
+/// Remove `start_byte..end_byte` plus one trailing newline, then validate.
+fn delete_span(
+    source: &str,
+    start_byte: usize,
+    end_byte: usize,
+    language: Language,
+) -> Result<String, CodeviewError> {
     // Find if there's a trailing newline to remove
     let mut effective_end = end_byte;
     if end_byte < source.len() && source.as_bytes()[end_byte] == b'\n' {
@@ -63,12 +242,24 @@ pub fn replace_body(
     symbol_name: &str,
     new_body: &str,
     language: Language,
+) -> Result<String, CodeviewError> {
+    replace_body_with_indent(source, symbol_name, new_body, language, editorconfig::DEFAULT_INDENT)
+}
+
+/// Like [`replace_body`], but uses `indent_unit` (e.g. two spaces or a tab) for
+/// the extra nesting level instead of the default four spaces.
+pub fn replace_body_with_indent(
+    source: &str,
+    symbol_name: &str,
+    new_body: &str,
+    language: Language,
+    indent_unit: &str,
 ) -> Result<String, CodeviewError> {
     let tree = parser::parse(source, language)?;
     let item_node = find_symbol_node(source, &tree, symbol_name, language)?;
     
     let body_node = find_body_node(item_node, language)?;
-    let body_start = body_node.start_byte();
+    let mut body_start = body_node.start_byte();
     let body_end = body_node.end_byte();
     
     // Detect indent level of the body's opening brace line
@@ -79,11 +270,21 @@ pub fn replace_body(
         .collect::<String>();
     
     // Build the new body block with proper indentation
-    let reindented = reindent_body(new_body, original_indent);
     let new_block = if language.uses_braces_for_blocks() {
+        let reindented = reindent_body(new_body, original_indent, indent_unit);
         format!("{{\n{}\n{}}}", reindented, original_indent)
     } else {
-        format!("\n{}", reindented)
+        // A Python block is indented one level under its `def`/`class` line
+        let header_start = source[..item_node.start_byte()].rfind('\n').map_or(0, |i| i + 1);
+        let header_indent: String = source[header_start..].chars().take_while(|c| *c == ' ' || *c == '\t').collect();
+        let reindented = reindent_body(new_body, &header_indent, indent_unit);
+        if line_start > header_start {
+            // Replace whole lines, from the first statement's indentation on
+            body_start = line_start;
+            reindented
+        } else {
+            format!("\n{}", reindented)
+        }
     };
     
     let mut result = String::new();
@@ -95,62 +296,254 @@ pub fn replace_body(
     Ok(result)
 }
 
+/// The inner content of a symbol's body block, without the braces (or, in
+/// Python, the header line) and dedented to column 0. This is the form
+/// [`replace_body`] takes, so the two round-trip.
+pub fn body_text(source: &str, symbol_name: &str, language: Language) -> Result<String, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    let item_node = find_symbol_node(source, &tree, symbol_name, language)?;
+    let body_node = find_body_node(item_node, language)?;
+    let inner = if language.uses_braces_for_blocks() {
+        &source[body_node.start_byte() + 1..body_node.end_byte() - 1]
+    } else {
+        // From the start of the first statement's line, so it keeps its indent,
+        // unless the body shares the header's line (`def f(): pass`)
+        let line_start = source[..body_node.start_byte()].rfind('\n').map_or(0, |i| i + 1);
+        let start = if line_start > item_node.start_byte() { line_start } else { body_node.start_byte() };
+        &source[start..body_node.end_byte()]
+    };
+
+    let lines: Vec<&str> = inner.lines().map(str::trim_end).collect();
+    let first = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let last = lines.iter().rposition(|l| !l.is_empty()).map_or(first, |i| i + 1);
+    let lines = &lines[first..last];
+    let min_indent = lines
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut body = String::new();
+    for line in lines {
+        body.push_str(line.get(min_indent..).unwrap_or(""));
+        body.push('\n');
+    }
+    Ok(body)
+}
+
+/// Append a method to the end of an impl or class body. `method_source` is
+/// re-indented one level inside the container; in brace languages it goes
+/// before the closing brace, in Python after the last statement.
+/// Returns the modified source code.
+pub fn add_method(
+    source: &str,
+    container_name: &str,
+    method_source: &str,
+    language: Language,
+) -> Result<String, CodeviewError> {
+    add_method_with_indent(source, container_name, method_source, language, editorconfig::DEFAULT_INDENT)
+}
+
+/// Like [`add_method`], but indents the method with `indent_unit`.
+pub fn add_method_with_indent(
+    source: &str,
+    container_name: &str,
+    method_source: &str,
+    language: Language,
+    indent_unit: &str,
+) -> Result<String, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    let container = match find_container_node(tree.root_node(), source, container_name) {
+        Some(node) => node,
+        None => {
+            let node = find_symbol_node(source, &tree, container_name, language)?;
+            return Err(CodeviewError::ParseError(format!(
+                "Symbol '{}' is not an impl or class (kind: {})", container_name, node.kind()
+            )));
+        }
+    };
+    let body = container.child_by_field_name("body").ok_or_else(|| {
+        CodeviewError::ParseError(format!("Symbol has no body block (kind: {})", container.kind()))
+    })?;
+
+    // Detect indent level of the container's first line
+    let line_start = source[..container.start_byte()].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let original_indent = &source[line_start..container.start_byte()]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect::<String>();
+    let reindented = reindent_body(method_source, original_indent, indent_unit);
+
+    let (at, text) = if language.uses_braces_for_blocks() {
+        let close = body.end_byte() - 1;
+        let close_line = source[..close].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let has_members = body.named_child_count() > 0;
+        if close_line > body.start_byte() && source[close_line..close].trim().is_empty() {
+            (close_line, format!("{}{}\n", if has_members { "\n" } else { "" }, reindented))
+        } else {
+            (close, format!("\n{}\n{}", reindented, original_indent))
+        }
+    } else {
+        (body.end_byte(), format!("\n\n{}", reindented))
+    };
+
+    let result = format!("{}{}{}", &source[..at], text, &source[at..]);
+    validate_result(&result, language)?;
+    Ok(result)
+}
+
+/// The first impl/trait/class node named `name`, preferring an inherent Rust
+/// impl over trait impls for the same type.
+fn find_container_node<'a>(root: Node<'a>, source: &str, name: &str) -> Option<Node<'a>> {
+    let mut found: Vec<Node<'a>> = Vec::new();
+    collect_containers(root, source, name, &mut found);
+    found
+        .iter()
+        .copied()
+        .find(|n| n.child_by_field_name("trait").is_none())
+        .or_else(|| found.first().copied())
+}
+
+fn collect_containers<'a>(node: Node<'a>, source: &str, name: &str, found: &mut Vec<Node<'a>>) {
+    let container_name = match node.kind() {
+        "impl_item" => node.child_by_field_name("type"),
+        "trait_item" | "class_declaration" | "abstract_class_declaration" | "class" | "class_definition" => {
+            node.child_by_field_name("name")
+        }
+        _ => None,
+    };
+    if let Some(n) = container_name {
+        let text = &source[n.byte_range()];
+        if text.split('<').next().unwrap_or(text).trim() == name {
+            found.push(node);
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_containers(child, source, name, found);
+    }
+}
+
+/// Replace a symbol's signature (attributes, parameters, return type) while keeping its body.
+/// The replaced span runs from the symbol's start (including attributes) up to the
+/// body's opening brace, or through the `:` in Python. `new_signature` is the header
+/// without the body opener, e.g. `fn foo(a: u32) -> u32` or `def foo(a, b)`.
+pub fn replace_signature(
+    source: &str,
+    symbol_name: &str,
+    new_signature: &str,
+    language: Language,
+) -> Result<String, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    let item_node = find_symbol_node(source, &tree, symbol_name, language)?;
+    let (start_byte, _) = find_attr_start(item_node);
+    let body_node = find_body_node(item_node, language)?;
+
+    let new_signature = new_signature.trim_end();
+    let (end_byte, replacement) = if language.uses_braces_for_blocks() {
+        let signature = new_signature.trim_end_matches('{').trim_end();
+        (body_node.start_byte(), format!("{} ", signature))
+    } else {
+        // The `:` token directly before the body block
+        let parent = body_node.parent().unwrap_or(item_node);
+        let mut cursor = parent.walk();
+        let colon = parent
+            .children(&mut cursor)
+            .filter(|c| c.kind() == ":" && c.end_byte() <= body_node.start_byte())
+            .last()
+            .ok_or_else(|| CodeviewError::ParseError(format!("Symbol has no ':' before its body: {}", symbol_name)))?;
+        (colon.end_byte(), format!("{}:", new_signature.trim_end_matches(':')))
+    };
+
+    let mut result = String::new();
+    result.push_str(&source[..start_byte]);
+    result.push_str(&replacement);
+    result.push_str(&source[end_byte..]);
+
+    validate_result(&result, language)?;
+    Ok(result)
+}
+
+/// Remove top-level imports (`use`/`import` items) whose text repeats an earlier
+/// one, ignoring whitespace differences. Only exact duplicates are removed.
+pub fn dedup_imports(source: &str, language: Language) -> Result<String, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    let duplicates = find_duplicate_imports(source, &tree, language);
+
+    let mut result = String::new();
+    let mut pos = 0;
+    for node in &duplicates {
+        let start = source[..node.start_byte()].rfind('\n').map_or(0, |i| i + 1);
+        // Drop the whole line when the import is alone on it
+        let start = if source[start..node.start_byte()].trim().is_empty() { start } else { node.start_byte() };
+        let mut end = node.end_byte();
+        if source[end..].starts_with('\n') {
+            end += 1;
+        }
+        result.push_str(&source[pos..start]);
+        pos = end;
+    }
+    result.push_str(&source[pos..]);
+
+    validate_result(&result, language)?;
+    Ok(result)
+}
+
+/// 1-based line ranges of the imports [`dedup_imports`] would remove.
+pub fn duplicate_import_lines(source: &str, language: Language) -> Result<Vec<(usize, usize)>, CodeviewError> {
+    let tree = parser::parse(source, language)?;
+    Ok(find_duplicate_imports(source, &tree, language)
+        .iter()
+        .map(|n| (n.start_position().row + 1, n.end_position().row + 1))
+        .collect())
+}
+
+/// Top-level import nodes whose whitespace-normalized text already appeared, in source order.
+fn find_duplicate_imports<'a>(source: &str, tree: &'a Tree, language: Language) -> Vec<Node<'a>> {
+    let extractor = crate::extractor::extractor_for(language);
+    let root = tree.root_node();
+    let mut seen = std::collections::HashSet::new();
+    let mut duplicates = Vec::new();
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        if extractor.node_kind_to_item_kind(child.kind()) != Some(crate::extractor::ItemKind::Use) {
+            continue;
+        }
+        let normalized = source[child.byte_range()].split_whitespace().collect::<Vec<_>>().join(" ");
+        if !seen.insert(normalized) {
+            duplicates.push(child);
+        }
+    }
+    duplicates
+}
+
 /// Apply multiple edits to a file in one pass.
 /// Edits are applied bottom-to-top so byte offsets remain valid.
 pub fn batch(
     source: &str,
     edits: &[BatchEdit],
     language: Language,
+) -> Result<String, CodeviewError> {
+    batch_with_indent(source, edits, language, editorconfig::DEFAULT_INDENT)
+}
+
+/// Like [`batch`], but re-indents replaced bodies with `indent_unit`.
+pub fn batch_with_indent(
+    source: &str,
+    edits: &[BatchEdit],
+    language: Language,
+    indent_unit: &str,
 ) -> Result<String, CodeviewError> {
     // Resolve all byte ranges first, before any mutations
     let tree = parser::parse(source, language)?;
     let mut resolved: Vec<ResolvedEdit> = Vec::new();
     
     for edit in edits {
-        match edit.action {
-            BatchAction::Replace => {
-                let content = edit.content.as_deref().ok_or_else(|| {
-                    CodeviewError::ParseError(format!(
-                        "Missing 'content' for replace action on '{}'", edit.symbol
-                    ))
-                })?;
-                let (start, end) = find_symbol_range(source, &tree, &edit.symbol, language)?;
-                resolved.push(ResolvedEdit { start, end, replacement: content.to_string() });
-            }
-            BatchAction::ReplaceBody => {
-                let content = edit.content.as_deref().ok_or_else(|| {
-                    CodeviewError::ParseError(format!(
-                        "Missing 'content' for replace-body action on '{}'", edit.symbol
-                    ))
-                })?;
-                let item_node = find_symbol_node(source, &tree, &edit.symbol, language)?;
-                let body_node = find_body_node(item_node, language)?;
-                let body_start = body_node.start_byte();
-                let body_end = body_node.end_byte();
-                
-                let line_start = source[..body_start].rfind('\n').map(|i| i + 1).unwrap_or(0);
-                let original_indent = &source[line_start..body_start]
-                    .chars()
-                    .take_while(|c| c.is_whitespace())
-                    .collect::<String>();
-                let reindented = reindent_body(content, original_indent);
-                let new_block = format!("{{\n{}\n{}}}", reindented, original_indent);
-                
-                resolved.push(ResolvedEdit { start: body_start, end: body_end, replacement: new_block });
-            }
-            BatchAction::Delete => {
-                let (start, end) = find_symbol_range(source, &tree, &edit.symbol, language)?;
-                let mut effective_end = end;
-                if effective_end < source.len() && source.as_bytes()[effective_end] == b'\n' {
-                    effective_end += 1;
-                }
-                resolved.push(ResolvedEdit { start, end: effective_end, replacement: String::new() });
-            }
-        }
+        resolved.push(resolve_edit(source, &tree, edit, language, indent_unit)?);
     }
     
     // Sort by start byte descending (bottom-to-top) so earlier offsets stay valid
-    resolved.sort_by(|a, b| b.start.cmp(&a.start));
+    resolved.sort_by_key(|e| std::cmp::Reverse(e.start));
     
     // Check for overlapping ranges
     for w in resolved.windows(2) {
@@ -171,6 +564,141 @@ pub fn batch(
     Ok(result)
 }
 
+/// Batch edits applied one at a time to a source held in memory. Each edit is
+/// resolved against the current text, and the tree is updated with an
+/// incremental re-parse instead of parsing the whole file again, which keeps
+/// long edit sequences on large files cheap.
+pub struct BatchSession {
+    source: String,
+    tree: Tree,
+    parser: tree_sitter::Parser,
+    language: Language,
+    indent_unit: String,
+}
+
+impl BatchSession {
+    pub fn new(source: &str, language: Language) -> Result<BatchSession, CodeviewError> {
+        BatchSession::with_indent(source, language, editorconfig::DEFAULT_INDENT)
+    }
+
+    /// Like [`BatchSession::new`], but re-indents replaced bodies with `indent_unit`.
+    pub fn with_indent(source: &str, language: Language, indent_unit: &str) -> Result<BatchSession, CodeviewError> {
+        let mut parser = parser::new_parser(language)?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| CodeviewError::ParseError("Failed to parse source code".to_string()))?;
+        Ok(BatchSession { source: source.to_string(), tree, parser, language, indent_unit: indent_unit.to_string() })
+    }
+
+    /// Apply `edit` to the current source. An edit that cannot be resolved or
+    /// leaves invalid syntax is rejected, and the session keeps its previous state.
+    pub fn apply(&mut self, edit: &BatchEdit) -> Result<(), CodeviewError> {
+        let resolved = resolve_edit(&self.source, &self.tree, edit, self.language, &self.indent_unit)?;
+        let source = format!("{}{}{}", &self.source[..resolved.start], resolved.replacement, &self.source[resolved.end..]);
+        let new_end = resolved.start + resolved.replacement.len();
+
+        let mut old_tree = self.tree.clone();
+        old_tree.edit(&tree_sitter::InputEdit {
+            start_byte: resolved.start,
+            old_end_byte: resolved.end,
+            new_end_byte: new_end,
+            start_position: point_at(&self.source, resolved.start),
+            old_end_position: point_at(&self.source, resolved.end),
+            new_end_position: point_at(&source, new_end),
+        });
+        let tree = self
+            .parser
+            .parse(&source, Some(&old_tree))
+            .ok_or_else(|| CodeviewError::ParseError("Failed to parse source code".to_string()))?;
+        if tree.root_node().has_error() {
+            return Err(CodeviewError::ParseError("Edit resulted in invalid syntax".to_string()));
+        }
+        self.source = source;
+        self.tree = tree;
+        Ok(())
+    }
+
+    /// The source with every applied edit.
+    pub fn finish(self) -> String {
+        self.source
+    }
+}
+
+/// Row and byte column of byte offset `byte` in `text`.
+fn point_at(text: &str, byte: usize) -> tree_sitter::Point {
+    let before = &text[..byte];
+    let row = before.matches('\n').count();
+    let column = before.rfind('\n').map_or(byte, |i| byte - i - 1);
+    tree_sitter::Point { row, column }
+}
+
+/// Byte range and replacement text of `edit` against `source` as parsed into `tree`.
+fn resolve_edit(
+    source: &str,
+    tree: &Tree,
+    edit: &BatchEdit,
+    language: Language,
+    indent_unit: &str,
+) -> Result<ResolvedEdit, CodeviewError> {
+    match edit.action {
+        BatchAction::Replace => {
+            let content = edit.content.as_deref().ok_or_else(|| {
+                CodeviewError::ParseError(format!(
+                    "Missing 'content' for replace action on '{}'", edit.symbol
+                ))
+            })?;
+            let (start, end) = find_symbol_range(source, tree, &edit.symbol, language)?;
+            Ok(ResolvedEdit { start, end, replacement: content.to_string() })
+        }
+        BatchAction::ReplaceBody => {
+            let content = edit.content.as_deref().ok_or_else(|| {
+                CodeviewError::ParseError(format!(
+                    "Missing 'content' for replace-body action on '{}'", edit.symbol
+                ))
+            })?;
+            let item_node = find_symbol_node(source, tree, &edit.symbol, language)?;
+            let body_node = find_body_node(item_node, language)?;
+            let body_start = body_node.start_byte();
+            let body_end = body_node.end_byte();
+            
+            let line_start = source[..body_start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let original_indent = &source[line_start..body_start]
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .collect::<String>();
+            let reindented = reindent_body(content, original_indent, indent_unit);
+            let new_block = format!("{{\n{}\n{}}}", reindented, original_indent);
+            
+            Ok(ResolvedEdit { start: body_start, end: body_end, replacement: new_block })
+        }
+        BatchAction::InsertBefore | BatchAction::InsertAfter => {
+            let before = matches!(edit.action, BatchAction::InsertBefore);
+            let content = edit.content.as_deref().ok_or_else(|| {
+                CodeviewError::ParseError(format!(
+                    "Missing 'content' for {} action on '{}'",
+                    if before { "insert-before" } else { "insert-after" },
+                    edit.symbol
+                ))
+            })?;
+            let (start, end) = find_symbol_range(source, tree, &edit.symbol, language)?;
+            let (at, text) = if before {
+                insertion_before(source, start, content)
+            } else {
+                insertion_after(source, end, content)
+            };
+            Ok(ResolvedEdit { start: at, end: at, replacement: text })
+        }
+        BatchAction::Delete => {
+            let (start, end) = find_symbol_range(source, tree, &edit.symbol, language)?;
+            let mut effective_end = end;
+            if effective_end < source.len() && source.as_bytes()[effective_end] == b'\n' {
+                effective_end += 1;
+            }
+            Ok(ResolvedEdit { start, end: effective_end, replacement: String::new() })
+        }
+    }
+}
+
 /// Result metadata for a single edit operation (used with --json output).
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct EditResult {
@@ -178,6 +706,9 @@ pub struct EditResult {
     pub action: String,
     pub line_start: usize,
     pub line_end: usize,
+    /// Unified diff of the edit, with `--diff`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
 }
 
 /// Get the 1-based line range of a symbol (including attributes).
@@ -207,6 +738,8 @@ pub enum BatchAction {
     Replace,
     ReplaceBody,
     Delete,
+    InsertBefore,
+    InsertAfter,
 }
 
 struct ResolvedEdit {
@@ -245,9 +778,9 @@ fn find_body_node<'a>(item_node: Node<'a>, language: Language) -> Result<Node<'a
 }
 
 /// Re-indent body content to match the target indent level.
-/// Each non-empty line gets `base_indent + one level (4 spaces)`.
-fn reindent_body(body: &str, base_indent: &str) -> String {
-    let inner_indent = format!("{}    ", base_indent);
+/// Each non-empty line gets `base_indent + one level (indent_unit)`.
+fn reindent_body(body: &str, base_indent: &str, indent_unit: &str) -> String {
+    let inner_indent = format!("{}{}", base_indent, indent_unit);
     
     // Detect the minimum indent of the input to strip it
     let min_indent = body.lines()
@@ -344,6 +877,18 @@ fn validate_result(source: &str, language: Language) -> Result<(), CodeviewError
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn batch_session_tree_matches_a_fresh_parse() {
+        let source = "fn a() {\n    1\n}\n\nfn b() {\n    2\n}\n";
+        let mut session = BatchSession::new(source, Language::Rust).unwrap();
+        let edit = |symbol: &str, action, content: &str| BatchEdit { symbol: symbol.to_string(), action, content: Some(content.to_string()) };
+        session.apply(&edit("a", BatchAction::ReplaceBody, "let long_name = 10;\nlong_name")).unwrap();
+        session.apply(&edit("b", BatchAction::InsertAfter, "fn c() {}")).unwrap();
+        let fresh = parser::parse(&session.source, Language::Rust).unwrap();
+        assert_eq!(session.tree.root_node().to_sexp(), fresh.root_node().to_sexp());
+        assert_eq!(session.tree.root_node().end_position(), fresh.root_node().end_position());
+    }
     
     #[test]
     fn test_replace_function() {
@@ -21,9 +21,28 @@ pub enum CodeviewError {
         source: std::io::Error,
     },
     
+    #[error("{0}: file is not valid UTF-8")]
+    InvalidUtf8(String),
+
     #[error("Parse error: {0}")]
     ParseError(String),
     
     #[error("Serialization error")]
     SerializationError(#[from] serde_json::Error),
 }
+
+impl CodeviewError {
+    /// Stable snake_case name of the error variant, for machine-readable reports.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CodeviewError::PathNotFound(_) => "path_not_found",
+            CodeviewError::InvalidPath(_) => "invalid_path",
+            CodeviewError::UnsupportedExtension(_) => "unsupported_extension",
+            CodeviewError::NoExtension(_) => "no_extension",
+            CodeviewError::ReadError { .. } => "read_error",
+            CodeviewError::InvalidUtf8(_) => "invalid_utf8",
+            CodeviewError::ParseError(_) => "parse_error",
+            CodeviewError::SerializationError(_) => "serialization_error",
+        }
+    }
+}
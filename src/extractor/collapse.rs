@@ -3,6 +3,108 @@
 //! Language-agnostic text surgery for producing interface views.
 
 use tree_sitter::Node;
+
+/// How function bodies are rendered in interface views.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BodyStyle {
+    /// Replace the whole body with `{ ... }`.
+    #[default]
+    Collapsed,
+    /// Keep the first N body lines, then a `...` marker.
+    Preview(usize),
+    /// Keep the whole body.
+    Full,
+}
+
+/// Render a single item's body according to `style`.
+pub fn render_body(
+    source: &str,
+    item_start: usize,
+    item_end: usize,
+    body_start: usize,
+    body_end: usize,
+    style: BodyStyle,
+) -> (String, Vec<(usize, String)>) {
+    match style {
+        BodyStyle::Collapsed => collapse_body(source, item_start, item_end, body_start, body_end),
+        BodyStyle::Preview(n) => {
+            let omitted: Vec<_> = omitted_body_lines(source, body_start, body_end, n).into_iter().collect();
+            render_omitting(source, item_start, item_end, &omitted)
+        }
+        BodyStyle::Full => render_omitting(source, item_start, item_end, &[]),
+    }
+}
+
+/// Render every function body inside an impl/trait/class block according to `style`.
+pub fn render_block(source: &str, start_byte: usize, block_node: Node, style: BodyStyle) -> (String, Vec<(usize, String)>) {
+    match style {
+        BodyStyle::Collapsed => collapse_block(source, start_byte, block_node),
+        BodyStyle::Preview(n) => {
+            let mut body_ranges: Vec<(usize, usize)> = Vec::new();
+            collect_fn_bodies(block_node, &mut body_ranges);
+            body_ranges.sort_by_key(|&(s, _)| s);
+            let omitted: Vec<_> = body_ranges
+                .iter()
+                .filter_map(|&(s, e)| omitted_body_lines(source, s, e, n))
+                .collect();
+            render_omitting(source, start_byte, block_node.end_byte(), &omitted)
+        }
+        BodyStyle::Full => render_omitting(source, start_byte, block_node.end_byte(), &[]),
+    }
+}
+
+/// Replace the interior of each body in `body_ranges` with a single `...` line,
+/// keeping the surrounding text of `source[start_byte..end_byte]` intact.
+/// Bodies that fit on their header line are left as-is.
+pub fn collapse_nested(source: &str, start_byte: usize, end_byte: usize, body_ranges: &[(usize, usize)]) -> (String, Vec<(usize, String)>) {
+    let omitted: Vec<_> = body_ranges
+        .iter()
+        .filter_map(|&(s, e)| omitted_body_lines(source, s, e, 0))
+        .collect();
+    render_omitting(source, start_byte, end_byte, &omitted)
+}
+
+/// Source lines (0-indexed, inclusive) of a body that fall past its first `keep` lines.
+/// Returns `None` when the body is short enough to show in full.
+fn omitted_body_lines(source: &str, body_start: usize, body_end: usize, keep: usize) -> Option<(usize, usize)> {
+    let open_line = source[..body_start].matches('\n').count();
+    let close_line = source[..body_end.saturating_sub(1).max(body_start)].matches('\n').count();
+    let line_prefix = &source[source[..body_start].rfind('\n').map_or(0, |i| i + 1)..body_start];
+    // Braced bodies keep their `{` and `}` lines; indentation-based bodies start at the first
+    // statement, unless it shares the header line (`def f(): return 1`)
+    let (first, last) = if source[body_start..].starts_with('{') {
+        (open_line + 1, close_line.checked_sub(1)?)
+    } else if !line_prefix.trim().is_empty() {
+        (open_line + 1, close_line)
+    } else {
+        (open_line, close_line)
+    };
+    let drop_from = first + keep;
+    (drop_from <= last).then_some((drop_from, last))
+}
+
+/// Copy `source[start_byte..end_byte]` line by line, replacing each omitted line range
+/// with a single `...` line indented like the first line it stands for.
+fn render_omitting(source: &str, start_byte: usize, end_byte: usize, omitted: &[(usize, usize)]) -> (String, Vec<(usize, String)>) {
+    let first_line = source[..start_byte].matches('\n').count();
+    let mut mappings = Vec::new();
+
+    for (i, line) in source[start_byte..end_byte].lines().enumerate() {
+        let line_idx = first_line + i;
+        match omitted.iter().find(|&&(s, e)| line_idx >= s && line_idx <= e) {
+            Some(&(s, _)) if line_idx == s => {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                mappings.push((line_idx + 1, format!("{}...", indent)));
+            }
+            Some(_) => {}
+            None => mappings.push((line_idx + 1, line.to_string())),
+        }
+    }
+
+    let content = mappings.iter().map(|(_, l)| l.as_str()).collect::<Vec<_>>().join("\n");
+    (content, mappings)
+}
+
 pub fn collapse_body(
     source: &str,
     item_start: usize,
@@ -216,4 +318,31 @@ mod tests {
         assert!(collapsed.contains("{ ... }"));
         assert_eq!(mappings[0].0, 2); // fn is on line 2
     }
+
+    #[test]
+    fn render_body_preview_keeps_first_lines() {
+        let source = "fn foo() {\n    a();\n    b();\n    c();\n    d();\n}";
+        let body_start = source.find('{').unwrap();
+        let (content, mappings) = render_body(source, 0, source.len(), body_start, source.len(), BodyStyle::Preview(2));
+        assert_eq!(content, "fn foo() {\n    a();\n    b();\n    ...\n}");
+        assert_eq!(mappings[3], (4, "    ...".to_string()));
+        assert_eq!(mappings[4].0, 6);
+    }
+
+    #[test]
+    fn render_body_preview_short_body_unchanged() {
+        let source = "fn foo() {\n    a();\n}";
+        let body_start = source.find('{').unwrap();
+        let (content, _) = render_body(source, 0, source.len(), body_start, source.len(), BodyStyle::Preview(3));
+        assert_eq!(content, source);
+    }
+
+    #[test]
+    fn render_body_preview_indented_body() {
+        let source = "def f():\n    a = 1\n    b = 2\n    return a\n";
+        let body_start = source.find("a =").unwrap();
+        let body_end = source.len() - 1;
+        let (content, _) = render_body(source, 0, body_end, body_start, body_end, BodyStyle::Preview(1));
+        assert_eq!(content, "def f():\n    a = 1\n    ...");
+    }
 }
@@ -1,15 +1,41 @@
-use super::collapse::{collapse_block, build_source_line_mappings};
-use super::{extractor_for, find_attr_start, Item, ItemKind, Visibility, LanguageExtractor};
+use super::collapse::{collapse_block, collapse_nested, build_collapsed_block_mappings_pub, build_source_line_mappings};
+use super::{decorators, extractor_for, find_attr_start, type_parameters, Item, ItemKind, Visibility, LanguageExtractor};
 use crate::languages::{ts_language, Language};
+use std::borrow::Cow;
 use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
 
+/// A byte range of the source to replace with a placeholder.
+type Splice = (usize, usize, Cow<'static, str>);
+
+/// What to collapse inside otherwise-expanded symbols.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExpandStyle {
+    /// Collapse the bodies of definitions nested inside each symbol (inner functions, methods).
+    pub nest_summary: bool,
+    /// Collapse match/switch arm bodies, keeping their patterns.
+    pub collapse_match: bool,
+    /// Abbreviate string literals longer than this many characters.
+    pub collapse_strings: Option<usize>,
+    /// Collapse blocks nested more than this many levels inside the body.
+    pub body_depth: Option<usize>,
+    /// Show the submodules of an expanded Rust module in interface form (function bodies collapsed).
+    pub recursive_expand: bool,
+    /// Strip the indentation every line of a symbol shares, keeping relative indentation.
+    pub dedent: bool,
+}
+
 /// Extract full implementation for specified symbols using tree-sitter queries.
 pub fn extract(source: &str, tree: &Tree, symbols: &[String], language: Language) -> Vec<Item> {
+    extract_with_style(source, tree, symbols, language, ExpandStyle::default())
+}
+
+/// Like [`extract`], collapsing the parts of each symbol selected by `style`.
+pub fn extract_with_style(source: &str, tree: &Tree, symbols: &[String], language: Language, style: ExpandStyle) -> Vec<Item> {
     let extractor = extractor_for(language);
-    extract_with_extractor(source, tree, symbols, language, extractor.as_ref())
+    extract_with_extractor(source, tree, symbols, language, extractor.as_ref(), style)
 }
 
-fn extract_with_extractor(source: &str, tree: &Tree, symbols: &[String], language: Language, extractor: &dyn LanguageExtractor) -> Vec<Item> {
+fn extract_with_extractor(source: &str, tree: &Tree, symbols: &[String], language: Language, extractor: &dyn LanguageExtractor, style: ExpandStyle) -> Vec<Item> {
     let ts_lang = ts_language(language);
     let query = Query::new(&ts_lang, extractor.expand_query())
         .expect("expand_query should compile");
@@ -43,14 +69,17 @@ fn extract_with_extractor(source: &str, tree: &Tree, symbols: &[String], languag
             Some(n) => n.as_str(),
             None => continue,
         };
-        if !symbols.iter().any(|s| s == name_str) {
+        let accepted = symbols.iter().any(|s| match split_qualified(s) {
+            (Some(qualifier), bare) => bare == name_str && enclosing_type_names(item_node, source, extractor).iter().any(|t| t == qualifier),
+            (None, bare) => bare == name_str,
+        });
+        if !accepted {
             continue;
         }
 
         let (effective_start_byte, line_start) = find_attr_start(item_node);
         let line_end = item_node.end_position().row + 1;
 
-        let content = source[effective_start_byte..item_node.end_byte()].to_string();
         let visibility = Visibility::from_parent(item_node, source);
 
         let kind = match extractor.node_kind_to_item_kind(item_node.kind()) {
@@ -58,6 +87,52 @@ fn extract_with_extractor(source: &str, tree: &Tree, symbols: &[String], languag
             None => continue,
         };
 
+        let mut nested_bodies = Vec::new();
+        if style.nest_summary {
+            if let Some(body) = definition_body(item_node) {
+                collect_nested_def_bodies(body, &mut nested_bodies);
+            }
+        }
+        let mut splices: Vec<Splice> = Vec::new();
+        if style.collapse_match {
+            if let Some(body) = definition_body(item_node) {
+                collect_match_arms(body, &mut splices);
+            }
+        }
+        if let Some(max_chars) = style.collapse_strings {
+            collect_long_strings(item_node, source, max_chars, &mut splices);
+        }
+        let placeholder = if language == Language::Python { "..." } else { "{ ... }" };
+        if let Some(max_depth) = style.body_depth {
+            if let Some(body) = definition_body(item_node) {
+                collect_deep_blocks(body, 0, max_depth, placeholder, &mut splices);
+            }
+        }
+        if style.recursive_expand && item_node.kind() == "mod_item" {
+            if let Some(body) = definition_body(item_node) {
+                collect_submodule_fn_bodies(body, false, &mut splices);
+            }
+        }
+        let (content, line_mappings) = if !splices.is_empty() {
+            // Nested definition bodies become `{ ... }` too, so everything is spliced in one pass
+            splices.extend(nested_bodies.iter().map(|&(s, e)| (s, e, Cow::Borrowed(placeholder))));
+            let (c, m) = apply_splices(source, effective_start_byte, item_node.end_byte(), &mut splices);
+            (c, Some(m))
+        } else if nested_bodies.is_empty() {
+            (source[effective_start_byte..item_node.end_byte()].to_string(), None)
+        } else {
+            let (c, m) = collapse_nested(source, effective_start_byte, item_node.end_byte(), &nested_bodies);
+            (c, Some(m))
+        };
+        let (content, line_mappings) = if style.dedent {
+            let line_begin = source[..effective_start_byte].rfind('\n').map_or(0, |i| i + 1);
+            let prefix = &source[line_begin..effective_start_byte];
+            let first_indent = if prefix.trim().is_empty() { prefix.len() } else { 0 };
+            dedent(content, line_mappings, first_indent)
+        } else {
+            (content, line_mappings)
+        };
+
         items.push(Item {
             kind,
             name,
@@ -67,14 +142,218 @@ fn extract_with_extractor(source: &str, tree: &Tree, symbols: &[String], languag
             signature: None,
             body: None,
             content,
-            line_mappings: None,
+            line_mappings,
+            parameters: None,
+            type_parameters: type_parameters(item_node, source),
+            return_doc: None,
+            source_module: None,
+            resolved: None,
+            parent: None,
+            node_kind: item_node.kind().to_string(),
+            is_test: extractor.is_test(item_node, source),
+            modifiers: extractor.modifiers(item_node, source),
+            decorators: decorators(item_node, source),
         });
+        items.extend(extractor.extract_members(source, item_node));
     }
 
     items.sort_by_key(|item| item.line_start);
     items
 }
 
+/// Remove the indentation shared by every line of `content`. The first line
+/// starts mid-line, so its indentation is `first_indent`, the column it starts at.
+fn dedent(content: String, line_mappings: Option<Vec<(usize, String)>>, first_indent: usize) -> (String, Option<Vec<(usize, String)>>) {
+    let indent_of = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let common = content
+        .lines()
+        .skip(1)
+        .filter(|l| !l.trim().is_empty())
+        .map(indent_of)
+        .fold(first_indent, usize::min);
+    if common == 0 {
+        return (content, line_mappings);
+    }
+    // The first line holds no indentation of its own to strip
+    let strip = |i: usize, line: &str| -> String {
+        if i == 0 { line.to_string() } else { line[indent_of(line).min(common)..].to_string() }
+    };
+    let content = content.split('\n').enumerate().map(|(i, l)| strip(i, l)).collect::<Vec<_>>().join("\n");
+    let line_mappings = line_mappings.map(|m| m.into_iter().enumerate().map(|(i, (n, l))| (n, strip(i, &l))).collect());
+    (content, line_mappings)
+}
+
+/// Split a symbol argument into its type qualifier and bare name:
+/// `User::new` and `UserService.getUser` are qualified, `new` is not.
+pub(crate) fn split_qualified(symbol: &str) -> (Option<&str>, &str) {
+    match symbol.rsplit_once("::").or_else(|| symbol.rsplit_once('.')) {
+        Some((qualifier, name)) if !qualifier.is_empty() && !name.is_empty() => (Some(qualifier), name),
+        _ => (None, symbol),
+    }
+}
+
+/// A type name without generic arguments or module path: `models::User<T>` is `User`.
+pub(crate) fn base_type_name(text: &str) -> &str {
+    let text = text.split('<').next().unwrap_or(text);
+    text.rsplit("::").next().unwrap_or(text).trim()
+}
+
+/// Names of the impl, trait or class directly enclosing `node`, without
+/// generic arguments or module paths. A Rust trait impl answers to both the
+/// trait and the implementing type.
+fn enclosing_type_names(node: Node, source: &str, extractor: &dyn LanguageExtractor) -> Vec<String> {
+    let base = |text: &str| base_type_name(text).to_string();
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == "trait_item" {
+            return n.child_by_field_name("name").map(|name| base(&source[name.byte_range()])).into_iter().collect();
+        }
+        if let Some(name) = extractor.extract_impl_name(n, source) {
+            let mut names = vec![base(&name)];
+            if n.kind() == "impl_item" {
+                names.extend(super::rust::impl_type_name(n, source).map(|t| base(&t)));
+            }
+            return names;
+        }
+        current = n.parent();
+    }
+    Vec::new()
+}
+
+/// Body of a definition node, looking through decorator/export wrappers.
+fn definition_body(node: Node) -> Option<Node> {
+    let def = node
+        .child_by_field_name("definition")
+        .or_else(|| node.child_by_field_name("declaration"))
+        .unwrap_or(node);
+    def.child_by_field_name("body")
+}
+
+/// Collect body ranges of the outermost definitions nested under `node`.
+fn collect_nested_def_bodies(node: Node, ranges: &mut Vec<(usize, usize)>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let is_definition = matches!(
+            child.kind(),
+            "function_item" | "function_definition" | "function_declaration" | "method_definition"
+                | "class_definition" | "class_declaration" | "impl_item" | "trait_item"
+        );
+        match child.child_by_field_name("body") {
+            Some(body) if is_definition => ranges.push((body.start_byte(), body.end_byte())),
+            _ => collect_nested_def_bodies(child, ranges),
+        }
+    }
+}
+
+/// Collect the bodies of the outermost match/switch arms under `node` as
+/// `(start, end, placeholder)` splices. Arm patterns stay outside the range.
+fn collect_match_arms(node: Node, arms: &mut Vec<Splice>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            // Rust: `pattern => value`
+            "match_arm" => {
+                if let Some(value) = child.child_by_field_name("value") {
+                    arms.push((value.start_byte(), value.end_byte(), Cow::Borrowed("{ ... }")));
+                }
+            }
+            // TS/JS: `case x:` followed by any number of statements
+            "switch_case" | "switch_default" => {
+                let mut body_cursor = child.walk();
+                let last = child.children_by_field_name("body", &mut body_cursor).last();
+                let colon = child.children(&mut child.walk()).find(|c| c.kind() == ":");
+                if let (Some(colon), Some(last)) = (colon, last) {
+                    arms.push((colon.end_byte(), last.end_byte(), Cow::Borrowed(" { ... }")));
+                }
+            }
+            // Python: `case x:` followed by an indented block
+            "case_clause" => {
+                let colon = child.children(&mut child.walk()).find(|c| c.kind() == ":");
+                if let (Some(colon), Some(block)) = (colon, child.child_by_field_name("consequence")) {
+                    arms.push((colon.end_byte(), block.end_byte(), Cow::Borrowed(" ...")));
+                }
+            }
+            _ => collect_match_arms(child, arms),
+        }
+    }
+}
+
+/// Collect string literals under `node` longer than `max_chars` characters as
+/// splices that keep the quotes and note the original length.
+fn collect_long_strings(node: Node, source: &str, max_chars: usize, splices: &mut Vec<Splice>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "string_literal" | "raw_string_literal" | "string" | "template_string" => {
+                let len = source[child.byte_range()].chars().count();
+                if len > max_chars {
+                    splices.push((child.start_byte(), child.end_byte(), Cow::Owned(format!("\"...<{} chars>...\"", len))));
+                }
+            }
+            _ => collect_long_strings(child, source, max_chars, splices),
+        }
+    }
+}
+
+/// Collect blocks under `node` nested more than `max_depth` levels deep (the
+/// definition body itself being level 0) as splices. Only the outermost such
+/// block is collapsed; anything inside it goes with it.
+fn collect_deep_blocks(node: Node, depth: usize, max_depth: usize, placeholder: &'static str, splices: &mut Vec<Splice>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if matches!(child.kind(), "block" | "statement_block") {
+            if depth + 1 > max_depth {
+                splices.push((child.start_byte(), child.end_byte(), Cow::Borrowed(placeholder)));
+            } else {
+                collect_deep_blocks(child, depth + 1, max_depth, placeholder, splices);
+            }
+        } else {
+            collect_deep_blocks(child, depth, max_depth, placeholder, splices);
+        }
+    }
+}
+
+/// Collect the bodies of functions inside `mod` items nested under `node` as
+/// `{ ... }` splices, leaving the module's own functions intact.
+fn collect_submodule_fn_bodies(node: Node, in_submodule: bool, splices: &mut Vec<Splice>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "mod_item" => collect_submodule_fn_bodies(child, true, splices),
+            "function_item" if in_submodule => {
+                if let Some(body) = child.child_by_field_name("body") {
+                    splices.push((body.start_byte(), body.end_byte(), Cow::Borrowed("{ ... }")));
+                }
+            }
+            _ => collect_submodule_fn_bodies(child, in_submodule, splices),
+        }
+    }
+}
+
+/// Replace each `(start, end, placeholder)` range of `source[start_byte..end_byte]`
+/// with its placeholder. Ranges nested inside an earlier range are dropped.
+fn apply_splices(source: &str, start_byte: usize, end_byte: usize, splices: &mut [Splice]) -> (String, Vec<(usize, String)>) {
+    splices.sort_by_key(|&(s, _, _)| s);
+    let mut result = String::new();
+    let mut ranges = Vec::new();
+    let mut pos = start_byte;
+    for (start, end, placeholder) in splices.iter() {
+        let (start, end) = (*start, *end);
+        if start < pos {
+            continue;
+        }
+        result.push_str(&source[pos..start]);
+        result.push_str(placeholder);
+        ranges.push((start, end));
+        pos = end;
+    }
+    result.push_str(&source[pos..end_byte]);
+
+    let start_line = source[..start_byte].matches('\n').count() + 1;
+    let mappings = build_collapsed_block_mappings_pub(source, end_byte, &ranges, start_line, &result);
+    (result, mappings)
+}
+
 /// Extract a class with method signatures collapsed, optionally expanding specific methods.
 pub fn extract_signatures(source: &str, tree: &Tree, class_name: &str, expand_methods: &[String], language: Language) -> Vec<Item> {
     let extractor = extractor_for(language);
@@ -130,6 +409,16 @@ pub fn extract_signatures(source: &str, tree: &Tree, class_name: &str, expand_me
                 body: None,
                 content,
                 line_mappings: None,
+                parameters: None,
+                type_parameters: type_parameters(item_node, source),
+                return_doc: None,
+                source_module: None,
+                resolved: None,
+                parent: None,
+                node_kind: item_node.kind().to_string(),
+                is_test: extractor.is_test(item_node, source),
+                modifiers: extractor.modifiers(item_node, source),
+                decorators: decorators(item_node, source),
             }];
         }
 
@@ -155,6 +444,16 @@ pub fn extract_signatures(source: &str, tree: &Tree, class_name: &str, expand_me
                 body: None,
                 content,
                 line_mappings,
+                parameters: None,
+                type_parameters: type_parameters(item_node, source),
+                return_doc: None,
+                source_module: None,
+                resolved: None,
+                parent: None,
+                node_kind: item_node.kind().to_string(),
+                is_test: extractor.is_test(item_node, source),
+                modifiers: extractor.modifiers(item_node, source),
+                decorators: decorators(item_node, source),
             }];
         } else {
             // Combined mode: collapse all method bodies except specified ones
@@ -174,6 +473,16 @@ pub fn extract_signatures(source: &str, tree: &Tree, class_name: &str, expand_me
                 body: None,
                 content,
                 line_mappings,
+                parameters: None,
+                type_parameters: type_parameters(item_node, source),
+                return_doc: None,
+                source_module: None,
+                resolved: None,
+                parent: None,
+                node_kind: item_node.kind().to_string(),
+                is_test: extractor.is_test(item_node, source),
+                modifiers: extractor.modifiers(item_node, source),
+                decorators: decorators(item_node, source),
             }];
         }
     }
@@ -1,16 +1,42 @@
-use super::collapse::{collapse_body, collapse_block, build_source_line_mappings};
-use super::{extractor_for, find_attr_start, Item, Visibility, LanguageExtractor};
+use super::collapse::{render_body, render_block, build_source_line_mappings, BodyStyle};
+use super::{decorators, extractor_for, find_attr_start, find_doc_start, python, type_parameters, Item, ItemKind, Visibility, LanguageExtractor};
 use crate::languages::{ts_language, Language};
-use tree_sitter::{Query, QueryCursor, StreamingIterator, Tree};
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
 use std::collections::BTreeMap;
 
 /// Extract interface view (collapsed function bodies) using tree-sitter queries.
 pub fn extract(source: &str, tree: &Tree, language: Language) -> Vec<Item> {
+    extract_with_style(source, tree, language, BodyStyle::Collapsed)
+}
+
+/// Extract interface view, rendering function bodies according to `style`.
+pub fn extract_with_style(source: &str, tree: &Tree, language: Language, style: BodyStyle) -> Vec<Item> {
+    extract_with_docs(source, tree, language, style, false)
+}
+
+/// Like [`extract_with_style`], optionally keeping each item's doc comments
+/// (for Python, the first line of its docstring).
+pub fn extract_with_docs(source: &str, tree: &Tree, language: Language, style: BodyStyle, docs: bool) -> Vec<Item> {
     let extractor = extractor_for(language);
-    extract_with_extractor(source, tree, language, extractor.as_ref())
+    extract_with_extractor(source, tree, language, extractor.as_ref(), style, docs, false)
 }
 
-fn extract_with_extractor(source: &str, tree: &Tree, language: Language, extractor: &dyn LanguageExtractor) -> Vec<Item> {
+/// Like [`extract_with_docs`], also listing the methods of Python classes as
+/// `Method` items of their own, as Rust impl and TS class methods always are.
+pub fn extract_with_class_methods(source: &str, tree: &Tree, language: Language, style: BodyStyle, docs: bool) -> Vec<Item> {
+    let extractor = extractor_for(language);
+    extract_with_extractor(source, tree, language, extractor.as_ref(), style, docs, true)
+}
+
+fn extract_with_extractor(
+    source: &str,
+    tree: &Tree,
+    language: Language,
+    extractor: &dyn LanguageExtractor,
+    style: BodyStyle,
+    docs: bool,
+    class_methods: bool,
+) -> Vec<Item> {
     let ts_lang = ts_language(language);
     let query = Query::new(&ts_lang, extractor.interface_query())
         .expect("interface_query should compile");
@@ -24,6 +50,8 @@ fn extract_with_extractor(source: &str, tree: &Tree, language: Language, extract
     let body_idx = query.capture_index_for_name("body");
 
     let mut items_map: BTreeMap<usize, Item> = BTreeMap::new();
+    // Members can share a line with their owner, so they aren't keyed by line
+    let mut members: Vec<Item> = Vec::new();
 
     let root = tree.root_node();
     let mut matches_iter = cursor.matches(&query, root, source_bytes);
@@ -35,26 +63,14 @@ fn extract_with_extractor(source: &str, tree: &Tree, language: Language, extract
         };
 
         let mut kind_str = item_node.kind();
-        // For TS export_statement, use the inner declaration's kind
-        let inner_node = if kind_str == "export_statement" {
-            let mut inner = None;
-            let mut c = item_node.walk();
-            for child in item_node.children(&mut c) {
-                let ck = child.kind();
-                if ck != "export" && ck != ";" && ck != "default" && ck != "comment" && ck != "decorator" {
-                    inner = Some(child);
-                    break;
-                }
-            }
-            inner
-        } else {
-            None
-        };
+        // For TS export_statement/ambient declarations and Python decorated definitions, use the inner declaration's kind
+        let inner_node = wrapped_declaration(item_node);
         if let Some(inner) = inner_node {
             kind_str = inner.kind();
         }
 
-        let visibility = if item_node.kind() == "export_statement" {
+        // Ambient (`declare`) items describe an API defined elsewhere, so they count as public
+        let visibility = if matches!(item_node.kind(), "export_statement" | "ambient_declaration") {
             Visibility::Public
         } else {
             vis_idx
@@ -71,26 +87,35 @@ fn extract_with_extractor(source: &str, tree: &Tree, language: Language, extract
             .and_then(|idx| m.captures.iter().find(|c| c.index == idx))
             .map(|c| c.node);
 
-        let kind = match extractor.node_kind_to_item_kind(kind_str) {
-            Some(k) => k,
-            None => continue,
+        let reexport = if item_node.kind() == "export_statement" {
+            extractor.extract_reexport(item_node, source)
+        } else {
+            None
         };
-        let (effective_start_byte, line_start) = find_attr_start(item_node);
+
+        let kind = match (&reexport, extractor.node_kind_to_item_kind(kind_str)) {
+            (Some(_), _) => ItemKind::Use,
+            (None, Some(k)) => k,
+            (None, None) => continue,
+        };
+        let (effective_start_byte, line_start) =
+            if docs { find_doc_start(item_node, source) } else { find_attr_start(item_node) };
         let line_end = item_node.end_position().row + 1;
 
         let (content, line_mappings, has_body) = match kind_str {
             "impl_item" | "trait_item" | "class_declaration" | "abstract_class_declaration" | "interface_declaration" => {
-                let (c, m) = collapse_block(source, effective_start_byte, item_node);
+                let (c, m) = render_block(source, effective_start_byte, item_node, style);
                 (c, m, false)
             }
             _ if body_node.is_some() => {
                 let body = body_node.unwrap();
-                let (c, m) = collapse_body(
+                let (c, m) = render_body(
                     source,
                     effective_start_byte,
                     item_node.end_byte(),
                     body.start_byte(),
                     body.end_byte(),
+                    style,
                 );
                 (c, m, true)
             }
@@ -100,10 +125,26 @@ fn extract_with_extractor(source: &str, tree: &Tree, language: Language, extract
             }
         };
 
-        let name = if kind_str == "impl_item" {
-            extractor.extract_impl_name(item_node, source)
-        } else {
-            name
+        // A collapsed Python body hides the docstring, so its first line goes back above `{ ... }`
+        let (content, line_mappings) = match inner_node.unwrap_or(item_node) {
+            def if docs && has_body && style == BodyStyle::Collapsed && language == Language::Python => {
+                match (python::docstring_line(def, source), content.rsplit_once('\n')) {
+                    (Some(doc), Some((head, collapsed))) => {
+                        let indent = &collapsed[..collapsed.len() - collapsed.trim_start().len()];
+                        let content = format!("{}\n{}{}\n{}", head, indent, doc, collapsed);
+                        let line_mappings = build_source_line_mappings(&content, line_start);
+                        (content, line_mappings)
+                    }
+                    _ => (content, line_mappings),
+                }
+            }
+            _ => (content, line_mappings),
+        };
+
+        let (name, source_module) = match reexport {
+            Some((names, module)) => (Some(names), module),
+            None if kind_str == "impl_item" => (extractor.extract_impl_name(item_node, source), None),
+            None => (name, None),
         };
 
         let line_mappings = if line_mappings.is_empty() {
@@ -112,24 +153,72 @@ fn extract_with_extractor(source: &str, tree: &Tree, language: Language, extract
             Some(line_mappings)
         };
 
+        let (parameters, return_doc) = if matches!(kind, ItemKind::Function) {
+            let fn_node = inner_node.unwrap_or(item_node);
+            (
+                extractor.extract_parameters(fn_node, source),
+                extractor.extract_return_doc(fn_node, source),
+            )
+        } else {
+            (None, None)
+        };
+
+        let is_new = !items_map.contains_key(&line_start);
         items_map.entry(line_start).or_insert(Item {
             kind: kind.clone(),
             name: name.clone(),
             visibility: visibility.clone(),
             line_start,
             line_end,
-            signature: None,
+            signature: extractor.extract_signature(inner_node.unwrap_or(item_node), source),
             body: if has_body { Some("{ ... }".to_string()) } else { None },
             content: content.clone(),
             line_mappings: line_mappings.clone(),
+            parameters,
+            type_parameters: type_parameters(inner_node.unwrap_or(item_node), source),
+            return_doc,
+            source_module,
+            resolved: None,
+            parent: None,
+            node_kind: kind_str.to_string(),
+            is_test: extractor.is_test(inner_node.unwrap_or(item_node), source),
+            modifiers: extractor.modifiers(inner_node.unwrap_or(item_node), source),
+            decorators: decorators(item_node, source),
         });
 
-        if matches!(kind_str, "impl_item" | "trait_item" | "class_declaration" | "abstract_class_declaration") {
+        if matches!(kind_str, "impl_item" | "trait_item" | "class_declaration" | "abstract_class_declaration")
+            || (class_methods && kind_str == "class_definition")
+        {
             // For export_statement, pass the inner node so extract_methods_from_block can find "body"
             let block_node = if let Some(inner) = inner_node { inner } else { item_node };
-            extractor.extract_methods_from_block(source, block_node, &mut items_map);
+            extractor.extract_methods_from_block(source, block_node, &mut items_map, style);
+        }
+        if is_new {
+            members.extend(extractor.extract_members(source, inner_node.unwrap_or(item_node)));
         }
     }
 
-    items_map.into_values().collect()
+    let mut items: Vec<Item> = items_map.into_values().collect();
+    if !members.is_empty() {
+        items.extend(members);
+        // Stable, so a member sharing its owner's line still follows it
+        items.sort_by_key(|item| item.line_start);
+    }
+    items
+}
+
+/// The declaration inside a TS `export`/`declare` wrapper, unwrapping both in
+/// `export declare ...`, or the definition inside a Python `decorated_definition`.
+fn wrapped_declaration(node: Node) -> Option<Node> {
+    if node.kind() == "decorated_definition" {
+        return node.child_by_field_name("definition");
+    }
+    if !matches!(node.kind(), "export_statement" | "ambient_declaration") {
+        return None;
+    }
+    let mut c = node.walk();
+    let inner = node
+        .children(&mut c)
+        .find(|child| !matches!(child.kind(), "export" | "declare" | ";" | "default" | "comment" | "decorator"))?;
+    wrapped_declaration(inner).or(Some(inner))
 }
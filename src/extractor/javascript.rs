@@ -1,5 +1,5 @@
-use super::collapse::{collapse_body, build_source_line_mappings};
-use super::{find_attr_start, Item, ItemKind, Visibility};
+use super::collapse::{render_body, build_source_line_mappings, BodyStyle};
+use super::{decorators, find_attr_start, jsdoc, Item, ItemKind, Modifiers, Parameter, Visibility};
 use tree_sitter::Node;
 use std::collections::BTreeMap;
 
@@ -59,11 +59,30 @@ impl super::LanguageExtractor for JavaScriptExtractor {
         }
     }
 
-    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut BTreeMap<usize, Item>) {
+    fn extract_parameters(&self, node: Node, source: &str) -> Option<Vec<Parameter>> {
+        jsdoc::extract_parameters(node, source)
+    }
+
+    fn extract_return_doc(&self, node: Node, source: &str) -> Option<String> {
+        jsdoc::extract_return_doc(node, source)
+    }
+
+    fn extract_reexport(&self, node: Node, source: &str) -> Option<(String, Option<String>)> {
+        super::typescript::extract_reexport(node, source)
+    }
+
+    fn is_test(&self, node: Node, source: &str) -> bool {
+        super::typescript::is_test_node(node, source)
+    }
+
+    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut BTreeMap<usize, Item>, style: BodyStyle) {
         let body = match block_node.child_by_field_name("body") {
             Some(b) if b.kind() == "class_body" => b,
             _ => return,
         };
+        let parent = block_node
+            .child_by_field_name("name")
+            .map(|n| source[n.byte_range()].to_string());
 
         let mut cursor = body.walk();
         for child in body.children(&mut cursor) {
@@ -79,12 +98,13 @@ impl super::LanguageExtractor for JavaScriptExtractor {
             let line_end = child.end_position().row + 1;
 
             let (content, line_mappings, has_body) = if let Some(body) = child.child_by_field_name("body") {
-                let (c, m) = collapse_body(
+                let (c, m) = render_body(
                     source,
                     effective_start_byte,
                     child.end_byte(),
                     body.start_byte(),
                     body.end_byte(),
+                    style,
                 );
                 (c, m, true)
             } else {
@@ -111,6 +131,16 @@ impl super::LanguageExtractor for JavaScriptExtractor {
                 body: if has_body { Some("{ ... }".to_string()) } else { None },
                 content,
                 line_mappings,
+                parameters: jsdoc::extract_parameters(child, source),
+                type_parameters: Vec::new(),
+                return_doc: jsdoc::extract_return_doc(child, source),
+                source_module: None,
+                resolved: None,
+                parent: parent.clone(),
+                node_kind: child.kind().to_string(),
+                is_test: super::typescript::is_test_node(child, source),
+                modifiers: Modifiers::from_node(child),
+                decorators: decorators(child, source),
             });
         }
     }
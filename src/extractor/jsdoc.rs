@@ -0,0 +1,209 @@
+//! JSDoc parsing and parameter extraction for TypeScript/JavaScript.
+//!
+//! Reads the `/** ... */` comment preceding a function or method and maps its
+//! `@param`/`@returns` tags onto the parameters found in the AST.
+
+use super::{ParamKind, Parameter};
+use tree_sitter::Node;
+
+/// Tags parsed from one JSDoc comment.
+#[derive(Debug, Default, PartialEq)]
+pub struct JsDoc {
+    pub params: Vec<(String, String)>,
+    pub returns: Option<String>,
+}
+
+/// Parse `@param` and `@returns` tags from a `/** ... */` comment.
+/// Multi-line tag descriptions are joined with single spaces.
+pub fn parse(comment: &str) -> JsDoc {
+    let body = comment
+        .trim()
+        .trim_start_matches("/**")
+        .trim_end_matches("*/");
+
+    let mut doc = JsDoc::default();
+    // (tag, text) of the tag currently being accumulated
+    let mut current: Option<(String, String)> = None;
+
+    for raw_line in body.lines() {
+        let line = raw_line.trim().trim_start_matches('*').trim();
+        if let Some(rest) = line.strip_prefix('@') {
+            if let Some(tag) = current.take() {
+                push_tag(&mut doc, tag);
+            }
+            let (tag, text) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            current = Some((tag.to_string(), text.trim().to_string()));
+        } else if let Some((_, text)) = current.as_mut() {
+            if !line.is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(line);
+            }
+        }
+    }
+    if let Some(tag) = current.take() {
+        push_tag(&mut doc, tag);
+    }
+
+    doc
+}
+
+fn push_tag(doc: &mut JsDoc, (tag, text): (String, String)) {
+    match tag.as_str() {
+        "param" | "arg" | "argument" => {
+            let text = skip_type_expression(&text);
+            let (name, desc) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+            // `[name]` / `[name=default]` mark optional params
+            let name = name.trim_start_matches('[').trim_end_matches(']');
+            let name = name.split('=').next().unwrap_or(name);
+            let desc = desc.trim().trim_start_matches('-').trim();
+            if !name.is_empty() {
+                doc.params.push((name.to_string(), desc.to_string()));
+            }
+        }
+        "returns" | "return" => {
+            let desc = skip_type_expression(&text).trim();
+            if !desc.is_empty() {
+                doc.returns = Some(desc.to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Skip a leading `{type}` expression (which may contain nested braces).
+fn skip_type_expression(text: &str) -> &str {
+    if !text.starts_with('{') {
+        return text;
+    }
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return text[i + 1..].trim_start();
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Find the JSDoc comment directly preceding `node` (or its `export`/`declare` wrapper).
+pub fn preceding_jsdoc<'a>(node: Node, source: &'a str) -> Option<&'a str> {
+    let mut anchor = node;
+    while let Some(p) = anchor.parent().filter(|p| matches!(p.kind(), "export_statement" | "ambient_declaration")) {
+        anchor = p;
+    }
+    let mut prev = anchor.prev_sibling();
+    // Skip decorators between the comment and the declaration
+    while let Some(p) = prev {
+        if p.kind() != "decorator" {
+            break;
+        }
+        prev = p.prev_sibling();
+    }
+    let comment = prev.filter(|p| p.kind() == "comment")?;
+    let text = &source[comment.byte_range()];
+    text.starts_with("/**").then_some(text)
+}
+
+/// Extract parameters of a function/method node, with docs from its JSDoc comment.
+pub fn extract_parameters(node: Node, source: &str) -> Option<Vec<Parameter>> {
+    let params_node = node.child_by_field_name("parameters")?;
+    let doc = preceding_jsdoc(node, source).map(parse).unwrap_or_default();
+
+    let mut params = Vec::new();
+    let mut cursor = params_node.walk();
+    for child in params_node.named_children(&mut cursor) {
+        if child.kind() == "comment" {
+            continue;
+        }
+        let (name_node, type_annotation, kind) = match child.kind() {
+            "required_parameter" | "optional_parameter" => {
+                let annotation = child.child_by_field_name("type");
+                let ty = annotation.map(|t| source[t.byte_range()].trim_start_matches(':').trim().to_string());
+                let kind = annotation.and_then(|t| t.named_child(0)).map(|t| param_kind(t, node, source));
+                (child.child_by_field_name("pattern").unwrap_or(child), ty, kind)
+            }
+            _ => (child, None, None),
+        };
+        let name = parameter_name(name_node, source);
+        let doc = doc
+            .params
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, d)| d.clone())
+            .filter(|d| !d.is_empty());
+        params.push(Parameter { name, type_annotation, doc, kind });
+    }
+
+    Some(params)
+}
+
+/// Return doc (`@returns`) for a function/method node.
+pub fn extract_return_doc(node: Node, source: &str) -> Option<String> {
+    preceding_jsdoc(node, source).and_then(|c| parse(c).returns)
+}
+
+/// Classify a TS parameter type: function types are closures, the function's
+/// own type parameters are generic, anything else is concrete.
+fn param_kind(ty: Node, function: Node, source: &str) -> ParamKind {
+    match ty.kind() {
+        "function_type" => ParamKind::Closure,
+        "type_identifier" => {
+            let name = &source[ty.byte_range()];
+            let is_type_param = function.child_by_field_name("type_parameters").is_some_and(|tps| {
+                let mut cursor = tps.walk();
+                let found = tps
+                    .named_children(&mut cursor)
+                    .any(|tp| tp.child_by_field_name("name").is_some_and(|n| &source[n.byte_range()] == name));
+                found
+            });
+            if is_type_param { ParamKind::Generic } else { ParamKind::Concrete }
+        }
+        _ => ParamKind::Concrete,
+    }
+}
+
+fn parameter_name(node: Node, source: &str) -> String {
+    match node.kind() {
+        "assignment_pattern" => node
+            .child_by_field_name("left")
+            .map(|l| parameter_name(l, source))
+            .unwrap_or_else(|| source[node.byte_range()].to_string()),
+        "rest_pattern" => source[node.byte_range()].trim_start_matches("...").to_string(),
+        _ => source[node.byte_range()].to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_param_with_type_and_dash() {
+        let doc = parse("/**\n * Adds.\n * @param {number} a - first value\n * @param b second\n * @returns {number} the sum\n */");
+        assert_eq!(doc.params, vec![
+            ("a".to_string(), "first value".to_string()),
+            ("b".to_string(), "second".to_string()),
+        ]);
+        assert_eq!(doc.returns.as_deref(), Some("the sum"));
+    }
+
+    #[test]
+    fn parse_optional_and_multiline() {
+        let doc = parse("/**\n * @param {Object} [opts={}] the options\n *   spanning lines\n */");
+        assert_eq!(doc.params, vec![("opts".to_string(), "the options spanning lines".to_string())]);
+    }
+
+    #[test]
+    fn parse_nested_type_braces() {
+        let doc = parse("/** @param {{a: number}} cfg config */");
+        assert_eq!(doc.params, vec![("cfg".to_string(), "config".to_string())]);
+    }
+}
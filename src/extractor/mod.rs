@@ -3,6 +3,7 @@ pub mod typescript;
 pub mod javascript;
 pub mod python;
 pub mod collapse;
+pub mod jsdoc;
 pub mod interface;
 pub mod expand;
 
@@ -22,9 +23,209 @@ pub struct Item {
     /// Used when content has been modified (e.g., collapsed bodies)
     #[serde(skip)]
     pub line_mappings: Option<Vec<(usize, String)>>,
+    /// Structured parameters for functions/methods (where the language extractor supports it)
+    pub parameters: Option<Vec<Parameter>>,
+    /// Generic type parameters as written (`T`, `U: Clone`, `'a`)
+    pub type_parameters: Vec<String>,
+    /// Documentation for the return value (e.g. JSDoc `@returns`)
+    pub return_doc: Option<String>,
+    /// Module a re-export forwards from (e.g. `./bar` in `export { foo } from './bar'`)
+    pub source_module: Option<String>,
+    /// File a relative re-export's module resolves to (with `resolve_reexports`)
+    pub resolved: Option<String>,
+    /// Type a method belongs to (impl/trait type, or class name)
+    pub parent: Option<String>,
+    /// Underlying tree-sitter node kind (inner declaration for `export` wrappers)
+    pub node_kind: String,
+    /// Test code: Rust `#[test]`/`#[cfg(test)]`, TS/JS `describe`/`it`/`test` callbacks
+    /// or `*.test.*`/`*.spec.*` files, Python `test_*` functions and `Test*` classes
+    pub is_test: bool,
+    /// Declaration keywords such as `async` and `static`
+    pub modifiers: Modifiers,
+    /// Traits named in Rust `#[derive(...)]` and Python/TS decorator names
+    /// (`Serialize`, `app.route`), in source order
+    pub decorators: Vec<String>,
+}
+
+/// Declaration modifiers of an item, from its keywords (and, in Python, its decorators).
+/// Only set flags are serialized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Modifiers {
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_async: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_static: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_const: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_unsafe: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_abstract: bool,
+}
+
+impl Modifiers {
+    /// Collect modifier keywords among the direct children of a declaration node
+    /// (and Rust's `function_modifiers`), looking through `export`/decorator wrappers.
+    pub fn from_node(node: tree_sitter::Node) -> Modifiers {
+        let node = node
+            .child_by_field_name("declaration")
+            .or_else(|| node.child_by_field_name("definition"))
+            .unwrap_or(node);
+        let mut modifiers = Modifiers::default();
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "function_modifiers" {
+                let mut inner = child.walk();
+                for keyword in child.children(&mut inner) {
+                    modifiers.set(keyword.kind());
+                }
+            } else {
+                modifiers.set(child.kind());
+            }
+        }
+        modifiers
+    }
+
+    fn set(&mut self, keyword: &str) {
+        match keyword {
+            "async" => self.is_async = true,
+            "static" => self.is_static = true,
+            "const" => self.is_const = true,
+            "unsafe" => self.is_unsafe = true,
+            "abstract" => self.is_abstract = true,
+            _ => {}
+        }
+    }
+}
+
+/// Generic type parameters of a declaration node as written, whitespace-normalized,
+/// looking through `export`/decorator wrappers. Empty for non-generic items.
+pub fn type_parameters(node: tree_sitter::Node, source: &str) -> Vec<String> {
+    let node = node
+        .child_by_field_name("declaration")
+        .or_else(|| node.child_by_field_name("definition"))
+        .unwrap_or(node);
+    let Some(params) = node.child_by_field_name("type_parameters") else {
+        return Vec::new();
+    };
+    let mut cursor = params.walk();
+    let list = params
+        .named_children(&mut cursor)
+        .filter(|p| !p.kind().contains("comment"))
+        .map(|p| source[p.byte_range()].split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    list
+}
+
+/// Derived traits and decorator names of a declaration node, from its attributes
+/// and decorators, looking through `export`/decorator wrappers. Other Rust
+/// attributes are left out.
+pub fn decorators(node: tree_sitter::Node, source: &str) -> Vec<String> {
+    let mut outer = node;
+    while let Some(p) = outer.parent().filter(|p| matches!(p.kind(), "export_statement" | "decorated_definition")) {
+        outer = p;
+    }
+    let mut attrs = preceding_attributes(outer);
+    attrs.reverse();
+    let inner = outer
+        .child_by_field_name("declaration")
+        .or_else(|| outer.child_by_field_name("definition"));
+    for n in [Some(outer), inner].into_iter().flatten() {
+        let mut cursor = n.walk();
+        attrs.extend(n.children(&mut cursor).filter(|c| c.kind() == "decorator"));
+    }
+    attrs.iter().flat_map(|a| decorator_names(&source[a.byte_range()])).collect()
+}
+
+/// `#[derive(A, B)]` gives `A` and `B`, `@app.route("/")` gives `app.route`.
+fn decorator_names(text: &str) -> Vec<String> {
+    let compact = |s: &str| s.split_whitespace().collect::<String>();
+    if let Some(attr) = text.strip_prefix("#[") {
+        let derived = attr
+            .trim_end_matches(']')
+            .trim()
+            .strip_prefix("derive")
+            .and_then(|rest| rest.trim_start().strip_prefix('('));
+        return match derived {
+            Some(list) => list.trim_end_matches(')').split(',').map(compact).filter(|d| !d.is_empty()).collect(),
+            None => Vec::new(),
+        };
+    }
+    let name = text.trim_start_matches('@');
+    vec![compact(name.split('(').next().unwrap_or(name))]
+}
+
+/// The declaration text of `node` (and its `export` wrapper) up to its body,
+/// on one line. A bodiless declaration spanning several lines is cut after its
+/// first line (`const TABLE = [`). Trailing `:` and `;` are dropped.
+pub fn declaration_header(node: tree_sitter::Node, source: &str) -> String {
+    let start = node
+        .parent()
+        .filter(|p| p.kind() == "export_statement")
+        .map_or(node.start_byte(), |p| p.start_byte());
+    let end = match node.child_by_field_name("body") {
+        Some(body) => body.start_byte(),
+        None => source[start..node.end_byte()].find('\n').map_or(node.end_byte(), |i| start + i),
+    };
+    let header = source[start..end].split_whitespace().collect::<Vec<_>>().join(" ");
+    header.trim_end_matches([':', ';', '{', '(', '[']).trim_end().to_string()
+}
+
+/// A single function/method parameter.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Parameter {
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_annotation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// Rough shape of the parameter's type, when it has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ParamKind>,
+}
+
+/// What a parameter's type is, as far as the syntax tells.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamKind {
+    /// A named or built-in type (`u32`, `Vec<T>`, `User`)
+    Concrete,
+    /// One of the function's own type parameters (`T`)
+    Generic,
+    /// `impl Trait` or `dyn Trait` (Rust)
+    ImplTrait,
+    /// A callable: `impl Fn(..)`, `fn(..)`, a generic bounded by `Fn*`, or a TS function type
+    Closure,
 }
 
 impl Item {
+    /// A private item with just a kind, name, content and line range; every other
+    /// field empty. Set the rest with struct update syntax:
+    /// `Item { visibility: Visibility::Public, ..Item::new(..) }`.
+    pub fn new(kind: ItemKind, name: Option<&str>, content: &str, line_start: usize, line_end: usize) -> Item {
+        Item {
+            kind,
+            name: name.map(str::to_string),
+            visibility: Visibility::Private,
+            line_start,
+            line_end,
+            signature: None,
+            body: None,
+            content: content.to_string(),
+            line_mappings: None,
+            parameters: None,
+            type_parameters: Vec::new(),
+            return_doc: None,
+            source_module: None,
+            resolved: None,
+            parent: None,
+            node_kind: String::new(),
+            is_test: false,
+            modifiers: Modifiers::default(),
+            decorators: Vec::new(),
+        }
+    }
+
     pub fn is_public(&self) -> bool {
         matches!(self.visibility, Visibility::Public)
     }
@@ -46,6 +247,10 @@ pub enum ItemKind {
     TypeAlias,
     MacroDef,
     Class,
+    /// A Rust enum variant, listed after its enum
+    Variant,
+    /// A class field declared as a TS constructor parameter property
+    Field,
 }
 
 
@@ -66,6 +271,85 @@ impl ItemKind {
             _ => None,
         }
     }
+
+    /// Every kind, in declaration order.
+    pub const ALL: [ItemKind; 15] = [
+        ItemKind::Function,
+        ItemKind::Method,
+        ItemKind::Struct,
+        ItemKind::Enum,
+        ItemKind::Trait,
+        ItemKind::Impl,
+        ItemKind::Mod,
+        ItemKind::Use,
+        ItemKind::Const,
+        ItemKind::Static,
+        ItemKind::TypeAlias,
+        ItemKind::MacroDef,
+        ItemKind::Class,
+        ItemKind::Variant,
+        ItemKind::Field,
+    ];
+
+    /// What the construct reported as this kind is called in `language`, or
+    /// `None` when that language never produces it (TS interfaces are `Trait`).
+    pub fn display_name(&self, language: crate::languages::Language) -> Option<&'static str> {
+        use crate::languages::Language;
+        let ts_like = matches!(language, Language::TypeScript | Language::Tsx);
+        let js_like = ts_like || matches!(language, Language::JavaScript | Language::Jsx);
+        match (self, language) {
+            (ItemKind::Function, Language::Rust) => Some("fn"),
+            (ItemKind::Function, Language::Python) | (ItemKind::Method, Language::Python) => Some("def"),
+            (ItemKind::Function, _) => Some("function"),
+            (ItemKind::Method, _) => Some("method"),
+            (ItemKind::Struct, Language::Rust) => Some("struct"),
+            (ItemKind::Enum, Language::Rust) => Some("enum"),
+            (ItemKind::Enum, _) if ts_like => Some("enum"),
+            (ItemKind::Trait, Language::Rust) => Some("trait"),
+            (ItemKind::Trait, _) if ts_like => Some("interface"),
+            (ItemKind::Impl, Language::Rust) => Some("impl"),
+            (ItemKind::Mod, Language::Rust) => Some("mod"),
+            (ItemKind::Mod, _) if ts_like => Some("namespace"),
+            (ItemKind::Use, Language::Rust) => Some("use"),
+            (ItemKind::Use, _) => Some("import"),
+            (ItemKind::Const, Language::Rust) => Some("const"),
+            (ItemKind::Const, Language::Python) => Some("assignment"),
+            (ItemKind::Const, _) => Some("const/let/var"),
+            (ItemKind::Static, Language::Rust) => Some("static"),
+            (ItemKind::TypeAlias, Language::Rust) => Some("type"),
+            (ItemKind::TypeAlias, _) if ts_like => Some("type"),
+            (ItemKind::MacroDef, Language::Rust) => Some("macro_rules!"),
+            (ItemKind::Class, _) if js_like || language == Language::Python => Some("class"),
+            (ItemKind::Variant, Language::Rust) => Some("variant"),
+            (ItemKind::Field, Language::Rust) => Some("field"),
+            (ItemKind::Field, Language::Python) => Some("annotated attribute"),
+            (ItemKind::Field, _) if ts_like => Some("field/property"),
+            _ => None,
+        }
+    }
+
+    /// Parse a kind by its JSON name (`function`, `typealias`, ...); `fn` is
+    /// accepted for `function`.
+    pub fn from_name(name: &str) -> Option<ItemKind> {
+        match name {
+            "function" | "fn" => Some(ItemKind::Function),
+            "method" => Some(ItemKind::Method),
+            "struct" => Some(ItemKind::Struct),
+            "enum" => Some(ItemKind::Enum),
+            "trait" => Some(ItemKind::Trait),
+            "impl" => Some(ItemKind::Impl),
+            "mod" => Some(ItemKind::Mod),
+            "use" => Some(ItemKind::Use),
+            "const" => Some(ItemKind::Const),
+            "static" => Some(ItemKind::Static),
+            "typealias" => Some(ItemKind::TypeAlias),
+            "macrodef" => Some(ItemKind::MacroDef),
+            "class" => Some(ItemKind::Class),
+            "variant" => Some(ItemKind::Variant),
+            "field" => Some(ItemKind::Field),
+            _ => None,
+        }
+    }
 }
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -81,17 +365,9 @@ pub enum Visibility {
 pub fn find_attr_start(node: tree_sitter::Node) -> (usize, usize) {
     let mut start_byte = node.start_byte();
     let mut start_row = node.start_position().row;
-    // Walk backward through siblings looking for attributes/decorators
-    let mut current = node;
-    loop {
-        match current.prev_sibling() {
-            Some(prev) if prev.kind() == "attribute_item" || prev.kind() == "decorator" => {
-                start_byte = prev.start_byte();
-                start_row = prev.start_position().row;
-                current = prev;
-            }
-            _ => break,
-        }
+    if let Some(first) = preceding_attributes(node).last() {
+        start_byte = first.start_byte();
+        start_row = first.start_position().row;
     }
     // For nodes inside export_statement (e.g. class_declaration), check if the
     // parent export_statement has decorator children that precede this node
@@ -108,6 +384,44 @@ pub fn find_attr_start(node: tree_sitter::Node) -> (usize, usize) {
     (start_byte, start_row + 1)
 }
 
+/// `attribute_item`/`decorator` siblings directly above `node`, nearest first.
+fn preceding_attributes(node: tree_sitter::Node) -> Vec<tree_sitter::Node> {
+    let mut attrs = Vec::new();
+    let mut current = node;
+    while let Some(prev) = current.prev_sibling().filter(|p| matches!(p.kind(), "attribute_item" | "decorator")) {
+        attrs.push(prev);
+        current = prev;
+    }
+    attrs
+}
+
+/// Like [`find_attr_start`], but also takes in the doc comments (`///` or
+/// `/** */`) directly above the item and its attributes. Inner `//!` docs
+/// describe the enclosing module, so they are left out.
+pub fn find_doc_start(node: tree_sitter::Node, source: &str) -> (usize, usize) {
+    let (mut start_byte, mut line) = find_attr_start(node);
+    // Start from the item's earliest attribute
+    let mut anchor = node;
+    while let Some(prev) = anchor.prev_sibling().filter(|p| p.start_byte() >= start_byte) {
+        anchor = prev;
+    }
+    while let Some(prev) = anchor.prev_sibling() {
+        let text = &source[prev.byte_range()];
+        let is_doc = prev.kind().contains("comment")
+            && ((text.starts_with("///") && !text.starts_with("////")) || (text.starts_with("/**") && text != "/**/"));
+        // A line comment's range can end just past its newline
+        let end = prev.end_position();
+        let end_row = if end.column == 0 { end.row.saturating_sub(1) } else { end.row };
+        if !is_doc || end_row + 1 < anchor.start_position().row {
+            break;
+        }
+        start_byte = prev.start_byte();
+        line = prev.start_position().row + 1;
+        anchor = prev;
+    }
+    (start_byte, line)
+}
+
 impl Visibility {
     pub fn from_node(node: Option<tree_sitter::Node>, source: &str) -> Self {
         if let Some(vis_node) = node {
@@ -136,6 +450,41 @@ impl Visibility {
     }
 }
 
+/// A `Field` member of `parent` declared by `node`, with its content starting at
+/// `start_byte` (its attributes) and the declaration on one line as its signature.
+pub(crate) fn field_item(
+    source: &str,
+    node: tree_sitter::Node,
+    start_byte: usize,
+    name: Option<String>,
+    visibility: Visibility,
+    parent: Option<String>,
+) -> Item {
+    let content = source[start_byte..node.end_byte()].to_string();
+    let line_start = source[..start_byte].matches('\n').count() + 1;
+    Item {
+        kind: ItemKind::Field,
+        name,
+        visibility,
+        line_start,
+        line_end: node.end_position().row + 1,
+        signature: Some(source[node.byte_range()].split_whitespace().collect::<Vec<_>>().join(" ")),
+        body: None,
+        line_mappings: Some(collapse::build_source_line_mappings(&content, line_start)),
+        content,
+        parameters: None,
+        type_parameters: Vec::new(),
+        return_doc: None,
+        source_module: None,
+        resolved: None,
+        parent,
+        node_kind: node.kind().to_string(),
+        is_test: false,
+        modifiers: Modifiers::default(),
+        decorators: decorators(node, source),
+    }
+}
+
 /// Resolve a `Language` to its concrete `LanguageExtractor`.
 pub fn extractor_for(language: crate::languages::Language) -> Box<dyn LanguageExtractor> {
     match language {
@@ -146,13 +495,102 @@ pub fn extractor_for(language: crate::languages::Language) -> Box<dyn LanguageEx
     }
 }
 
+/// Up to three symbol names in `tree` closest to `target` by edit distance
+/// (case-insensitive), nearest first, for "did you mean" hints. Only names
+/// within a third of the target's length (at least 2 edits) are suggested.
+pub fn suggest_symbols(source: &str, tree: &tree_sitter::Tree, target: &str, language: crate::languages::Language) -> Vec<String> {
+    use tree_sitter::StreamingIterator;
+
+    let extractor = extractor_for(language);
+    let query = tree_sitter::Query::new(&crate::languages::ts_language(language), extractor.expand_query())
+        .expect("expand_query should compile");
+    let name_indices: Vec<u32> = ["name", "impl_type"]
+        .into_iter()
+        .filter_map(|name| query.capture_index_for_name(name))
+        .collect();
+
+    let mut names = std::collections::BTreeSet::new();
+    let mut cursor = tree_sitter::QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
+    while let Some(m) = matches.next() {
+        for capture in m.captures.iter().filter(|c| name_indices.contains(&c.index)) {
+            names.insert(source[capture.node.byte_range()].to_string());
+        }
+    }
+
+    let target = expand::split_qualified(target).1.to_lowercase();
+    let max_distance = (target.chars().count() / 3).max(2);
+    let mut scored: Vec<(usize, String)> = names
+        .into_iter()
+        .map(|name| (levenshtein(&target, &name.to_lowercase()), name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    scored.sort();
+    scored.into_iter().take(3).map(|(_, name)| name).collect()
+}
+
+/// Edit distance between `a` and `b` in characters.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 /// Language-specific extraction behavior.
 pub trait LanguageExtractor {
     fn interface_query(&self) -> &str;
     fn expand_query(&self) -> &str;
     fn node_kind_to_item_kind(&self, kind: &str) -> Option<ItemKind>;
     fn extract_impl_name(&self, node: tree_sitter::Node, source: &str) -> Option<String>;
-    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut std::collections::BTreeMap<usize, Item>);
+    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut std::collections::BTreeMap<usize, Item>, style: collapse::BodyStyle);
+
+    /// One-line signature of a top-level definition node, without its body.
+    /// Defaults to the [`declaration_header`].
+    fn extract_signature(&self, node: tree_sitter::Node, source: &str) -> Option<String> {
+        Some(declaration_header(node, source))
+    }
+
+    /// Structured parameters of a function node. Defaults to not extracted.
+    fn extract_parameters(&self, _node: tree_sitter::Node, _source: &str) -> Option<Vec<Parameter>> {
+        None
+    }
+
+    /// Documentation for a function node's return value. Defaults to none.
+    fn extract_return_doc(&self, _node: tree_sitter::Node, _source: &str) -> Option<String> {
+        None
+    }
+
+    /// Whether a definition node is test code. Defaults to false.
+    fn is_test(&self, _node: tree_sitter::Node, _source: &str) -> bool {
+        false
+    }
+
+    /// Exported names and source module of a re-export statement
+    /// (`export { a } from './m'`, `export * from './m'`). Defaults to none.
+    fn extract_reexport(&self, _node: tree_sitter::Node, _source: &str) -> Option<(String, Option<String>)> {
+        None
+    }
+
+    /// Non-method members of a definition node: Rust enum variants and struct
+    /// fields, TS class fields, interface properties and constructor parameter
+    /// properties, Python annotated class attributes. Defaults to none.
+    fn extract_members(&self, _source: &str, _node: tree_sitter::Node) -> Vec<Item> {
+        Vec::new()
+    }
+
+    /// Declaration modifiers of a definition node. Defaults to its keywords.
+    fn modifiers(&self, node: tree_sitter::Node, _source: &str) -> Modifiers {
+        Modifiers::from_node(node)
+    }
 }
 
 
@@ -177,6 +615,25 @@ mod tests {
         assert_eq!(ItemKind::from_node_kind("macro_definition"), Some(ItemKind::MacroDef));
     }
 
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn suggest_symbols_nearest_first() {
+        let source = "fn process_path() {}\nfn process_paths() {}\nfn process_file() {}\nstruct Processor;\nfn unrelated() {}\n";
+        let tree = parse(source, Language::Rust).unwrap();
+        assert_eq!(
+            suggest_symbols(source, &tree, "proces_path", Language::Rust),
+            vec!["process_path".to_string(), "process_paths".to_string()]
+        );
+        assert_eq!(suggest_symbols(source, &tree, "processor", Language::Rust), vec!["Processor".to_string()]);
+        assert!(suggest_symbols(source, &tree, "zzz", Language::Rust).is_empty());
+    }
+
     #[test]
     fn item_kind_from_node_kind_unknown() {
         assert_eq!(ItemKind::from_node_kind("if_expression"), None);
@@ -253,4 +710,14 @@ mod tests {
         assert_eq!(byte, 0); // attribute starts at byte 0
         assert_eq!(line, 1);
     }
+
+    #[test]
+    fn find_doc_start_takes_adjacent_doc_comments() {
+        let source = "//! Module docs.\n\n// plain\n/// Docs.\n/// More.\n#[inline]\nfn foo() {}";
+        let tree = parse(source, Language::Rust).unwrap();
+        let fn_node = tree.root_node().named_children(&mut tree.root_node().walk()).find(|n| n.kind() == "function_item").unwrap();
+        let (byte, line) = find_doc_start(fn_node, source);
+        assert_eq!(&source[byte..], "/// Docs.\n/// More.\n#[inline]\nfn foo() {}");
+        assert_eq!(line, 4);
+    }
 }
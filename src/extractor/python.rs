@@ -1,5 +1,5 @@
-use super::collapse::{build_source_line_mappings, collapse_body};
-use super::{Item, ItemKind, Visibility};
+use super::collapse::{build_source_line_mappings, render_body, BodyStyle};
+use super::{decorators, field_item, type_parameters, Item, ItemKind, Modifiers, Visibility};
 use std::collections::BTreeMap;
 use tree_sitter::Node;
 
@@ -44,6 +44,87 @@ fn find_decorator_start(node: Node) -> (usize, usize) {
     (node.start_byte(), node.start_position().row + 1)
 }
 
+/// The first line of a function or class docstring, requoted so it stands on
+/// its own: `"""Summary."""`.
+pub(crate) fn docstring_line(def: Node, source: &str) -> Option<String> {
+    let statement = def.child_by_field_name("body")?.named_child(0)?;
+    if statement.kind() != "expression_statement" || statement.named_child_count() != 1 {
+        return None;
+    }
+    let string = statement.named_child(0).filter(|s| s.kind() == "string")?;
+    let text = &source[string.byte_range()];
+    let prefix_len = text.find(['"', '\'']).unwrap_or(0);
+    let (prefix, quoted) = text.split_at(prefix_len);
+    let quote = if quoted.starts_with("\"\"\"") || quoted.starts_with("'''") { &quoted[..3] } else { quoted.get(..1)? };
+    let inner = quoted.strip_prefix(quote)?.strip_suffix(quote)?;
+    let summary = inner.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(format!("{}{}{}{}", prefix, quote, summary, quote))
+}
+
+/// Annotated attributes in a class body (`timeout: int = 3`) as `Field` items.
+/// Unannotated assignments are class variables, not declared fields, and are skipped.
+fn extract_class_fields(source: &str, class_node: Node) -> Vec<Item> {
+    if class_node.kind() != "class_definition" {
+        return Vec::new();
+    }
+    let Some(body) = class_node.child_by_field_name("body") else { return Vec::new() };
+    let parent = class_node
+        .child_by_field_name("name")
+        .map(|n| source[n.byte_range()].to_string());
+
+    let mut fields = Vec::new();
+    let mut cursor = body.walk();
+    for statement in body.named_children(&mut cursor) {
+        let Some(assignment) = statement.named_child(0).filter(|a| a.kind() == "assignment") else { continue };
+        if statement.kind() != "expression_statement" || assignment.child_by_field_name("type").is_none() {
+            continue;
+        }
+        let Some(name) = assignment.child_by_field_name("left").filter(|l| l.kind() == "identifier") else { continue };
+        let name = source[name.byte_range()].to_string();
+        let visibility = python_visibility(&name);
+        fields.push(field_item(source, statement, statement.start_byte(), Some(name), visibility, parent.clone()));
+    }
+    fields
+}
+
+/// Whether `node` is a `test_*` function or a `Test*` class, or sits inside a `Test*` class.
+fn is_test_node(node: Node, source: &str) -> bool {
+    let mut current = Some(node);
+    while let Some(n) = current {
+        let def = n.child_by_field_name("definition").unwrap_or(n);
+        if let Some(name) = def.child_by_field_name("name") {
+            let name = &source[name.byte_range()];
+            match def.kind() {
+                "function_definition" if name.starts_with("test_") => return true,
+                "class_definition" if name.starts_with("Test") => return true,
+                _ => {}
+            }
+        }
+        current = n.parent();
+    }
+    false
+}
+
+/// Keyword modifiers (`async`) plus `@staticmethod` and `@abstractmethod` decorators.
+fn modifiers(node: Node, source: &str) -> Modifiers {
+    let mut modifiers = Modifiers::from_node(node);
+    let decorated = match node.parent() {
+        Some(p) if p.kind() == "decorated_definition" => p,
+        _ => node,
+    };
+    let mut cursor = decorated.walk();
+    for decorator in decorated.children(&mut cursor).filter(|c| c.kind() == "decorator") {
+        let text = source[decorator.byte_range()].trim_start_matches('@').trim();
+        // `@abc.abstractmethod` counts like `@abstractmethod`
+        match text.rsplit('.').next().unwrap_or(text) {
+            "staticmethod" => modifiers.is_static = true,
+            "abstractmethod" => modifiers.is_abstract = true,
+            _ => {}
+        }
+    }
+    modifiers
+}
+
 impl super::LanguageExtractor for PythonExtractor {
     fn interface_query(&self) -> &str {
         crate::languages::python::INTERFACE_QUERY
@@ -67,6 +148,18 @@ impl super::LanguageExtractor for PythonExtractor {
         }
     }
 
+    fn is_test(&self, node: Node, source: &str) -> bool {
+        is_test_node(node, source)
+    }
+
+    fn modifiers(&self, node: Node, source: &str) -> Modifiers {
+        modifiers(node, source)
+    }
+
+    fn extract_members(&self, source: &str, node: Node) -> Vec<Item> {
+        extract_class_fields(source, node)
+    }
+
     fn extract_impl_name(&self, node: Node, source: &str) -> Option<String> {
         match node.kind() {
             "class_definition" => node
@@ -93,6 +186,7 @@ impl super::LanguageExtractor for PythonExtractor {
         source: &str,
         block_node: Node,
         items: &mut BTreeMap<usize, Item>,
+        style: BodyStyle,
     ) {
         // block_node is the class_definition or decorated_definition
         // Find the body (block) inside the class
@@ -112,6 +206,9 @@ impl super::LanguageExtractor for PythonExtractor {
             Some(n) => n,
             None => return,
         };
+        let parent = class_node
+            .child_by_field_name("name")
+            .map(|n| source[n.byte_range()].to_string());
 
         let body = match class_node.child_by_field_name("body") {
             Some(b) if b.kind() == "block" => b,
@@ -151,12 +248,13 @@ impl super::LanguageExtractor for PythonExtractor {
 
             let (content, line_mappings, has_body) =
                 if let Some(body) = func_node.child_by_field_name("body") {
-                    let (c, m) = collapse_body(
+                    let (c, m) = render_body(
                         source,
                         effective_start_byte,
                         child.end_byte(),
                         body.start_byte(),
                         body.end_byte(),
+                        style,
                     );
                     (c, m, true)
                 } else {
@@ -186,6 +284,16 @@ impl super::LanguageExtractor for PythonExtractor {
                 },
                 content,
                 line_mappings,
+                parameters: None,
+                type_parameters: type_parameters(func_node, source),
+                return_doc: None,
+                source_module: None,
+                resolved: None,
+                parent: parent.clone(),
+                node_kind: func_node.kind().to_string(),
+                is_test: is_test_node(func_node, source),
+                modifiers: modifiers(func_node, source),
+                decorators: decorators(func_node, source),
             });
         }
     }
@@ -2,20 +2,24 @@
 //!
 //! Helpers for extracting signatures, impl names, and methods from Rust AST nodes.
 
-use super::collapse::{collapse_body, build_source_line_mappings};
-use super::{find_attr_start, Item, ItemKind, Visibility};
+use super::collapse::{render_body, build_source_line_mappings, BodyStyle};
+use super::{decorators, field_item, find_attr_start, type_parameters, Item, ItemKind, Modifiers, ParamKind, Parameter, Visibility};
 use tree_sitter::Node;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub fn extract_methods_from_block(
     source: &str,
     block_node: Node,
     items: &mut BTreeMap<usize, Item>,
+    style: BodyStyle,
 ) {
     let decl_list = match block_node.child_by_field_name("body") {
         Some(body) if body.kind() == "declaration_list" => body,
         _ => return,
     };
+    // `impl Trait for Type` belongs to Type; trait items to the trait itself
+    let parent = impl_type_name(block_node, source)
+        .or_else(|| block_node.child_by_field_name("name").map(|n| source[n.byte_range()].to_string()));
 
     let mut cursor = decl_list.walk();
     for child in decl_list.children(&mut cursor) {
@@ -32,12 +36,13 @@ pub fn extract_methods_from_block(
         let line_end = child.end_position().row + 1;
 
         let (content, line_mappings, has_body) = if let Some(body) = child.child_by_field_name("body") {
-            let (c, m) = collapse_body(
+            let (c, m) = render_body(
                 source,
                 effective_start_byte,
                 child.end_byte(),
                 body.start_byte(),
                 body.end_byte(),
+                style,
             );
             (c, m, true)
         } else {
@@ -63,10 +68,185 @@ pub fn extract_methods_from_block(
             body: if has_body { Some("{ ... }".to_string()) } else { None },
             content,
             line_mappings,
+            parameters: extract_parameters(child, source),
+            type_parameters: type_parameters(child, source),
+            return_doc: None,
+            source_module: None,
+            resolved: None,
+            parent: parent.clone(),
+            node_kind: child.kind().to_string(),
+            is_test: is_test_node(child, source),
+            modifiers: Modifiers::from_node(child),
+            decorators: decorators(child, source),
         });
     }
 }
 
+/// Each variant of an `enum_item` as a `Variant` item whose signature is the
+/// variant's name plus its associated data (`Rect { w: f64, h: f64 }`).
+/// Variants share their enum's visibility.
+pub fn extract_enum_variants(source: &str, enum_node: Node) -> Vec<Item> {
+    if enum_node.kind() != "enum_item" {
+        return Vec::new();
+    }
+    let Some(variant_list) = enum_node.child_by_field_name("body") else { return Vec::new() };
+    let parent = enum_node
+        .child_by_field_name("name")
+        .map(|n| source[n.byte_range()].to_string());
+    let visibility = Visibility::from_parent(enum_node, source);
+
+    let mut variants = Vec::new();
+    let mut cursor = variant_list.walk();
+    for child in variant_list.children(&mut cursor) {
+        if child.kind() != "enum_variant" {
+            continue;
+        }
+        let (effective_start_byte, line_start) = find_attr_start(child);
+        let content = source[effective_start_byte..child.end_byte()].to_string();
+        // Name plus fields, joined onto one line; `= 3` discriminants aren't associated data
+        let data_end = child
+            .child_by_field_name("body")
+            .or_else(|| child.child_by_field_name("name"))
+            .map_or(child.end_byte(), |n| n.end_byte());
+        let signature = source[child.start_byte()..data_end]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace(", }", " }")
+            .replace(",)", ")");
+
+        variants.push(Item {
+            kind: ItemKind::Variant,
+            name: child
+                .child_by_field_name("name")
+                .map(|n| source[n.byte_range()].to_string()),
+            visibility: visibility.clone(),
+            line_start,
+            line_end: child.end_position().row + 1,
+            signature: Some(signature),
+            body: None,
+            line_mappings: Some(build_source_line_mappings(&content, line_start)),
+            content,
+            parameters: None,
+            type_parameters: Vec::new(),
+            return_doc: None,
+            source_module: None,
+            resolved: None,
+            parent: parent.clone(),
+            node_kind: child.kind().to_string(),
+            is_test: false,
+            modifiers: Modifiers::default(),
+            decorators: decorators(child, source),
+        });
+    }
+    variants
+}
+
+/// Named fields of a struct node as `Field` items. Tuple struct fields have no
+/// names and are skipped.
+pub fn extract_struct_fields(source: &str, struct_node: Node) -> Vec<Item> {
+    if !matches!(struct_node.kind(), "struct_item" | "union_item") {
+        return Vec::new();
+    }
+    let Some(field_list) = struct_node.child_by_field_name("body").filter(|b| b.kind() == "field_declaration_list") else {
+        return Vec::new();
+    };
+    let parent = struct_node
+        .child_by_field_name("name")
+        .map(|n| source[n.byte_range()].to_string());
+
+    let mut fields = Vec::new();
+    let mut cursor = field_list.walk();
+    for child in field_list.children(&mut cursor) {
+        if child.kind() != "field_declaration" {
+            continue;
+        }
+        let name = child
+            .child_by_field_name("name")
+            .map(|n| source[n.byte_range()].to_string());
+        let (start_byte, _) = find_attr_start(child);
+        fields.push(field_item(source, child, start_byte, name, Visibility::from_parent(child, source), parent.clone()));
+    }
+    fields
+}
+
+/// Parameters of a `function_item`/`function_signature_item`, without `self`,
+/// each with a `kind` hint from its type.
+pub fn extract_parameters(node: Node, source: &str) -> Option<Vec<Parameter>> {
+    let params_node = node.child_by_field_name("parameters")?;
+
+    // Type parameter name -> bound text, from `<T: Bound>` and `where T: Bound`
+    let mut generics: HashMap<String, String> = HashMap::new();
+    let mut add_bound = |text: &str| {
+        let (name, bound) = text.split_once(':').unwrap_or((text, ""));
+        let entry = generics.entry(name.trim().to_string()).or_default();
+        entry.push_str(bound);
+        entry.push('+');
+    };
+    if let Some(tps) = node.child_by_field_name("type_parameters") {
+        let mut cursor = tps.walk();
+        for tp in tps.named_children(&mut cursor).filter(|c| c.kind() == "type_parameter") {
+            add_bound(&source[tp.byte_range()]);
+        }
+    }
+    let mut cursor = node.walk();
+    if let Some(where_clause) = node.children(&mut cursor).find(|c| c.kind() == "where_clause") {
+        let mut cursor = where_clause.walk();
+        for pred in where_clause.named_children(&mut cursor).filter(|c| c.kind() == "where_predicate") {
+            add_bound(&source[pred.byte_range()]);
+        }
+    }
+
+    let mut params = Vec::new();
+    let mut cursor = params_node.walk();
+    for child in params_node.named_children(&mut cursor) {
+        if child.kind() != "parameter" {
+            continue;
+        }
+        let name = child
+            .child_by_field_name("pattern")
+            .map(|p| source[p.byte_range()].to_string())
+            .unwrap_or_default();
+        let ty = child.child_by_field_name("type");
+        params.push(Parameter {
+            name,
+            type_annotation: ty.map(|t| source[t.byte_range()].to_string()),
+            doc: None,
+            kind: ty.map(|t| param_kind(t, source, &generics)),
+        });
+    }
+    Some(params)
+}
+
+/// Classify a parameter type, looking through references and pointers.
+fn param_kind(ty: Node, source: &str, generics: &HashMap<String, String>) -> ParamKind {
+    let text = &source[ty.byte_range()];
+    match ty.kind() {
+        "reference_type" | "pointer_type" => ty
+            .child_by_field_name("type")
+            .map_or(ParamKind::Concrete, |inner| param_kind(inner, source, generics)),
+        "abstract_type" | "dynamic_type" => {
+            let bounds = text.trim_start_matches("impl").trim_start_matches("dyn");
+            if is_fn_bound(bounds) { ParamKind::Closure } else { ParamKind::ImplTrait }
+        }
+        "function_type" => ParamKind::Closure,
+        "type_identifier" => match generics.get(text) {
+            Some(bounds) if is_fn_bound(bounds) => ParamKind::Closure,
+            Some(_) => ParamKind::Generic,
+            None => ParamKind::Concrete,
+        },
+        _ => ParamKind::Concrete,
+    }
+}
+
+/// Whether a `+`-separated bound list includes `Fn`, `FnMut`, or `FnOnce`.
+fn is_fn_bound(bounds: &str) -> bool {
+    bounds.split('+').any(|bound| {
+        let head = bound.trim().split(['(', '<', ' ']).next().unwrap_or("");
+        matches!(head.rsplit("::").next(), Some("Fn" | "FnMut" | "FnOnce"))
+    })
+}
+
 /// Build a function signature string from a function_item node.
 pub fn build_fn_signature(source: &str, node: Node) -> String {
     let mut parts = Vec::new();
@@ -75,7 +255,8 @@ pub fn build_fn_signature(source: &str, node: Node) -> String {
     for child in node.children(&mut cursor) {
         match child.kind() {
             "visibility_modifier" => parts.push(source[child.byte_range()].to_string()),
-            "async" | "const" | "unsafe" | "extern" => {
+            // `async`/`const`/`unsafe`/`extern "C"` are grouped under `function_modifiers`
+            "function_modifiers" | "async" | "const" | "unsafe" | "extern" => {
                 parts.push(source[child.byte_range()].to_string())
             }
             _ => {}
@@ -84,15 +265,14 @@ pub fn build_fn_signature(source: &str, node: Node) -> String {
 
     parts.push("fn".to_string());
 
-    if let Some(name) = node.child_by_field_name("name") {
-        parts.push(source[name.byte_range()].to_string());
-    }
-    if let Some(tp) = node.child_by_field_name("type_parameters") {
-        parts.push(source[tp.byte_range()].to_string());
-    }
-    if let Some(params) = node.child_by_field_name("parameters") {
-        parts.push(source[params.byte_range()].to_string());
+    // Name, generics (lifetimes included) and parameters are written without spaces between them
+    let mut head = String::new();
+    for field in ["name", "type_parameters", "parameters"] {
+        if let Some(child) = node.child_by_field_name(field) {
+            head.push_str(&source[child.byte_range()]);
+        }
     }
+    parts.push(head);
     if let Some(ret) = node.child_by_field_name("return_type") {
         parts.push("->".to_string());
         parts.push(source[ret.byte_range()].to_string());
@@ -101,22 +281,86 @@ pub fn build_fn_signature(source: &str, node: Node) -> String {
     let mut cursor2 = node.walk();
     for child in node.children(&mut cursor2) {
         if child.kind() == "where_clause" {
-            parts.push(source[child.byte_range()].to_string());
+            parts.push(source[child.byte_range()].split_whitespace().collect::<Vec<_>>().join(" "));
         }
     }
 
     parts.join(" ")
 }
 
+/// Header of a struct, enum or trait: visibility, keyword, name, generics,
+/// supertraits and where clause, joined onto one line without the body.
+pub fn build_type_signature(source: &str, node: Node) -> Option<String> {
+    if !matches!(node.kind(), "struct_item" | "enum_item" | "trait_item") {
+        return None;
+    }
+    let body = node.child_by_field_name("body");
+    let mut header = source[node.start_byte()..body.map_or(node.end_byte(), |b| b.start_byte())].to_string();
+    // A tuple struct's where clause follows its fields
+    let mut cursor = node.walk();
+    if let Some(clause) = node.children(&mut cursor).find(|c| c.kind() == "where_clause" && body.is_some_and(|b| c.start_byte() >= b.end_byte())) {
+        header.push(' ');
+        header.push_str(&source[clause.byte_range()]);
+    }
+    let header = header.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(header.trim_end_matches([';', ',']).trim_end().to_string())
+}
+
+/// Whether `node` or any enclosing item carries `#[test]` (or `#[<path>::test]`)
+/// or a `#[cfg(..)]` that requires `test`, such as `#[cfg(all(test, unix))]`.
+pub fn is_test_node(node: Node, source: &str) -> bool {
+    let mut current = Some(node);
+    while let Some(n) = current {
+        let mut prev = n.prev_sibling();
+        while let Some(p) = prev.filter(|p| p.kind() == "attribute_item") {
+            let attr: String = source[p.byte_range()].split_whitespace().collect();
+            if attr == "#[test]" || attr.ends_with("::test]") || cfg_requires_test(&attr) {
+                return true;
+            }
+            prev = p.prev_sibling();
+        }
+        current = n.parent();
+    }
+    false
+}
+
+/// Whether a whitespace-free `#[cfg(..)]` attribute names `test` outside a `not(..)`.
+fn cfg_requires_test(attr: &str) -> bool {
+    let Some(predicate) = attr.strip_prefix("#[cfg(") else { return false };
+    let mut negated = Vec::new();
+    let mut token = String::new();
+    for c in predicate.chars() {
+        match c {
+            '(' => {
+                negated.push(token == "not" || negated.last() == Some(&true));
+                token.clear();
+            }
+            ')' | ',' => {
+                if token == "test" && negated.last() != Some(&true) {
+                    return true;
+                }
+                if c == ')' {
+                    negated.pop();
+                }
+                token.clear();
+            }
+            _ => token.push(c),
+        }
+    }
+    false
+}
+
 /// Extract impl name (trait name or type name).
 pub fn extract_impl_name(node: Node, source: &str) -> Option<String> {
     if let Some(trait_node) = node.child_by_field_name("trait") {
         return Some(source[trait_node.byte_range()].to_string());
     }
-    if let Some(type_node) = node.child_by_field_name("type") {
-        return Some(source[type_node.byte_range()].to_string());
-    }
-    None
+    impl_type_name(node, source)
+}
+
+/// The implementing type of an impl block: `User` in both `impl User` and `impl Greeter for User`.
+pub fn impl_type_name(node: Node, source: &str) -> Option<String> {
+    node.child_by_field_name("type").map(|type_node| source[type_node.byte_range()].to_string())
 }
 
 /// Rust language extractor.
@@ -139,8 +383,29 @@ impl super::LanguageExtractor for RustExtractor {
         extract_impl_name(node, source)
     }
 
+    fn is_test(&self, node: tree_sitter::Node, source: &str) -> bool {
+        is_test_node(node, source)
+    }
+
+
+    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut std::collections::BTreeMap<usize, Item>, style: BodyStyle) {
+        extract_methods_from_block(source, block_node, items, style)
+    }
+
+    fn extract_parameters(&self, node: tree_sitter::Node, source: &str) -> Option<Vec<super::Parameter>> {
+        extract_parameters(node, source)
+    }
+
+    fn extract_signature(&self, node: tree_sitter::Node, source: &str) -> Option<String> {
+        if node.kind() == "function_item" {
+            return Some(build_fn_signature(source, node));
+        }
+        build_type_signature(source, node).or_else(|| Some(super::declaration_header(node, source)))
+    }
 
-    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut std::collections::BTreeMap<usize, Item>) {
-        extract_methods_from_block(source, block_node, items)
+    fn extract_members(&self, source: &str, node: tree_sitter::Node) -> Vec<Item> {
+        let mut members = extract_enum_variants(source, node);
+        members.extend(extract_struct_fields(source, node));
+        members
     }
 }
@@ -1,5 +1,5 @@
-use super::collapse::{collapse_body, build_source_line_mappings};
-use super::{find_attr_start, Item, ItemKind, Visibility};
+use super::collapse::{render_body, build_source_line_mappings, BodyStyle};
+use super::{field_item, find_attr_start, decorators, jsdoc, type_parameters, Item, ItemKind, Modifiers, Parameter, Visibility};
 use tree_sitter::Node;
 use std::collections::BTreeMap;
 
@@ -62,6 +62,130 @@ fn member_visibility(node: Node, source: &str) -> Visibility {
     Visibility::Public
 }
 
+/// Constructor parameter properties (`constructor(private name: string)`) of a
+/// class node as `Field` items. Plain constructor parameters are skipped.
+fn extract_parameter_properties(source: &str, class_node: Node) -> Vec<Item> {
+    if !matches!(class_node.kind(), "class_declaration" | "abstract_class_declaration") {
+        return Vec::new();
+    }
+    let Some(body) = class_node.child_by_field_name("body") else { return Vec::new() };
+    let parent = class_node
+        .child_by_field_name("name")
+        .map(|n| source[n.byte_range()].to_string());
+
+    let mut cursor = body.walk();
+    let Some(constructor) = body.children(&mut cursor).find(|c| {
+        c.kind() == "method_definition"
+            && c.child_by_field_name("name").is_some_and(|n| &source[n.byte_range()] == "constructor")
+    }) else {
+        return Vec::new();
+    };
+    let Some(params) = constructor.child_by_field_name("parameters") else { return Vec::new() };
+
+    let mut fields = Vec::new();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        let mut param_cursor = param.walk();
+        let is_property = param
+            .children(&mut param_cursor)
+            .any(|c| matches!(c.kind(), "accessibility_modifier" | "readonly"));
+        if !is_property {
+            continue;
+        }
+        let name = param
+            .child_by_field_name("pattern")
+            .map(|n| source[n.byte_range()].to_string());
+        fields.push(field_item(source, param, param.start_byte(), name, member_visibility(param, source), parent.clone()));
+    }
+    fields
+}
+
+/// Declared fields of a class (`name: string;`) or properties of an interface
+/// (`id: number;`) as `Field` items. Interface properties are always public.
+fn extract_declared_fields(source: &str, node: Node) -> Vec<Item> {
+    let member_kind = match node.kind() {
+        "class_declaration" | "abstract_class_declaration" => "public_field_definition",
+        "interface_declaration" => "property_signature",
+        _ => return Vec::new(),
+    };
+    let Some(body) = node.child_by_field_name("body") else { return Vec::new() };
+    let parent = node
+        .child_by_field_name("name")
+        .map(|n| source[n.byte_range()].to_string());
+
+    let mut fields = Vec::new();
+    let mut cursor = body.walk();
+    for child in body.children(&mut cursor) {
+        if child.kind() != member_kind {
+            continue;
+        }
+        let name = child
+            .child_by_field_name("name")
+            .map(|n| source[n.byte_range()].to_string());
+        let (start_byte, _) = find_attr_start(child);
+        fields.push(field_item(source, child, start_byte, name, member_visibility(child, source), parent.clone()));
+    }
+    fields
+}
+
+/// Whether `node` sits inside a `describe`/`it`/`test` callback (including
+/// modifiers like `describe.only`). Shared with the JavaScript extractor.
+pub fn is_test_node(node: Node, source: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == "call_expression" {
+            if let Some(callee) = n.child_by_field_name("function") {
+                let callee = &source[callee.byte_range()];
+                let base = callee.split('.').next().unwrap_or(callee);
+                if matches!(base, "describe" | "it" | "test") {
+                    return true;
+                }
+            }
+        }
+        current = n.parent();
+    }
+    false
+}
+
+/// Names and source module of an `export_statement` that re-exports rather than
+/// declares: `export { a, b as c } from './m'`, `export * from './m'`,
+/// `export * as ns from './m'`, or a local `export { a }`.
+/// Shared with the JavaScript extractor.
+pub fn extract_reexport(node: Node, source: &str) -> Option<(String, Option<String>)> {
+    let module = node
+        .child_by_field_name("source")
+        .map(|s| source[s.byte_range()].trim_matches(['\'', '"', '`']).to_string());
+
+    let mut names = Vec::new();
+    let mut has_clause = false;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "export_clause" => {
+                has_clause = true;
+                let mut spec_cursor = child.walk();
+                for spec in child.named_children(&mut spec_cursor) {
+                    if let Some(n) = spec.child_by_field_name("alias").or_else(|| spec.child_by_field_name("name")) {
+                        names.push(source[n.byte_range()].to_string());
+                    }
+                }
+            }
+            "namespace_export" => {
+                let mut ns_cursor = child.walk();
+                let ns = child.named_children(&mut ns_cursor).last();
+                names.push(ns.map_or("*".to_string(), |n| source[n.byte_range()].to_string()));
+            }
+            "*" => names.push("*".to_string()),
+            _ => {}
+        }
+    }
+
+    if module.is_none() && !has_clause {
+        return None;
+    }
+    Some((names.join(", "), module))
+}
+
 impl super::LanguageExtractor for TypeScriptExtractor {
     fn interface_query(&self) -> &str {
         crate::languages::typescript::INTERFACE_QUERY
@@ -73,13 +197,14 @@ impl super::LanguageExtractor for TypeScriptExtractor {
 
     fn node_kind_to_item_kind(&self, kind: &str) -> Option<ItemKind> {
         match kind {
-            "function_declaration" => Some(ItemKind::Function),
+            "function_declaration" | "function_signature" => Some(ItemKind::Function),
             "class_declaration" | "abstract_class_declaration" => Some(ItemKind::Class),
             "interface_declaration" => Some(ItemKind::Trait),
             "type_alias_declaration" => Some(ItemKind::TypeAlias),
             "enum_declaration" => Some(ItemKind::Enum),
             "import_statement" => Some(ItemKind::Use),
-            "lexical_declaration" => Some(ItemKind::Const),
+            "lexical_declaration" | "variable_declaration" => Some(ItemKind::Const),
+            "module" | "internal_module" => Some(ItemKind::Mod),
             "method_definition" => Some(ItemKind::Method),
             "export_statement" => {
                 // Check inner declaration
@@ -98,18 +223,44 @@ impl super::LanguageExtractor for TypeScriptExtractor {
         }
     }
 
-    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut BTreeMap<usize, Item>) {
+    fn extract_parameters(&self, node: Node, source: &str) -> Option<Vec<Parameter>> {
+        jsdoc::extract_parameters(node, source)
+    }
+
+    fn extract_return_doc(&self, node: Node, source: &str) -> Option<String> {
+        jsdoc::extract_return_doc(node, source)
+    }
+
+    fn extract_reexport(&self, node: Node, source: &str) -> Option<(String, Option<String>)> {
+        extract_reexport(node, source)
+    }
+
+    fn is_test(&self, node: Node, source: &str) -> bool {
+        is_test_node(node, source)
+    }
+
+    fn extract_members(&self, source: &str, node: Node) -> Vec<Item> {
+        let mut members = extract_declared_fields(source, node);
+        members.extend(extract_parameter_properties(source, node));
+        members
+    }
+
+    fn extract_methods_from_block(&self, source: &str, block_node: tree_sitter::Node, items: &mut BTreeMap<usize, Item>, style: BodyStyle) {
         let body = match block_node.child_by_field_name("body") {
             Some(b) if b.kind() == "class_body" => b,
             _ => return,
         };
+        let parent = block_node
+            .child_by_field_name("name")
+            .map(|n| source[n.byte_range()].to_string());
 
         let _is_abstract_class = block_node.kind() == "abstract_class_declaration";
 
         let mut cursor = body.walk();
         for child in body.children(&mut cursor) {
             let is_abstract_method = child.kind() == "abstract_method_signature";
-            if child.kind() != "method_definition" && !is_abstract_method {
+            // Declared classes list bodiless method signatures
+            if child.kind() != "method_definition" && child.kind() != "method_signature" && !is_abstract_method {
                 continue;
             }
 
@@ -125,12 +276,13 @@ impl super::LanguageExtractor for TypeScriptExtractor {
                 let text = &source[effective_start_byte..child.end_byte()];
                 (text.to_string(), Vec::new(), false)
             } else if let Some(body) = child.child_by_field_name("body") {
-                let (c, m) = collapse_body(
+                let (c, m) = render_body(
                     source,
                     effective_start_byte,
                     child.end_byte(),
                     body.start_byte(),
                     body.end_byte(),
+                    style,
                 );
                 (c, m, true)
             } else {
@@ -156,6 +308,16 @@ impl super::LanguageExtractor for TypeScriptExtractor {
                 body: if has_body { Some("{ ... }".to_string()) } else { None },
                 content,
                 line_mappings,
+                parameters: jsdoc::extract_parameters(child, source),
+                type_parameters: type_parameters(child, source),
+                return_doc: jsdoc::extract_return_doc(child, source),
+                source_module: None,
+                resolved: None,
+                parent: parent.clone(),
+                node_kind: child.kind().to_string(),
+                is_test: is_test_node(child, source),
+                modifiers: Modifiers::from_node(child),
+                decorators: decorators(child, source),
             });
         }
     }
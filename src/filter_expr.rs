@@ -0,0 +1,264 @@
+//! `--where` filter expressions.
+//!
+//! A small boolean language over items, e.g. `kind=fn and pub and lines>20`:
+//!
+//! - `pub` — the item is public
+//! - `test` — the item is a test
+//! - `kind=KIND` / `kind!=KIND` — kind names as in JSON output (`fn` for function)
+//! - `name=GLOB` / `name!=GLOB` — glob on the item name (`get*`); unnamed items never match
+//! - `lines OP N` — line count, with `OP` one of `=`, `!=`, `<`, `<=`, `>`, `>=`
+//!
+//! Terms combine with `not`, `and` and `or` (in that precedence) and parentheses.
+//! Values may be quoted with `'` or `"`.
+
+use crate::error::CodeviewError;
+use crate::extractor::{Item, ItemKind};
+use globset::{Glob, GlobMatcher};
+
+/// A parsed `--where` expression.
+#[derive(Debug, Clone)]
+pub enum FilterExpr {
+    Public,
+    Test,
+    Kind(ItemKind, bool),
+    Name(GlobMatcher, bool),
+    Lines(Comparison, usize),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Op(Comparison),
+    Open,
+    Close,
+}
+
+impl FilterExpr {
+    /// Parse an expression, reporting the offending token on error.
+    pub fn parse(input: &str) -> Result<FilterExpr, CodeviewError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0 };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(error(format!("unexpected {}", describe(token)))),
+        }
+    }
+
+    /// Whether `item` satisfies the expression.
+    pub fn matches(&self, item: &Item) -> bool {
+        match self {
+            FilterExpr::Public => item.is_public(),
+            FilterExpr::Test => item.is_test,
+            FilterExpr::Kind(kind, equal) => (item.kind == *kind) == *equal,
+            FilterExpr::Name(glob, equal) => item.name.as_deref().is_some_and(|n| glob.is_match(n) == *equal),
+            FilterExpr::Lines(op, n) => {
+                let lines = item.line_end + 1 - item.line_start;
+                match op {
+                    Comparison::Eq => lines == *n,
+                    Comparison::Ne => lines != *n,
+                    Comparison::Lt => lines < *n,
+                    Comparison::Le => lines <= *n,
+                    Comparison::Gt => lines > *n,
+                    Comparison::Ge => lines >= *n,
+                }
+            }
+            FilterExpr::Not(inner) => !inner.matches(item),
+            FilterExpr::And(a, b) => a.matches(item) && b.matches(item),
+            FilterExpr::Or(a, b) => a.matches(item) || b.matches(item),
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn keyword(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Word(w)) if w == word) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn or(&mut self) -> Result<FilterExpr, CodeviewError> {
+        let mut expr = self.and()?;
+        while self.keyword("or") {
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<FilterExpr, CodeviewError> {
+        let mut expr = self.unary()?;
+        while self.keyword("and") {
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<FilterExpr, CodeviewError> {
+        if self.keyword("not") {
+            return Ok(FilterExpr::Not(Box::new(self.unary()?)));
+        }
+        match self.advance().cloned() {
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.advance() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err(error("missing ')'".to_string())),
+                }
+            }
+            Some(Token::Word(word)) => self.term(&word),
+            Some(token) => Err(error(format!("unexpected {}", describe(&token)))),
+            None => Err(error("expression ends early".to_string())),
+        }
+    }
+
+    fn term(&mut self, field: &str) -> Result<FilterExpr, CodeviewError> {
+        match field {
+            "pub" => return Ok(FilterExpr::Public),
+            "test" => return Ok(FilterExpr::Test),
+            "kind" | "name" | "lines" => {}
+            _ => return Err(error(format!("unknown term '{}' (expected pub, test, kind, name or lines)", field))),
+        }
+        let op = match self.advance() {
+            Some(Token::Op(op)) => *op,
+            _ => return Err(error(format!("expected a comparison after '{}'", field))),
+        };
+        let value = match self.advance() {
+            Some(Token::Word(value)) => value.clone(),
+            _ => return Err(error(format!("expected a value after '{}'", field))),
+        };
+        let equal = match op {
+            Comparison::Eq => true,
+            Comparison::Ne => false,
+            _ if field != "lines" => return Err(error(format!("'{}' only supports = and !=", field))),
+            _ => true,
+        };
+        match field {
+            "kind" => {
+                let kind = ItemKind::from_name(&value).ok_or_else(|| error(format!("unknown kind '{}'", value)))?;
+                Ok(FilterExpr::Kind(kind, equal))
+            }
+            "name" => {
+                let glob = Glob::new(&value).map_err(|e| error(format!("invalid name glob '{}': {}", value, e)))?;
+                Ok(FilterExpr::Name(glob.compile_matcher(), equal))
+            }
+            _ => {
+                let n = value.parse().map_err(|_| error(format!("expected a line count, got '{}'", value)))?;
+                Ok(FilterExpr::Lines(op, n))
+            }
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, CodeviewError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' => {
+                chars.next();
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let or_equal = chars.next_if_eq(&'=').is_some();
+                let op = match (c, or_equal) {
+                    ('=', _) => Comparison::Eq,
+                    ('!', true) => Comparison::Ne,
+                    ('<', false) => Comparison::Lt,
+                    ('<', true) => Comparison::Le,
+                    ('>', false) => Comparison::Gt,
+                    ('>', true) => Comparison::Ge,
+                    _ => return Err(error("expected '!='".to_string())),
+                };
+                tokens.push(Token::Op(op));
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some(q) => value.push(q),
+                        None => return Err(error(format!("unterminated {} quote", c))),
+                    }
+                }
+                tokens.push(Token::Word(value));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(ch) = chars.next_if(|ch| !ch.is_whitespace() && !"()=!<>'\"".contains(*ch)) {
+                    word.push(ch);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(word) => format!("'{}'", word),
+        Token::Op(_) => "comparison".to_string(),
+        Token::Open => "'('".to_string(),
+        Token::Close => "')'".to_string(),
+    }
+}
+
+fn error(message: String) -> CodeviewError {
+    CodeviewError::ParseError(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precedence_not_and_or() {
+        let expr = FilterExpr::parse("not pub and kind=fn or lines>=3").unwrap();
+        assert!(matches!(expr, FilterExpr::Or(ref a, _) if matches!(**a, FilterExpr::And(ref n, _) if matches!(**n, FilterExpr::Not(_)))));
+    }
+
+    #[test]
+    fn parse_errors_name_the_problem() {
+        let err = |s| FilterExpr::parse(s).unwrap_err().to_string();
+        assert!(err("kind=widget").contains("unknown kind 'widget'"));
+        assert!(err("size>3").contains("unknown term 'size'"));
+        assert!(err("(pub").contains("missing ')'"));
+        assert!(err("name<abc").contains("only supports = and !="));
+        assert!(err("name='foo").contains("unterminated ' quote"));
+    }
+}
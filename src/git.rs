@@ -0,0 +1,158 @@
+//! Thin wrappers around the `git` CLI.
+//!
+//! Every helper degrades gracefully: when `git` is missing or the path is not
+//! inside a repository, callers get an empty result (or `None`) instead of an error.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Last-commit information for a single line.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlameInfo {
+    pub author: String,
+    pub short_sha: String,
+}
+
+/// Run `git blame --porcelain` for the given 1-indexed lines of `path` in a
+/// single invocation. Returns a map from line number to blame info.
+pub fn blame_lines(path: &Path, lines: &[usize]) -> HashMap<usize, BlameInfo> {
+    if lines.is_empty() {
+        return HashMap::new();
+    }
+
+    let dir = match path.parent() {
+        Some(d) if !d.as_os_str().is_empty() => d,
+        _ => Path::new("."),
+    };
+    let file_name = match path.file_name() {
+        Some(f) => f,
+        None => return HashMap::new(),
+    };
+
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(dir).arg("blame").arg("--porcelain");
+    for line in lines {
+        cmd.arg("-L").arg(format!("{},{}", line, line));
+    }
+    cmd.arg("--").arg(file_name);
+
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            parse_porcelain(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => HashMap::new(),
+    }
+}
+
+/// Files under `dir` that `git status` reports as modified, added or renamed,
+/// staged or not. Deleted and untracked files are left out. Paths are absolute.
+/// `None` when `dir` is not inside a repository (or `git` is missing).
+pub fn modified_files(dir: &Path) -> Option<Vec<PathBuf>> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let root = git(&["rev-parse", "--show-toplevel"])?;
+    let status = git(&["status", "--porcelain", "-z", "--untracked-files=no", "--", "."])?;
+    let root = Path::new(root.trim_end());
+    Some(parse_status(&status).into_iter().map(|path| root.join(path)).collect())
+}
+
+/// Paths of modified/added/renamed entries in `git status --porcelain -z`
+/// output, relative to the repository root.
+fn parse_status(output: &str) -> Vec<&str> {
+    let mut paths = Vec::new();
+    let mut entries = output.split('\0').filter(|e| !e.is_empty());
+    while let Some(entry) = entries.next() {
+        let (Some(xy), Some(path)) = (entry.get(..2), entry.get(3..)) else {
+            continue;
+        };
+        // A rename is followed by its original path
+        if xy.contains(['R', 'C']) {
+            entries.next();
+        }
+        if xy.contains(['M', 'A', 'R', 'C']) && !xy.contains('D') {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Parse `git blame --porcelain` output into a line → blame map.
+/// Author headers are only emitted the first time a commit appears, so they are
+/// remembered per commit.
+fn parse_porcelain(output: &str) -> HashMap<usize, BlameInfo> {
+    let mut authors: HashMap<String, String> = HashMap::new();
+    let mut result = HashMap::new();
+    let mut current: Option<(String, usize)> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some((sha, final_line)) = current.take() {
+                let author = authors.get(&sha).cloned().unwrap_or_default();
+                result.insert(final_line, BlameInfo {
+                    author,
+                    short_sha: sha.chars().take(7).collect(),
+                });
+            }
+            continue;
+        }
+
+        if let Some(author) = line.strip_prefix("author ") {
+            if let Some((sha, _)) = &current {
+                authors.insert(sha.clone(), author.to_string());
+            }
+            continue;
+        }
+
+        let mut parts = line.split(' ');
+        if let (Some(sha), Some(_orig), Some(final_line)) = (parts.next(), parts.next(), parts.next()) {
+            if sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+                if let Ok(n) = final_line.parse() {
+                    current = Some((sha.to_string(), n));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_porcelain_reuses_author_for_repeat_commit() {
+        let sha = "a".repeat(40);
+        let output = format!(
+            "{sha} 1 1 1\nauthor Alice\nauthor-mail <a@x>\nsummary init\nfilename f.rs\n\tfn a() {{}}\n{sha} 3 3 1\n\tfn b() {{}}\n"
+        );
+        let map = parse_porcelain(&output);
+        assert_eq!(map[&1].author, "Alice");
+        assert_eq!(map[&3].author, "Alice");
+        assert_eq!(map[&3].short_sha, "aaaaaaa");
+    }
+
+    #[test]
+    fn parse_status_keeps_modified_and_added() {
+        let output = " M src/a.rs\0A  src/b.py\0D  gone.rs\0R  new.ts\0old.ts\0MM both.rs\0";
+        assert_eq!(parse_status(output), vec!["src/a.rs", "src/b.py", "new.ts", "both.rs"]);
+    }
+
+    #[test]
+    fn blame_outside_repo_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("f.rs");
+        std::fs::write(&file, "fn a() {}\n").unwrap();
+        assert!(blame_lines(&file, &[1]).is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+//! Largest functions across a tree, for hotspot analysis.
+
+use crate::error::CodeviewError;
+use crate::extractor::{interface, ItemKind};
+use crate::xref::parse_files;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Options for hotspot scans.
+#[derive(Default)]
+pub struct HotspotOptions {
+    pub depth: Option<usize>,
+    pub ext: Vec<String>,
+    /// Keep only the N largest functions (all when `None`).
+    pub top: Option<usize>,
+}
+
+/// A function or method and its size in lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hotspot {
+    pub path: String,
+    pub name: String,
+    pub kind: ItemKind,
+    pub line_start: usize,
+    pub lines: usize,
+    /// Collapsed signature on one line, e.g. `pub fn run(x: u32) -> u32 { ... }`.
+    pub signature: String,
+}
+
+/// Functions and methods under `path`, largest first. Ties are broken by path
+/// and line so the order is stable.
+pub fn find_hotspots(path: &str, options: &HotspotOptions) -> Result<Vec<Hotspot>, CodeviewError> {
+    let files = parse_files(Path::new(path), options.depth, &options.ext)?;
+
+    let mut hotspots = Vec::new();
+    for file in &files {
+        for item in interface::extract(&file.source, &file.tree, file.language) {
+            if !matches!(item.kind, ItemKind::Function | ItemKind::Method) {
+                continue;
+            }
+            let Some(name) = item.name else { continue };
+            hotspots.push(Hotspot {
+                path: file.path.clone(),
+                name,
+                kind: item.kind,
+                line_start: item.line_start,
+                lines: item.line_end - item.line_start + 1,
+                signature: one_line_signature(&item.content),
+            });
+        }
+    }
+
+    hotspots.sort_by(|a, b| {
+        b.lines
+            .cmp(&a.lines)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.line_start.cmp(&b.line_start))
+    });
+    if let Some(top) = options.top {
+        hotspots.truncate(top);
+    }
+    Ok(hotspots)
+}
+
+/// Format one line per hotspot: size, location, and name, optionally followed
+/// by the collapsed signature on an indented line.
+pub fn format_hotspots(hotspots: &[Hotspot], signatures: bool) -> String {
+    let width = hotspots.first().map_or(1, |h| h.lines.to_string().len());
+    let mut output = String::new();
+    for h in hotspots {
+        writeln!(output, "{:>width$}  {}:{}  {}", h.lines, h.path, h.line_start, h.name).unwrap();
+        if signatures {
+            writeln!(output, "{:>width$}    {}", "", h.signature).unwrap();
+        }
+    }
+    output
+}
+
+/// Collapsed item content without attribute, decorator, or comment lines, joined onto one line.
+pub(crate) fn one_line_signature(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !(l.starts_with("#[") || l.starts_with('@') || l.starts_with("//") || l.starts_with("///")))
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+        // Undo the spacing left by parameter lists that were split across lines
+        .replace("( ", "(")
+        .replace(", )", ")")
+        .replace(" )", ")")
+}
@@ -30,6 +30,15 @@ pub const INTERFACE_QUERY: &str = r#"
 (program
   (import_statement) @item)
 
+; Re-exports and export lists (export { a } from './m', export * from './m', export { a })
+(program
+  (export_statement
+    (export_clause)) @item)
+
+(program
+  (export_statement
+    source: (string)) @item)
+
 ; Top-level lexical declarations (const/let)
 (program
   (lexical_declaration
@@ -67,6 +76,10 @@ pub const EXPAND_QUERY: &str = r#"
   name: (identifier) @name
   body: (class_body) @body) @item
 
+(method_definition
+  name: (property_identifier) @name
+  body: (statement_block) @body) @item
+
 (lexical_declaration
   (variable_declarator
     name: (identifier) @name)) @item
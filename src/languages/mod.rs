@@ -41,6 +41,19 @@ pub fn detect_language(path: &Path) -> Result<Language, CodeviewError> {
     }
 }
 
+/// Parse a language name (`python`) or its file extension (`py`).
+pub fn parse_language(name: &str) -> Option<Language> {
+    match name.to_ascii_lowercase().as_str() {
+        "rust" | "rs" => Some(Language::Rust),
+        "typescript" | "ts" => Some(Language::TypeScript),
+        "tsx" => Some(Language::Tsx),
+        "javascript" | "js" => Some(Language::JavaScript),
+        "jsx" => Some(Language::Jsx),
+        "python" | "py" => Some(Language::Python),
+        _ => None,
+    }
+}
+
 /// Check if a file should be processed based on its extension
 pub fn is_supported_file(path: &Path) -> bool {
     path.extension()
@@ -72,6 +85,13 @@ mod tests {
         assert_eq!(lang, Language::Rust);
     }
 
+    #[test]
+    fn parse_language_names_and_extensions() {
+        assert_eq!(parse_language("python"), Some(Language::Python));
+        assert_eq!(parse_language("TS"), Some(Language::TypeScript));
+        assert_eq!(parse_language("sql"), None);
+    }
+
     #[test]
     fn detect_language_unsupported() {
         let err = detect_language(Path::new("foo.rb")).unwrap_err();
@@ -63,23 +63,30 @@ pub const INTERFACE_QUERY: &str = r#"
     (type_alias_declaration
       name: (type_identifier) @name)) @item)
 
-; Top-level enum declarations
+; Top-level enum declarations (no @body: members and their values are shown in full)
 (program
   (enum_declaration
-    name: (identifier) @name
-    body: (enum_body) @body) @item)
+    name: (identifier) @name) @item)
 
 ; Exported enum declarations
 (program
   (export_statement
     (enum_declaration
-      name: (identifier) @name
-      body: (enum_body) @body)) @item)
+      name: (identifier) @name)) @item)
 
 ; Top-level import statements
 (program
   (import_statement) @item)
 
+; Re-exports and export lists (export { a } from './m', export * from './m', export { a })
+(program
+  (export_statement
+    (export_clause)) @item)
+
+(program
+  (export_statement
+    source: (string)) @item)
+
 ; Top-level lexical declarations (const/let)
 (program
   (lexical_declaration
@@ -92,6 +99,46 @@ pub const INTERFACE_QUERY: &str = r#"
     (lexical_declaration
       (variable_declarator
         name: (identifier) @name))) @item)
+
+; Function signatures without a body (overloads, .d.ts declarations)
+(program
+  (function_signature
+    name: (identifier) @name) @item)
+
+(program
+  (export_statement
+    (function_signature
+      name: (identifier) @name)) @item)
+
+; Ambient declarations (declare function/const/class/module/namespace ...), as in .d.ts files
+(program
+  (ambient_declaration
+    [
+      (function_signature name: (identifier) @name)
+      (lexical_declaration (variable_declarator name: (identifier) @name))
+      (variable_declaration (variable_declarator name: (identifier) @name))
+      (class_declaration name: (type_identifier) @name)
+      (interface_declaration name: (type_identifier) @name)
+      (type_alias_declaration name: (type_identifier) @name)
+      (enum_declaration name: (identifier) @name)
+      (module name: [(identifier) @name (nested_identifier) @name (string (string_fragment) @name)])
+      (internal_module name: [(identifier) @name (nested_identifier) @name])
+    ]) @item)
+
+(program
+  (export_statement
+    (ambient_declaration
+      [
+        (function_signature name: (identifier) @name)
+        (lexical_declaration (variable_declarator name: (identifier) @name))
+        (variable_declaration (variable_declarator name: (identifier) @name))
+        (class_declaration name: (type_identifier) @name)
+        (interface_declaration name: (type_identifier) @name)
+        (type_alias_declaration name: (type_identifier) @name)
+        (enum_declaration name: (identifier) @name)
+        (module name: [(identifier) @name (nested_identifier) @name (string (string_fragment) @name)])
+        (internal_module name: [(identifier) @name (nested_identifier) @name])
+      ])) @item)
 "#;
 
 /// Tree-sitter query for TypeScript/TSX expand (not restricted to top-level).
@@ -108,6 +155,10 @@ pub const EXPAND_QUERY: &str = r#"
   name: (type_identifier) @name
   body: (class_body) @body) @item
 
+(method_definition
+  name: (property_identifier) @name
+  body: (statement_block) @body) @item
+
 (interface_declaration
   name: (type_identifier) @name
   body: (interface_body) @body) @item
@@ -158,4 +209,38 @@ pub const EXPAND_QUERY: &str = r#"
   (lexical_declaration
     (variable_declarator
       name: (identifier) @name))) @item
+
+(function_signature
+  name: (identifier) @name) @item
+
+(export_statement
+  (function_signature
+    name: (identifier) @name)) @item
+
+(ambient_declaration
+  [
+    (function_signature name: (identifier) @name)
+    (lexical_declaration (variable_declarator name: (identifier) @name))
+    (variable_declaration (variable_declarator name: (identifier) @name))
+    (class_declaration name: (type_identifier) @name)
+    (interface_declaration name: (type_identifier) @name)
+    (type_alias_declaration name: (type_identifier) @name)
+    (enum_declaration name: (identifier) @name)
+    (module name: [(identifier) @name (nested_identifier) @name (string (string_fragment) @name)])
+    (internal_module name: [(identifier) @name (nested_identifier) @name])
+  ]) @item
+
+(export_statement
+  (ambient_declaration
+    [
+      (function_signature name: (identifier) @name)
+      (lexical_declaration (variable_declarator name: (identifier) @name))
+      (variable_declaration (variable_declarator name: (identifier) @name))
+      (class_declaration name: (type_identifier) @name)
+      (interface_declaration name: (type_identifier) @name)
+      (type_alias_declaration name: (type_identifier) @name)
+      (enum_declaration name: (identifier) @name)
+      (module name: [(identifier) @name (nested_identifier) @name (string (string_fragment) @name)])
+      (internal_module name: [(identifier) @name (nested_identifier) @name])
+    ])) @item
 "#;
@@ -1,34 +1,120 @@
 mod error;
+mod git;
 mod parser;
 mod extractor;
+mod filter_expr;
 mod languages;
 mod output;
 mod walk;
+pub mod callgraph;
+pub mod compare;
+pub mod contract;
 pub mod editor;
+pub mod hotspots;
 pub mod search;
+pub mod stubs;
+pub mod watch;
+pub mod xref;
 
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::ops::ControlFlow;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
 
 pub use error::CodeviewError;
-pub use output::OutputFormat;
-pub use languages::Language;
-use extractor::{Item, ItemKind};
+pub use filter_expr::FilterExpr;
+pub use output::{OutputFormat, PathStyle};
+pub use output::template::Template;
+pub use languages::{parse_language, Language};
+pub use parser::ParserCache;
+pub use extractor::{Item, ItemKind, Modifiers, ParamKind, Parameter, Visibility};
+use extractor::collapse::BodyStyle;
+use extractor::expand::ExpandStyle;
 
 /// Options for processing paths
+#[derive(Default, Clone)]
 pub struct ProcessOptions {
     pub symbols: Vec<String>,
     pub pub_only: bool,
+    /// Keep private methods and fields of classes/impls that `pub_only` would hide
+    pub include_private: bool,
     pub fns_only: bool,
     pub types_only: bool,
     pub no_tests: bool,
     pub depth: Option<usize>,
     pub format: OutputFormat,
     pub stats: bool,
+    /// In plain output, lead with one summary line and separate files with `// ==== path ====` banners
+    pub merge: bool,
+    /// Report each public type's count of public methods instead of content
+    pub type_api: bool,
     pub ext: Vec<String>,
+    /// Path globs (relative to the scanned directory) whose files are parsed as the given language
+    pub dir_lang: Vec<(String, Language)>,
+    /// Path globs (relative to the scanned directory) a file must match to be included
+    pub include_globs: Vec<String>,
+    /// Path globs (relative to the scanned directory) whose files are skipped
+    pub exclude_globs: Vec<String>,
     pub signatures: bool,
     pub max_lines: Option<usize>,
     pub list_symbols: bool,
+    /// Cap on items shown per file (after filtering); the rest are summarized
+    pub max_symbols: Option<usize>,
+    /// Cap on items shown across all files; the rest are summarized
+    pub limit: Option<usize>,
+    /// Decode non-UTF-8 files with replacement characters instead of failing
+    pub lossy: bool,
+    /// Show the first N lines of each body instead of `{ ... }` in interface mode
+    pub preview: Option<usize>,
+    /// In interface mode, show function bodies in full instead of collapsing them
+    pub no_collapse: bool,
+    /// Emit per-file counts and a content SHA-256 (JSON Lines) instead of code
+    pub fingerprint: bool,
+    /// Emit one `path:line:col:kind:name` jump target per item instead of code
+    pub targets: bool,
+    /// Emit one `path:line: signature` line per definition instead of code
+    pub grep_defs: bool,
+    /// Only show methods whose enclosing impl/class type has this name
+    pub method_of: Option<String>,
+    /// Only show items matching this `--where` expression
+    pub filter: Option<FilterExpr>,
+    /// In expand mode, collapse the bodies of definitions nested in each symbol
+    pub nest_summary: bool,
+    /// In expand mode, collapse match/switch arm bodies while keeping arm patterns
+    pub collapse_match: bool,
+    /// In expand mode, abbreviate string literals longer than this many characters
+    pub collapse_strings: Option<usize>,
+    /// In expand mode, collapse blocks nested more than this many levels inside each body
+    pub body_depth: Option<usize>,
+    /// In expand mode, show the submodules of an expanded Rust module with function bodies collapsed
+    pub recursive_expand: bool,
+    /// In expand mode, strip the indentation shared by every line of each symbol
+    pub dedent: bool,
+    /// Include the raw tree-sitter node kind in JSON items
+    pub debug_kinds: bool,
+    /// With JSON output, emit one NDJSON line per item instead of a per-file array
+    pub per_symbol_stream: bool,
+    /// Include each JSON item's `(line_number, text)` line mappings
+    pub with_mappings: bool,
+    /// Resolve relative re-export modules (`./user`) to the file they name
+    pub resolve_reexports: bool,
+    /// Prefix list-symbols names with their enclosing symbol chain
+    pub show_parents: bool,
+    /// How file paths are printed in plain, JSON, and stats output
+    pub path_style: PathStyle,
+    /// Print runs of consecutive consts/statics as one block under a `// constants` heading
+    pub group_consts: bool,
+    /// List files without any items (interface mode) with a `// (no items)` marker
+    pub emit_empty: bool,
+    /// In directory mode, only process files git reports as modified or staged
+    pub git_modified: bool,
+    /// In interface mode, keep doc comments above items (for Python, the first docstring line)
+    pub docs: bool,
+    /// Plain mode: render each item as one line from this template instead
+    pub template: Option<Template>,
+    /// Columns per nesting level in outline output (default 2)
+    pub indent: Option<usize>,
 }
 
 /// Process a file or directory and return formatted output
@@ -37,7 +123,76 @@ pub fn process_path(
     options: ProcessOptions,
 ) -> Result<String, CodeviewError> {
     let path = Path::new(path);
-    
+    let expand_mode = !options.symbols.is_empty();
+
+    let mut source_sizes: Vec<(usize, usize)> = Vec::new();
+    let mut files_items: Vec<(String, Vec<Item>)> = Vec::new();
+    visit_files(path, &options, &mut |file_path, items, size| {
+        source_sizes.push(size);
+        files_items.push((file_path, items));
+        Ok(ControlFlow::Continue(()))
+    })?;
+
+    render_files(files_items, &source_sizes, path, expand_mode, &options)
+}
+
+/// Like [`process_path`] with `per_symbol_stream`, but each file's NDJSON lines
+/// are written to `out` and flushed as soon as that file is extracted rather
+/// than after the whole tree. Output modes that take precedence over the stream
+/// (stats, fingerprints, ...) are written in one piece as [`process_path`] returns them.
+///
+/// When `max_symbols` or `limit` cut the stream, it ends with one
+/// `{"omitted": N}` record counting the dropped items. Once `limit` is used up
+/// no further files are parsed; the record then also carries
+/// `files_not_scanned`.
+pub fn stream_symbols(path: &str, options: ProcessOptions, out: &mut impl std::io::Write) -> Result<(), CodeviewError> {
+    let streamed = options.per_symbol_stream
+        && options.format == OutputFormat::Json
+        && !(options.fingerprint || options.targets || options.grep_defs || options.type_api || options.stats || options.list_symbols);
+    if !streamed {
+        let output = process_path(path, options)?;
+        return out.write_all(output.as_bytes()).map_err(|e| serde_json::Error::io(e).into());
+    }
+
+    let root = Path::new(path);
+    let expand_mode = !options.symbols.is_empty();
+    let mut remaining = options.limit;
+    let mut omitted = 0;
+    let files_not_scanned = visit_files(root, &options, &mut |file_path, items, _| {
+        let mut items = filter_items(items, expand_mode, &options);
+        let found = items.len();
+        if let Some(max) = options.max_symbols {
+            items.truncate(max);
+        }
+        if let Some(left) = remaining.as_mut() {
+            items.truncate(*left);
+            *left -= items.len();
+        }
+        omitted += found - items.len();
+        let file_path = options.path_style.apply(&file_path, root);
+        output::json::write_symbol_stream(out, &file_path, &items, options.debug_kinds, options.with_mappings)?;
+        out.flush().map_err(serde_json::Error::io)?;
+        Ok(if remaining == Some(0) { ControlFlow::Break(()) } else { ControlFlow::Continue(()) })
+    })?;
+    if omitted > 0 || files_not_scanned > 0 {
+        output::json::write_stream_summary(out, omitted, files_not_scanned)?;
+        out.flush().map_err(serde_json::Error::io)?;
+    }
+    Ok(())
+}
+
+/// Receives one extracted file's path, items and `(lines, bytes)` size.
+type FileVisitor<'a> = dyn FnMut(String, Vec<Item>, (usize, usize)) -> Result<ControlFlow<()>, CodeviewError> + 'a;
+
+/// Extract the items of `path` (a file, or every file under a directory) and
+/// hand each file's items and `(lines, bytes)` size to `visit` as soon as they
+/// are extracted. Unreadable files in a directory are skipped with a warning.
+/// `visit` can stop the walk; the number of files it then left unread is returned.
+fn visit_files(
+    path: &Path,
+    options: &ProcessOptions,
+    visit: &mut FileVisitor,
+) -> Result<usize, CodeviewError> {
     if !path.exists() {
         return Err(CodeviewError::PathNotFound(path.display().to_string()));
     }
@@ -51,14 +206,30 @@ pub fn process_path(
         (options.symbols.clone(), Vec::new())
     };
     
-    let mut source_sizes: Vec<(usize, usize)> = Vec::new();
-    let files_items: Vec<(String, Vec<Item>)> = if path.is_file() {
-        let (items, lines, bytes) = process_file(path, &symbols, expand_mode, options.signatures, &expand_methods)?;
-        source_sizes.push((lines, bytes));
-        vec![(path.to_string_lossy().to_string(), items)]
+    if path.is_file() {
+        let (items, lines, bytes) = process_file(path, None, &symbols, &expand_methods, options)?;
+        if expand_mode {
+            warn_missing_symbols(&read_source(path, options.lossy)?, languages::detect_language(path)?, &symbols, &items)?;
+        }
+        // A single file leaves nothing unread, whether or not `visit` stops
+        let _ = visit(path.to_string_lossy().to_string(), items, (lines, bytes))?;
+        Ok(0)
     } else if path.is_dir() {
-        let files = walk::walk_directory(path, options.depth, &options.ext)?;
-        let mut results = Vec::new();
+        let overrides = walk::LanguageOverrides::new(&options.dir_lang)?;
+        let globs = walk::PathGlobs::new(&options.include_globs, &options.exclude_globs)?;
+        let mut files = walk::walk_directory_with(path, options.depth, &options.ext, &overrides, &globs)?;
+        if options.git_modified {
+            // Outside a repository nothing counts as modified
+            let modified: HashSet<PathBuf> = git::modified_files(path)
+                .unwrap_or_else(|| {
+                    eprintln!("Warning: not a git repository: {} (--git-modified matches no files)", path.display());
+                    Vec::new()
+                })
+                .iter()
+                .filter_map(|m| m.canonicalize().ok())
+                .collect();
+            files.retain(|f| f.canonicalize().is_ok_and(|f| modified.contains(&f)));
+        }
         // Track which symbols still need to be found for early exit in expand mode
         let mut remaining_symbols: Vec<&str> = if expand_mode {
             options.symbols.iter().map(|s| s.as_str()).collect()
@@ -66,91 +237,332 @@ pub fn process_path(
             Vec::new()
         };
         
-        for file_path in files {
-            match process_file(&file_path, &symbols, expand_mode, options.signatures, &expand_methods) {
+        let total = files.len();
+        for (n, file_path) in files.into_iter().enumerate() {
+            let language = file_path.strip_prefix(path).ok().and_then(|rel| overrides.language_for(rel));
+            match process_file(&file_path, language, &symbols, &expand_methods, options) {
                 Ok((items, lines, bytes)) => {
                     if expand_mode && !items.is_empty() {
                         // Remove found symbols from remaining set
                         for item in &items {
                             if let Some(name) = &item.name {
-                                remaining_symbols.retain(|s| *s != name.as_str());
+                                remaining_symbols.retain(|s| extractor::expand::split_qualified(s).1 != name.as_str());
                             }
                         }
                     }
-                    source_sizes.push((lines, bytes));
-                    results.push((file_path.to_string_lossy().to_string(), items));
+                    if visit(file_path.to_string_lossy().to_string(), items, (lines, bytes))?.is_break() {
+                        return Ok(total - n - 1);
+                    }
                     // Early exit: all symbols found
                     if expand_mode && remaining_symbols.is_empty() {
                         break;
                     }
                 }
+                Err(e @ CodeviewError::InvalidUtf8(_)) => {
+                    eprintln!("Warning: Skipping {} (use --lossy to decode it anyway)", e);
+                }
                 Err(e) => {
                     eprintln!("Warning: Failed to process {}: {}", file_path.display(), e);
                 }
             }
         }
-        results
+        Ok(0)
+    } else {
+        Err(CodeviewError::InvalidPath(path.display().to_string()))
+    }
+}
+
+/// Process in-memory `source` (e.g. an unsaved editor buffer read from stdin) as
+/// `language`, with the same options as [`process_path`]. Output labels it `<stdin>`.
+pub fn process_source(source: &str, language: Language, options: ProcessOptions) -> Result<String, CodeviewError> {
+    let label = Path::new("<stdin>");
+    let expand_mode = !options.symbols.is_empty();
+    let (symbols, expand_methods) = if options.signatures && options.symbols.len() > 1 {
+        (vec![options.symbols[0].clone()], options.symbols[1..].to_vec())
     } else {
-        return Err(CodeviewError::InvalidPath(path.display().to_string()));
+        (options.symbols.clone(), Vec::new())
     };
 
-    // Apply filters (union semantics: if multiple kind filters, match ANY)
+    let items = extract_items(source, label, language, &symbols, &expand_methods, &options)?;
+    if expand_mode {
+        warn_missing_symbols(source, language, &symbols, &items)?;
+    }
+    let source_sizes = [(source.lines().count(), source.len())];
+    render_files(vec![(label.to_string_lossy().to_string(), items)], &source_sizes, label, expand_mode, &options)
+}
+
+/// Drop the items of one file that the filter options exclude.
+fn filter_items(items: Vec<Item>, expand_mode: bool, options: &ProcessOptions) -> Vec<Item> {
+    // Union semantics: if multiple kind filters, match ANY
     let has_kind_filter = options.fns_only || options.types_only;
-    let filtered: Vec<(String, Vec<Item>)> = files_items
-        .into_iter()
-        .map(|(path, items)| {
-            let filtered_items = items
-                .into_iter()
-                .filter(|item| {
-                    if options.no_tests
-                        && matches!(item.kind, ItemKind::Mod)
-                        && item.name.as_deref() == Some("tests")
-                    {
-                        return false;
-                    }
-                    if options.pub_only && !item.is_public() {
-                        return false;
-                    }
-                    if has_kind_filter {
-                        let is_fn = matches!(item.kind, ItemKind::Function | ItemKind::Method);
-                        let is_type = matches!(
-                            item.kind,
-                            ItemKind::Struct | ItemKind::Enum | ItemKind::Trait | ItemKind::TypeAlias | ItemKind::Class
-                        );
-                        let mut matched = false;
-                        if options.fns_only && is_fn { matched = true; }
-                        if options.types_only && is_type { matched = true; }
-                        if !matched { return false; }
-                        // When only --types (no --fns), still hide standalone methods
-                        if matches!(item.kind, ItemKind::Method) && !options.fns_only {
-                            return false;
-                        }
-                    } else {
-                        // No kind filter: hide standalone Method items (shown inside impl blocks)
-                        if matches!(item.kind, ItemKind::Method) {
-                            return false;
-                        }
-                    }
-                    true
-                })
-                .collect();
-            (path, filtered_items)
+    let selected = |item: &Item| {
+        if options.no_tests
+            && matches!(item.kind, ItemKind::Mod)
+            && item.name.as_deref() == Some("tests")
+        {
+            return false;
+        }
+        let private_member_kept = options.include_private
+            && item.parent.is_some()
+            && matches!(item.kind, ItemKind::Method | ItemKind::Field);
+        if options.pub_only && !item.is_public() && !private_member_kept {
+            return false;
+        }
+        if options.filter.as_ref().is_some_and(|expr| !expr.matches(item)) {
+            return false;
+        }
+        if let Some(ref type_name) = options.method_of {
+            // Generic arguments and paths are ignored: `impl<T> models::User<T>` matches `User`
+            let on_type = matches!(item.kind, ItemKind::Method)
+                && item.parent.as_deref().is_some_and(|p| extractor::expand::base_type_name(p) == type_name);
+            if !on_type {
+                return false;
+            }
+        }
+        if has_kind_filter {
+            let is_fn = matches!(item.kind, ItemKind::Function | ItemKind::Method);
+            let is_type = matches!(
+                item.kind,
+                ItemKind::Struct | ItemKind::Enum | ItemKind::Trait | ItemKind::TypeAlias | ItemKind::Class
+            );
+            let mut matched = false;
+            if options.fns_only && is_fn { matched = true; }
+            if options.types_only && is_type { matched = true; }
+            if !matched { return false; }
+            // When only --types (no --fns), still hide standalone methods
+            if matches!(item.kind, ItemKind::Method) && !options.fns_only {
+                return false;
+            }
+        }
+        true
+    };
+    // Members already render inside their owner; listings and JSON show them on their own,
+    // as do expand mode and reports on methods
+    let renders_in_owner = |item: &Item| match item.kind {
+        ItemKind::Variant | ItemKind::Field => {
+            options.stats
+                || !(options.list_symbols
+                    || options.grep_defs
+                    || matches!(
+                        options.format,
+                        OutputFormat::Json | OutputFormat::Xml | OutputFormat::Outline | OutputFormat::Ctags
+                    ))
+        }
+        ItemKind::Method => {
+            !has_kind_filter
+                && options.method_of.is_none()
+                && !expand_mode
+                && !matches!(options.format, OutputFormat::Outline | OutputFormat::Ctags)
+                && !options.type_api
+                && !options.grep_defs
+        }
+        _ => false,
+    };
+
+    let kept: Vec<bool> = items.iter().map(selected).collect();
+    // A --where expression can drop the owner (`kind=method`), leaving its members to show on their own
+    let owner_kept = |member: &Item| {
+        items.iter().zip(&kept).any(|(owner, &kept)| {
+            kept && matches!(owner.kind, ItemKind::Impl | ItemKind::Struct | ItemKind::Enum | ItemKind::Trait | ItemKind::Class)
+                && owner.line_start <= member.line_start
+                && member.line_end <= owner.line_end
         })
+    };
+    let hidden: Vec<bool> = items
+        .iter()
+        .zip(&kept)
+        .map(|(item, &kept)| kept && renders_in_owner(item) && (options.filter.is_none() || owner_kept(item)))
+        .collect();
+    items
+        .into_iter()
+        .zip(kept.into_iter().zip(hidden))
+        .filter_map(|(item, (kept, hidden))| (kept && !hidden).then_some(item))
+        .collect()
+}
+
+/// Filter, cap, and format extracted items. `path` is the scanned path that
+/// `path_style` is relative to.
+fn render_files(
+    files_items: Vec<(String, Vec<Item>)>,
+    source_sizes: &[(usize, usize)],
+    path: &Path,
+    expand_mode: bool,
+    options: &ProcessOptions,
+) -> Result<String, CodeviewError> {
+    let mut filtered: Vec<(String, Vec<Item>)> = files_items
+        .into_iter()
+        .map(|(path, items)| (path, filter_items(items, expand_mode, options)))
         .collect();
 
+    // Cap items per file, remembering how many were dropped for the marker
+    let mut omitted: Vec<usize> = Vec::new();
+    if let Some(max) = options.max_symbols {
+        for (_, items) in filtered.iter_mut() {
+            omitted.push(items.len().saturating_sub(max));
+            items.truncate(max);
+        }
+    }
+
+    // Cap items across files. Files cut down to nothing are dropped entirely.
+    let mut truncation = None;
+    if let Some(limit) = options.limit {
+        let total: usize = filtered.iter().map(|(_, items)| items.len()).sum();
+        if total > limit {
+            let mut kept = 0;
+            let mut cut_files = 0;
+            let mut keep = Vec::with_capacity(filtered.len());
+            for (_, items) in filtered.iter_mut() {
+                let take = (limit - kept).min(items.len());
+                if take < items.len() {
+                    cut_files += 1;
+                }
+                keep.push(items.is_empty() || take > 0);
+                items.truncate(take);
+                kept += take;
+            }
+            let mut keep_files = keep.iter();
+            filtered.retain(|_| *keep_files.next().unwrap());
+            if !omitted.is_empty() {
+                let mut keep_files = keep.iter();
+                omitted.retain(|_| *keep_files.next().unwrap());
+            }
+            truncation = Some(output::Truncation { omitted: total - limit, files: cut_files });
+        }
+    }
+
+    // Text formats end with a summary line; JSON, XML and tags record it in their own syntax
+    let limit_summary = truncation.map(|t| format!("\n... {} more items across {} files\n", t.omitted, t.files));
+
+    // Fingerprint, targets, defs, and tags output reread the files, so they keep the scan paths
+    if !(options.fingerprint || options.targets || options.grep_defs || options.format == OutputFormat::Ctags) {
+        for (file_path, _) in filtered.iter_mut() {
+            *file_path = options.path_style.apply(file_path, path);
+        }
+    }
+
     // Format output
-    if options.stats {
-        output::stats::format_output(&filtered, &source_sizes, options.format)
+    if options.fingerprint {
+        output::fingerprint::format_output(&filtered, source_sizes)
+    } else if options.targets {
+        output::targets::format_output(&filtered)
+    } else if options.grep_defs {
+        output::defs::format_output(&filtered, options.lossy)
+    } else if options.type_api {
+        output::stats::format_type_api(&filtered, options.format)
+    } else if options.stats {
+        output::stats::format_output(&filtered, source_sizes, options.format)
     } else if options.list_symbols {
-        output::plain::format_list_symbols(&filtered)
+        output::plain::format_list_symbols(&filtered, &omitted).map(|out| out + limit_summary.as_deref().unwrap_or(""))
+    } else if let Some(template) = options.template.as_ref().filter(|_| options.format == OutputFormat::Plain) {
+        output::template::format_output(&filtered, template).map(|out| out + limit_summary.as_deref().unwrap_or(""))
     } else {
         match options.format {
-            OutputFormat::Plain => output::plain::format_output(&filtered, expand_mode, options.max_lines),
-            OutputFormat::Json => output::json::format_output(&filtered),
+            OutputFormat::Plain => output::plain::format_output(&filtered, &omitted, expand_mode, options.max_lines, options.group_consts, options.emit_empty, options.merge)
+                .map(|out| out + limit_summary.as_deref().unwrap_or("")),
+            OutputFormat::Json if options.per_symbol_stream => output::json::format_symbol_stream(&filtered, options.debug_kinds, options.with_mappings),
+            OutputFormat::Json => output::json::format_output(&filtered, &omitted, truncation, options.debug_kinds, options.with_mappings),
+            OutputFormat::Markdown => output::markdown::format_output(&filtered).map(|out| out + limit_summary.as_deref().unwrap_or("")),
+            OutputFormat::Outline => output::outline::format_output(&filtered, options.indent.unwrap_or(2)).map(|out| out + limit_summary.as_deref().unwrap_or("")),
+            OutputFormat::Ctags => output::ctags::format_output(&filtered, truncation),
+            OutputFormat::Xml => output::xml::format_output(&filtered, truncation, options.debug_kinds),
         }
     }
 }
 
+/// Expand each of `symbols` from a single file and write it to
+/// `<dir>/<symbol>.<ext>`, using the source file's extension. Items sharing a
+/// name (e.g. a struct and its impl) go into the same file in source order.
+/// The source file is left untouched. Returns the written paths.
+pub fn split_symbols(path_str: &str, symbols: &[String], dir: &str) -> Result<Vec<PathBuf>, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    if path.is_dir() {
+        return Err(CodeviewError::InvalidPath(
+            "--split-to only works on single files, not directories".to_string(),
+        ));
+    }
+
+    let source = read_source(path, false)?;
+    let language = languages::detect_language(path)?;
+    let tree = parser::parse(&source, language)?;
+    let items = extractor::expand::extract(&source, &tree, symbols, language);
+
+    // Resolve everything before writing so a missing symbol leaves no partial output
+    let mut outputs = Vec::new();
+    for symbol in symbols {
+        // `User::new` is extracted (and written out) as `new`
+        let name = extractor::expand::split_qualified(symbol).1;
+        let parts: Vec<&str> = items
+            .iter()
+            .filter(|item| item.name.as_deref() == Some(name))
+            .map(|item| item.content.as_str())
+            .collect();
+        if parts.is_empty() {
+            return Err(CodeviewError::ParseError(format!("Symbol not found: {}", symbol)));
+        }
+        outputs.push((name, parts.join("\n\n") + "\n"));
+    }
+
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir).map_err(|e| CodeviewError::ReadError {
+        path: dir.display().to_string(),
+        source: e,
+    })?;
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+
+    let mut written = Vec::new();
+    for (name, content) in outputs {
+        let target = dir.join(format!("{}.{}", name, ext));
+        fs::write(&target, content).map_err(|e| CodeviewError::ReadError {
+            path: target.display().to_string(),
+            source: e,
+        })?;
+        written.push(target);
+    }
+    Ok(written)
+}
+
+/// Layout used by [`render_item`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Numbered content lines only, as in the default interface view
+    Interface,
+    /// A `name [start:end]` header followed by the numbered content, as in expand mode
+    Expand,
+}
+
+/// Render one item the same way the CLI prints it.
+///
+/// The item's `content` is printed as-is: collapsed bodies stay collapsed, so
+/// `Expand` only changes the layout, not what was extracted.
+///
+/// ```
+/// use codeview::{render_item, Item, ItemKind, RenderMode, Visibility};
+///
+/// let item = Item {
+///     visibility: Visibility::Public,
+///     ..Item::new(ItemKind::Function, Some("add"), "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}", 3, 5)
+/// };
+///
+/// assert_eq!(
+///     render_item(&item, RenderMode::Interface),
+///     "3 | pub fn add(a: i32, b: i32) -> i32 {\n4 |     a + b\n5 | }\n",
+/// );
+/// assert!(render_item(&item, RenderMode::Expand).starts_with("add [3:5]\n3 | pub fn add"));
+/// ```
+pub fn render_item(item: &Item, mode: RenderMode) -> String {
+    let body = output::plain::format_item(item);
+    match mode {
+        RenderMode::Interface => body,
+        RenderMode::Expand => match &item.name {
+            Some(name) => format!("{} [{}:{}]\n{}", name, item.line_start, item.line_end, body),
+            None => format!("[{}:{}]\n{}", item.line_start, item.line_end, body),
+        },
+    }
+}
+
 /// Returns (items, lines, bytes)
 /// Extract a line range from a file with structural context.
 ///
@@ -172,10 +584,7 @@ pub fn extract_lines(path_str: &str, lines_arg: &str) -> Result<String, Codeview
     // Parse the range
     let (start, end) = parse_line_range(lines_arg)?;
 
-    let source = fs::read_to_string(path).map_err(|e| CodeviewError::ReadError {
-        path: path.display().to_string(),
-        source: e,
-    })?;
+    let source = read_source(path, false)?;
 
     let total_lines = source.lines().count();
     if start > total_lines {
@@ -209,8 +618,248 @@ pub fn extract_lines(path_str: &str, lines_arg: &str) -> Result<String, Codeview
     Ok(output)
 }
 
+/// Print every line of a file prefixed with its enclosing symbol path,
+/// as `L<n> [<outer> > <inner>]: <text>` (`[]` for top-level lines).
+pub fn annotate_symbols(path_str: &str) -> Result<String, CodeviewError> {
+    use std::fmt::Write;
 
-fn parse_line_range(arg: &str) -> Result<(usize, usize), CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    if path.is_dir() {
+        return Err(CodeviewError::InvalidPath(
+            "--annotate-symbols only works on single files, not directories".to_string(),
+        ));
+    }
+
+    let source = read_source(path, false)?;
+    let language = languages::detect_language(path)?;
+    let tree = parser::parse(&source, language)?;
+    let symbols = search::enclosing_symbols_by_line(&tree, &source, language);
+
+    let mut output = String::new();
+    for (i, (line, path)) in source.lines().zip(symbols.iter()).enumerate() {
+        writeln!(output, "L{} [{}]: {}", i + 1, path.join(" > "), line).unwrap();
+    }
+    Ok(output)
+}
+
+/// Every item kind with what each language calls it, as a table or, with
+/// `json`, as `{ "kinds": [ { "kind", "names": { language: name } } ] }`.
+/// Languages that never produce a kind are left out of its row.
+pub fn kinds_legend(json: bool) -> Result<String, CodeviewError> {
+    const LANGUAGES: [(&str, Language); 4] = [
+        ("rust", Language::Rust),
+        ("typescript", Language::TypeScript),
+        ("javascript", Language::JavaScript),
+        ("python", Language::Python),
+    ];
+
+    if json {
+        let kinds: Vec<serde_json::Value> = ItemKind::ALL
+            .iter()
+            .map(|kind| {
+                let names: serde_json::Map<String, serde_json::Value> = LANGUAGES
+                    .iter()
+                    .filter_map(|(lang, language)| kind.display_name(*language).map(|n| (lang.to_string(), n.into())))
+                    .collect();
+                Ok(serde_json::json!({ "kind": serde_json::to_value(kind)?, "names": names }))
+            })
+            .collect::<Result<_, serde_json::Error>>()?;
+        return Ok(format!("{}\n", serde_json::to_string_pretty(&serde_json::json!({ "kinds": kinds }))?));
+    }
+
+    let mut output = format!("{:<11}{:<14}{:<20}{:<15}{}\n", "kind", "rust", "typescript", "javascript", "python");
+    for kind in ItemKind::ALL {
+        let name = |language: Language| kind.display_name(language).unwrap_or("-");
+        let kind_name = serde_json::to_value(&kind)?;
+        let line = format!(
+            "{:<11}{:<14}{:<20}{:<15}{}",
+            kind_name.as_str().unwrap_or_default(),
+            name(Language::Rust),
+            name(Language::TypeScript),
+            name(Language::JavaScript),
+            name(Language::Python),
+        );
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// One-line signature of the first definition named `symbol` in a single file:
+/// the declaration up to its body, without the body, attributes, or docs.
+/// Rust functions use the same signature as JSON method items.
+pub fn signature_of(path_str: &str, symbol: &str) -> Result<String, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    if path.is_dir() {
+        return Err(CodeviewError::InvalidPath(
+            "--sig only works on single files, not directories".to_string(),
+        ));
+    }
+    let source = read_source(path, false)?;
+    let language = languages::detect_language(path)?;
+    let tree = parser::parse(&source, language)?;
+
+    let node = find_definition(tree.root_node(), &source, symbol)
+        .ok_or_else(|| CodeviewError::ParseError(format!("Symbol not found: {}", symbol)))?;
+    if node.kind() == "function_item" {
+        return Ok(extractor::rust::build_fn_signature(&source, node));
+    }
+    Ok(extractor::declaration_header(node, &source))
+}
+
+/// Body of the symbol `symbol` in a single file, without its signature or braces,
+/// as [`editor::body_text`] gives it.
+pub fn body_of(path_str: &str, symbol: &str) -> Result<String, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    if path.is_dir() {
+        return Err(CodeviewError::InvalidPath(
+            "--body-only only works on single files, not directories".to_string(),
+        ));
+    }
+    let source = read_source(path, false)?;
+    let language = languages::detect_language(path)?;
+    editor::body_text(&source, symbol, language)
+}
+
+/// First named definition called `symbol`, outer definitions before nested ones.
+fn find_definition<'t>(node: tree_sitter::Node<'t>, source: &str, symbol: &str) -> Option<tree_sitter::Node<'t>> {
+    let mut cursor = node.walk();
+    let children: Vec<_> = node.named_children(&mut cursor).collect();
+    let is_match = |n: &tree_sitter::Node| {
+        matches!(
+            n.kind(),
+            "function_item" | "function_signature_item" | "struct_item" | "enum_item" | "trait_item" | "type_item"
+                | "const_item" | "static_item" | "mod_item" | "function_declaration" | "generator_function_declaration"
+                | "function_signature" | "method_definition" | "class_declaration" | "abstract_class_declaration"
+                | "interface_declaration" | "type_alias_declaration" | "enum_declaration" | "function_definition"
+                | "class_definition"
+        ) && n.child_by_field_name("name").is_some_and(|name| &source[name.byte_range()] == symbol)
+    };
+    if let Some(found) = children.iter().find(|c| is_match(c)) {
+        return Some(*found);
+    }
+    children.into_iter().find_map(|c| find_definition(c, source, symbol))
+}
+
+/// Parse the file and run interface extraction `iterations` times, returning
+/// the total time spent. The file is read once, outside the timed loop.
+pub fn bench_extract(path_str: &str, iterations: usize) -> Result<Duration, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.is_file() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    let source = read_source(path, false)?;
+    let language = languages::detect_language(path)?;
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let tree = parser::parse(&source, language)?;
+        std::hint::black_box(extractor::interface::extract(&source, &tree, language));
+    }
+    Ok(start.elapsed())
+}
+
+/// Dump the tree-sitter syntax tree of a single file as an indented
+/// s-expression, one named node per line with its field name. With `lines`
+/// (`N-M`), only the smallest named node covering that range is dumped.
+pub fn dump_sexp(path_str: &str, lines: Option<&str>) -> Result<String, CodeviewError> {
+    let path = Path::new(path_str);
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    if path.is_dir() {
+        return Err(CodeviewError::InvalidPath(
+            "--sexp only works on single files, not directories".to_string(),
+        ));
+    }
+    let source = read_source(path, false)?;
+    let language = languages::detect_language(path)?;
+    let tree = parser::parse(&source, language)?;
+
+    let mut node = tree.root_node();
+    if let Some(lines_arg) = lines {
+        let (start, end) = parse_line_range(lines_arg)?;
+        let line_spans: Vec<(usize, &str)> = source
+            .split_inclusive('\n')
+            .scan(0, |offset, line| {
+                let span = (*offset, line);
+                *offset += line.len();
+                Some(span)
+            })
+            .collect();
+        if start > line_spans.len() {
+            return Err(CodeviewError::ParseError(format!(
+                "Start line {} is beyond end of file ({} lines)",
+                start, line_spans.len()
+            )));
+        }
+        // Cover the lines' text, not their surrounding whitespace
+        let (first_offset, first) = line_spans[start - 1];
+        let (last_offset, last) = line_spans[end.min(line_spans.len()) - 1];
+        let from = first_offset + (first.len() - first.trim_start().len());
+        let to = (last_offset + last.trim_end().len()).max(from);
+        node = node.named_descendant_for_byte_range(from, to).unwrap_or(node);
+    }
+
+    let mut output = String::new();
+    write_sexp(node, None, 0, &mut output);
+    output.push('\n');
+    Ok(output)
+}
+
+fn write_sexp(node: tree_sitter::Node, field: Option<&str>, depth: usize, out: &mut String) {
+    out.push_str(&"  ".repeat(depth));
+    if let Some(field) = field {
+        out.push_str(field);
+        out.push_str(": ");
+    }
+    if node.is_missing() {
+        out.push_str("(MISSING ");
+    } else {
+        out.push('(');
+    }
+    out.push_str(node.kind());
+
+    let mut cursor = node.walk();
+    if cursor.goto_first_child() {
+        loop {
+            if cursor.node().is_named() {
+                out.push('\n');
+                write_sexp(cursor.node(), cursor.field_name(), depth + 1, out);
+            }
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    out.push(')');
+}
+
+/// Read a source file, reporting non-UTF-8 content as `InvalidUtf8` instead of
+/// an opaque I/O error. With `lossy`, invalid bytes become U+FFFD instead.
+pub(crate) fn read_source(path: &Path, lossy: bool) -> Result<String, CodeviewError> {
+    let bytes = fs::read(path).map_err(|e| CodeviewError::ReadError {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+    match String::from_utf8(bytes) {
+        Ok(source) => Ok(source),
+        Err(e) if lossy => Ok(String::from_utf8_lossy(e.as_bytes()).into_owned()),
+        Err(_) => Err(CodeviewError::InvalidUtf8(path.display().to_string())),
+    }
+}
+
+/// Parse an `N-M` line range (1-indexed, inclusive).
+pub fn parse_line_range(arg: &str) -> Result<(usize, usize), CodeviewError> {
     let parts: Vec<&str> = arg.split('-').collect();
     if parts.len() != 2 {
         return Err(CodeviewError::ParseError(format!(
@@ -240,30 +889,167 @@ fn parse_line_range(arg: &str) -> Result<(usize, usize), CodeviewError> {
 
 fn process_file(
     path: &Path,
+    language: Option<Language>,
     symbols: &[String],
-    expand_mode: bool,
-    signatures: bool,
     expand_methods: &[String],
+    options: &ProcessOptions,
 ) -> Result<(Vec<Item>, usize, usize), CodeviewError> {
-    let source = fs::read_to_string(path)
-        .map_err(|e| CodeviewError::ReadError {
-            path: path.display().to_string(),
-            source: e,
-        })?;
+    let source = read_source(path, options.lossy)?;
 
     let lines = source.lines().count();
     let bytes = source.len();
 
-    let language = languages::detect_language(path)?;
-    let tree = parser::parse(&source, language)?;
+    let language = match language {
+        Some(language) => language,
+        None => languages::detect_language(path)?,
+    };
+    let items = extract_items(&source, path, language, symbols, expand_methods, options)?;
+    Ok((items, lines, bytes))
+}
+
+/// Print a warning to stderr for each requested symbol without a matching item,
+/// with the closest names in `source` as suggestions.
+fn warn_missing_symbols(source: &str, language: Language, symbols: &[String], items: &[Item]) -> Result<(), CodeviewError> {
+    let missing: Vec<&String> = symbols
+        .iter()
+        .filter(|s| {
+            let name = extractor::expand::split_qualified(s).1;
+            !items.iter().any(|item| item.name.as_deref() == Some(name))
+        })
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let tree = parser::parse(source, language)?;
+    for symbol in missing {
+        let suggestions = extractor::suggest_symbols(source, &tree, symbol, language);
+        if suggestions.is_empty() {
+            eprintln!("Warning: symbol '{}' not found", symbol);
+        } else {
+            eprintln!("Warning: symbol '{}' not found; did you mean {}?", symbol, suggestions.join(", "));
+        }
+    }
+    Ok(())
+}
+
+/// Extract the items of `source`, read from `path`, in the mode `options` and
+/// `symbols` select.
+fn extract_items(
+    source: &str,
+    path: &Path,
+    language: Language,
+    symbols: &[String],
+    expand_methods: &[String],
+    options: &ProcessOptions,
+) -> Result<Vec<Item>, CodeviewError> {
+    let tree = parser::parse(source, language)?;
 
-    let items = if signatures && !symbols.is_empty() {
-        extractor::expand::extract_signatures(&source, &tree, &symbols[0], expand_methods, language)
-    } else if expand_mode {
-        extractor::expand::extract(&source, &tree, symbols, language)
+    let mut items = if options.signatures && !symbols.is_empty() {
+        extractor::expand::extract_signatures(source, &tree, &symbols[0], expand_methods, language)
+    } else if !symbols.is_empty() {
+        let style = ExpandStyle {
+            nest_summary: options.nest_summary,
+            collapse_match: options.collapse_match,
+            collapse_strings: options.collapse_strings,
+            body_depth: options.body_depth,
+            recursive_expand: options.recursive_expand,
+            dedent: options.dedent,
+        };
+        extractor::expand::extract_with_style(source, &tree, symbols, language, style)
     } else {
-        extractor::interface::extract(&source, &tree, language)
+        let body_style = if options.no_collapse {
+            BodyStyle::Full
+        } else {
+            options.preview.map_or(BodyStyle::Collapsed, BodyStyle::Preview)
+        };
+        if options.grep_defs {
+            // The index lists Python methods too, which the interface view keeps inside their class
+            extractor::interface::extract_with_class_methods(source, &tree, language, body_style, options.docs)
+        } else {
+            extractor::interface::extract_with_docs(source, &tree, language, body_style, options.docs)
+        }
     };
 
-    Ok((items, lines, bytes))
+    if is_test_file(path, language) {
+        for item in items.iter_mut() {
+            item.is_test = true;
+        }
+    }
+
+    if options.resolve_reexports {
+        for item in items.iter_mut() {
+            if let Some(module) = item.source_module.as_deref() {
+                item.resolved = resolve_module(path, module).map(|p| p.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    if options.show_parents && options.list_symbols {
+        for item in items.iter_mut() {
+            item.name = qualified_name(item, &tree, source, language);
+        }
+    }
+
+    Ok(items)
+}
+
+/// Resolve a relative module specifier from `path`'s directory to an existing
+/// file, trying TS/JS extensions and `index` files. Bare package names and
+/// anything that doesn't exist resolve to `None`. Only one hop is taken: the
+/// target may itself re-export from elsewhere.
+fn resolve_module(path: &Path, module: &str) -> Option<PathBuf> {
+    if !module.starts_with('.') {
+        return None;
+    }
+    let mut base = PathBuf::new();
+    for component in path.parent().unwrap_or(Path::new("")).join(module).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(base.components().next_back(), Some(Component::Normal(_))) => {
+                base.pop();
+            }
+            other => base.push(other),
+        }
+    }
+
+    const EXTENSIONS: [&str; 4] = ["ts", "tsx", "js", "jsx"];
+    if base.is_file() {
+        return Some(base);
+    }
+    let with_ext = EXTENSIONS.iter().map(|ext| {
+        let mut file = base.clone().into_os_string();
+        file.push(format!(".{}", ext));
+        PathBuf::from(file)
+    });
+    let index = EXTENSIONS.iter().map(|ext| base.join(format!("index.{}", ext)));
+    with_ext.chain(index).find(|p| p.is_file())
+}
+
+/// TS/JS test files by naming convention (`*.test.ts`, `*.spec.js`, ...).
+fn is_test_file(path: &Path, language: Language) -> bool {
+    let is_js_family = matches!(language, Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx);
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    is_js_family && (name.contains(".test.") || name.contains(".spec."))
+}
+
+/// Prefix an item's name with its enclosing symbols, using the same lookup as
+/// search context: `User::greeting` for Rust, `UserService > getUser` elsewhere.
+fn qualified_name(item: &Item, tree: &tree_sitter::Tree, source: &str, language: Language) -> Option<String> {
+    let name = item.name.clone()?;
+    // The last line is inside the item's own node (the first may be an attribute)
+    let mut chain = search::find_enclosing_symbols(tree, source, item.line_end - 1, language);
+    // Fields and variants aren't symbols of their own, so the chain may end at their owner
+    let own_entry = |symbol: &str| symbol.strip_prefix("impl ").unwrap_or(symbol).trim_end_matches("()") == name;
+    if chain.last().is_some_and(|last| own_entry(last)) {
+        chain.pop();
+    }
+    if chain.is_empty() {
+        return Some(name);
+    }
+
+    let (separator, parts): (&str, Vec<&str>) = match language {
+        Language::Rust => ("::", chain.iter().map(|s| s.strip_prefix("impl ").unwrap_or(s)).collect()),
+        _ => (" > ", chain.iter().map(|s| s.trim_end_matches("()")).collect()),
+    };
+    Some(format!("{}{}{}", parts.join(separator), separator, name))
 }
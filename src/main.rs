@@ -1,7 +1,7 @@
 use clap::{Parser, Subcommand};
-use codeview::{editor, process_path, search, ProcessOptions, OutputFormat, Language, CodeviewError};
+use codeview::{callgraph, compare, editor, hotspots, process_path, search, stubs, watch, xref, ProcessOptions, OutputFormat, PathStyle, ItemKind, Language, CodeviewError, FilterExpr, Template};
 use codeview::editor::{BatchEdit, EditResult};
-use std::{fs, io::{self, Read}, path::Path, process};
+use std::{fs, io::{self, Read, Write}, path::Path, process, time::Duration};
 
 #[derive(Parser)]
 #[command(name = "codeview")]
@@ -18,10 +18,22 @@ struct Cli {
     /// Symbol names to expand (triggers expand mode)
     #[arg(value_name = "SYMBOLS")]
     symbols: Vec<String>,
+
+    /// Print every item kind with its per-language name and exit (with --json, as JSON)
+    #[arg(long = "kinds-legend")]
+    kinds_legend: bool,
     
     /// Only public items
     #[arg(long = "pub")]
     pub_only: bool,
+
+    /// With --pub, still show private methods and fields of classes/impls
+    #[arg(long = "include-private")]
+    include_private: bool,
+
+    /// Only items matching a filter expression, e.g. 'kind=fn and pub and lines>20'
+    #[arg(long = "where", value_name = "EXPR", value_parser = FilterExpr::parse)]
+    filter: Option<FilterExpr>,
     
     /// Only show functions/methods
     #[arg(long)]
@@ -38,19 +50,87 @@ struct Cli {
     /// JSON output instead of plain text
     #[arg(long)]
     json: bool,
+
+    /// On failure, print `{"error": {"kind", "message"}}` to stdout instead of a message on stderr
+    #[arg(long = "json-errors")]
+    json_errors: bool,
+
+    /// Markdown output (a section per file with symbol list and code blocks)
+    #[arg(long, conflicts_with = "json")]
+    markdown: bool,
+
+    /// Print a tree of symbol names and line ranges, nesting methods under their impl/class
+    #[arg(long, conflicts_with_all = ["json", "markdown"])]
+    outline: bool,
+
+    /// Columns each nesting level is indented by in `--outline` output (default 2)
+    #[arg(long, value_name = "N", requires = "outline", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    indent: Option<usize>,
+
+    /// Print a ctags-compatible `tags` file (one line per symbol, sorted by name)
+    #[arg(long, conflicts_with_all = ["json", "markdown", "outline"])]
+    tags: bool,
+
+    /// Concatenate all files under one summary line, separated by `// ==== path ====` banners
+    #[arg(long, conflicts_with_all = ["json", "markdown", "outline", "tags", "list_symbols", "stats"])]
+    merge: bool,
+
+    /// XML output (same fields as --json)
+    #[arg(long, conflicts_with_all = ["json", "markdown", "outline", "tags"])]
+    xml: bool,
     
     /// Exclude #[cfg(test)] mod tests blocks
     #[arg(long = "no-tests")]
     no_tests: bool,
     
     /// Show stats (file count, lines, bytes, tokens, items) instead of content
-    #[arg(long)]
+    #[arg(long, conflicts_with = "xml")]
     stats: bool,
 
-    /// Filter by file extensions (comma-separated, e.g. --ext rs,ts)
+    /// Show each public struct/enum/class with its count of public methods instead of content
+    #[arg(long = "type-api", conflicts_with = "stats")]
+    type_api: bool,
+
+    /// Print a JSON fingerprint per file (path, lines, bytes, items, sha256) instead of content
+    #[arg(long)]
+    fingerprint: bool,
+
+    /// Print one `path:line:col:kind:name` line per symbol (for fzf/grep-style pickers)
+    #[arg(long)]
+    targets: bool,
+
+    /// Print one `path:line: signature` line per definition (a flat index of the API surface)
+    #[arg(long = "grep-defs", conflicts_with_all = ["json", "markdown", "outline", "tags", "xml", "list_symbols", "stats", "targets", "template"])]
+    grep_defs: bool,
+
+    /// Filter by file extensions or filename globs (comma-separated, e.g. --ext rs,ts or --ext '*.spec.ts')
     #[arg(long, value_delimiter = ',')]
     ext: Vec<String>,
 
+    /// Parse files whose path (relative to the scanned directory) matches GLOB as LANG,
+    /// whatever their extension (repeatable, e.g. --dir-lang 'scripts/**=python')
+    #[arg(long = "dir-lang", value_name = "GLOB=LANG", value_parser = parse_dir_lang)]
+    dir_lang: Vec<(String, Language)>,
+
+    /// Read source from stdin instead of PATH (requires --lang); positional arguments are symbols
+    #[arg(long, requires = "lang", conflicts_with_all = [
+        "search", "lines", "sexp", "annotate_symbols", "split_to", "contract", "callgraph", "sig", "body_only", "bench_extract",
+        "watch", "fingerprint", "targets", "grep_defs", "tags",
+    ])]
+    stdin: bool,
+
+    /// Language of the source read with --stdin (rust/rs, typescript/ts, tsx, javascript/js, jsx, python/py)
+    #[arg(long, value_name = "LANG", requires = "stdin", value_parser = parse_lang)]
+    lang: Option<Language>,
+
+    /// Only include files whose path (relative to the scanned directory) matches GLOB (repeatable)
+    #[arg(long = "glob", value_name = "GLOB")]
+    glob: Vec<String>,
+
+    /// Skip files whose path (relative to the scanned directory) matches GLOB (repeatable)
+    #[arg(long = "exclude", value_name = "GLOB")]
+    exclude: Vec<String>,
+
     /// Show class with method signatures collapsed (use with a class symbol)
     #[arg(long)]
     signatures: bool,
@@ -67,17 +147,194 @@ struct Cli {
     #[arg(short = 'i', requires = "search")]
     case_insensitive: bool,
 
+    /// Match the search pattern only as a whole word (use with --search)
+    #[arg(short = 'w', requires = "search")]
+    whole_word: bool,
+
+    /// Show the lines that do not match the search pattern (use with --search)
+    #[arg(short = 'v', long = "invert-match", requires = "search")]
+    invert_match: bool,
+
+    /// Print only the number of search matches per file, plus a total
+    #[arg(short = 'c', long = "count", requires = "search", conflicts_with_all = ["expand_matches", "blame"])]
+    count: bool,
+
     /// Maximum number of search matches to display (default: 20 for directory search, unlimited for single-file)
     #[arg(long = "max-results", requires = "search")]
     max_results: Option<usize>,
 
+    /// Annotate search matches with the last author and commit from git blame
+    #[arg(long, requires = "search")]
+    blame: bool,
+
+    /// Print each enclosing symbol of a search match in full, with match lines marked
+    #[arg(long = "expand-matches", requires = "search")]
+    expand_matches: bool,
+
+    /// Show N lines of context after each search match
+    #[arg(short = 'A', value_name = "N", requires = "search")]
+    after_context: Option<usize>,
+
+    /// Show N lines of context before each search match
+    #[arg(short = 'B', value_name = "N", requires = "search")]
+    before_context: Option<usize>,
+
+    /// Show N lines of context before and after each search match
+    #[arg(short = 'C', value_name = "N", requires = "search")]
+    context: Option<usize>,
+
+    /// Show only the innermost N enclosing symbols of each search match (default: full path)
+    #[arg(long = "symbol-depth", value_name = "N", requires = "search", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    symbol_depth: Option<usize>,
+
+    /// Keep only search matches whose innermost enclosing symbol is one of these kinds (e.g. function,method)
+    #[arg(long = "in-kind", value_name = "KINDS", value_delimiter = ',', requires = "search", value_parser = parse_item_kind)]
+    in_kind: Vec<ItemKind>,
+
+    /// Note the next top-level symbol after each search match's enclosing symbol
+    #[arg(long = "next-symbol", requires = "search")]
+    next_symbol: bool,
+
     /// List symbols with kind and line number (compact, one line per symbol)
     #[arg(long = "list-symbols")]
     list_symbols: bool,
 
+    /// Show at most N symbols per file, summarizing the rest
+    #[arg(long = "max-symbols")]
+    max_symbols: Option<usize>,
+
+    /// Show at most N items in total across all files, summarizing the rest
+    #[arg(long, conflicts_with = "search")]
+    limit: Option<usize>,
+
+    /// Decode files that aren't valid UTF-8, replacing invalid bytes, instead of skipping them
+    #[arg(long)]
+    lossy: bool,
+
+    /// Show the first N lines of each function body instead of collapsing it
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    preview: Option<usize>,
+
+    /// Show function bodies in full in interface mode (no `{ ... }` collapsing)
+    #[arg(long = "no-collapse", conflicts_with = "preview")]
+    no_collapse: bool,
+
+    /// Only show methods defined on the named type (impl blocks, trait impls, or class body)
+    #[arg(long = "method-of", value_name = "TYPE")]
+    method_of: Option<String>,
+
+    /// When expanding, collapse bodies of nested definitions (inner functions, methods)
+    #[arg(long = "nest-summary")]
+    nest_summary: bool,
+
+    /// When expanding, collapse match/switch arm bodies to `{ ... }` but keep arm patterns
+    #[arg(long = "collapse-match")]
+    collapse_match: bool,
+
+    /// When expanding, replace string literals longer than N characters with `"...<LEN chars>..."`
+    #[arg(long = "collapse-strings", value_name = "N")]
+    collapse_strings: Option<usize>,
+
+    /// When expanding, collapse blocks nested more than N levels inside each body to `{ ... }`
+    #[arg(long = "body-depth", value_name = "N")]
+    body_depth: Option<usize>,
+
+    /// When expanding a Rust module, summarize its submodules (function bodies collapsed) instead of dumping them
+    #[arg(long = "recursive-expand")]
+    recursive_expand: bool,
+
+    /// When expanding, strip the indentation shared by every line of each symbol
+    #[arg(long)]
+    dedent: bool,
+
+    /// Include each item's raw tree-sitter node kind in JSON output
+    #[arg(long = "debug-kinds", requires = "json")]
+    debug_kinds: bool,
+
+    /// With --json, print one JSON object per symbol per line (NDJSON), tagged with its file
+    #[arg(long = "per-symbol-stream", requires = "json")]
+    per_symbol_stream: bool,
+
+    /// With --json, add each item's `line_mappings`: `[line_number, text]` per content line
+    #[arg(long = "with-mappings", requires = "json")]
+    with_mappings: bool,
+
+    /// With --json, add the file each relative re-export (`export { X } from './x'`) resolves to
+    #[arg(long = "resolve-reexports", requires = "json")]
+    resolve_reexports: bool,
+
+    /// Write each expanded symbol to its own file in DIR (source file is left untouched)
+    #[arg(long = "split-to", value_name = "DIR")]
+    split_to: Option<String>,
+
+    /// With --list-symbols, prefix each symbol with its enclosing chain (e.g. User::greeting)
+    #[arg(long = "show-parents", requires = "list_symbols")]
+    show_parents: bool,
+
+    /// Show a trait's methods next to TYPE's impl of it, marking unimplemented ones (Rust)
+    #[arg(long, num_args = 2, value_names = ["TYPE", "TRAIT"])]
+    contract: Option<Vec<String>>,
+
+    /// Print `caller -> callee` edges between the file's top-level functions (heuristic)
+    #[arg(long)]
+    callgraph: bool,
+
+    /// With --callgraph, print a Graphviz digraph instead of edge lines
+    #[arg(long, requires = "callgraph")]
+    dot: bool,
+
+    /// Print consecutive consts/statics as one compact block under a `// constants` heading
+    #[arg(long = "group-consts")]
+    group_consts: bool,
+
+    /// List files that have no items with a `// (no items)` marker instead of skipping them
+    #[arg(long = "emit-empty")]
+    emit_empty: bool,
+
+    /// In directory mode, only process files that `git status` reports as modified or staged
+    #[arg(long = "git-modified")]
+    git_modified: bool,
+
+    /// In interface mode, keep `///`/`/** */` doc comments above items (for Python, the first docstring line)
+    #[arg(long)]
+    docs: bool,
+
+    /// Print each item as one line from a template, e.g. '{kind} {name} ({file}:{line_start})'
+    #[arg(long, value_name = "FMT", value_parser = Template::parse, conflicts_with_all = ["json", "markdown", "outline", "tags", "xml", "list_symbols", "stats"])]
+    template: Option<Template>,
+
+    /// How file paths are printed: absolute, relative (to the scanned directory), or basename
+    #[arg(long = "path-style", value_name = "STYLE", value_parser = ["absolute", "relative", "basename"])]
+    path_style: Option<String>,
+
+    /// Re-render whenever a file under PATH changes
+    #[arg(long)]
+    watch: bool,
+
     /// Extract a line range with structural context (e.g. --lines 50-75)
     #[arg(long)]
     lines: Option<String>,
+
+    /// Print the file's tree-sitter syntax tree as an indented s-expression
+    /// (with --lines, only the subtree covering that range)
+    #[arg(long)]
+    sexp: bool,
+
+    /// Print every line prefixed with its enclosing symbol path
+    #[arg(long = "annotate-symbols", conflicts_with = "lines")]
+    annotate_symbols: bool,
+
+    /// Print only the one-line signature of each named symbol, without its body
+    #[arg(long = "sig", conflicts_with_all = ["lines", "signatures"])]
+    sig: bool,
+
+    /// Print only the inner body of each named symbol (the form --replace-body takes)
+    #[arg(long = "body-only", conflicts_with_all = ["lines", "signatures", "sig"])]
+    body_only: bool,
+
+    /// Time N rounds of parsing + interface extraction of a file and print ms/iter
+    #[arg(long = "bench-extract", value_name = "ITERATIONS", hide = true)]
+    bench_extract: Option<usize>,
 }
 
 #[derive(Subcommand)]
@@ -87,26 +344,62 @@ enum Commands {
         /// File to edit
         file: String,
         
-        /// Symbol name to edit (not needed with --batch)
+        /// Symbol name to edit (not needed with --batch or --lines)
         #[arg(default_value = "")]
         symbol: String,
         
         /// Replace the symbol with new source
-        #[arg(long, conflicts_with_all = ["delete", "replace_body", "batch"])]
+        #[arg(long, conflicts_with_all = ["delete", "replace_body", "replace_signature", "batch"])]
         replace: Option<String>,
         
-        /// Replace only the body block, preserving signature/attributes
-        #[arg(long = "replace-body", conflicts_with_all = ["delete", "replace", "batch"])]
+        /// Replace only the body block, preserving signature/attributes (the value may be omitted with --stdin)
+        #[arg(long = "replace-body", num_args = 0..=1, default_missing_value = "", conflicts_with_all = ["delete", "replace", "batch"])]
         replace_body: Option<String>,
+
+        /// Replace only the signature (attributes, params, return type), preserving the body
+        #[arg(long = "replace-signature", conflicts_with_all = ["delete", "replace", "replace_body", "batch"])]
+        replace_signature: Option<String>,
         
-        /// Read replacement from stdin (works with --replace or --replace-body)
+        /// Insert new source on its own line before the symbol (and its attributes)
+        #[arg(long = "insert-before", conflicts_with_all = ["replace", "replace_body", "replace_signature", "delete", "dedup_imports", "batch", "insert_after"])]
+        insert_before: Option<String>,
+
+        /// Insert new source on its own line after the symbol
+        #[arg(long = "insert-after", conflicts_with_all = ["replace", "replace_body", "replace_signature", "delete", "dedup_imports", "batch"])]
+        insert_after: Option<String>,
+        
+        /// Append a method to the named impl/class, indented to match
+        #[arg(long = "add-method", value_name = "SOURCE", conflicts_with_all = ["replace", "replace_body", "replace_signature", "insert_before", "insert_after", "delete", "dedup_imports", "batch", "rename"])]
+        add_method: Option<String>,
+
+        /// Rename the symbol and every same-named identifier in the file (file-local)
+        #[arg(long, value_name = "NEW_NAME", conflicts_with_all = ["replace", "replace_body", "replace_signature", "insert_before", "insert_after", "delete", "dedup_imports", "batch"])]
+        rename: Option<String>,
+
+        /// Replace lines N-M (1-indexed, inclusive) instead of a symbol; takes --replace or --replace-stdin
+        #[arg(long, value_name = "N-M", conflicts_with_all = ["replace_body", "replace_signature", "insert_before", "insert_after", "add_method", "rename", "delete", "dedup_imports", "batch"])]
+        lines: Option<String>,
+
+        /// With --lines, read the replacement from stdin
+        #[arg(long = "replace-stdin", requires = "lines", conflicts_with = "replace")]
+        replace_stdin: bool,
+
+        /// Read replacement from stdin (works with --replace, --replace-body, --replace-signature, --insert-before, --insert-after, or --add-method)
         #[arg(long)]
         stdin: bool,
         
         /// Delete the symbol
         #[arg(long, conflicts_with_all = ["replace", "replace_body", "batch"])]
         delete: bool,
+
+        /// With --delete, keep the symbol's leading attributes/decorators
+        #[arg(long = "keep-attributes", requires = "delete")]
+        keep_attributes: bool,
         
+        /// Remove imports that exactly repeat an earlier import
+        #[arg(long = "dedup-imports", conflicts_with_all = ["replace", "replace_body", "replace_signature", "delete", "batch"])]
+        dedup_imports: bool,
+
         /// Apply batch edits from a JSON file
         #[arg(long, conflicts_with_all = ["replace", "replace_body", "delete"])]
         batch: Option<String>,
@@ -118,39 +411,270 @@ enum Commands {
         /// Output JSON metadata about what changed
         #[arg(long)]
         json: bool,
+
+        /// Print a unified diff of the change instead of the modified file (implies --dry-run)
+        #[arg(long)]
+        diff: bool,
+
+        /// Use indent_style/indent_size from the nearest .editorconfig when reindenting bodies
+        #[arg(long = "respect-editorconfig")]
+        respect_editorconfig: bool,
+    },
+
+    /// List functions/methods that are never referenced in the scanned tree (heuristic)
+    Unused {
+        /// File or directory to scan
+        path: String,
+
+        /// Directory recursion depth (default: unlimited)
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Filter by file extensions (comma-separated, e.g. --ext rs,ts)
+        #[arg(long, value_delimiter = ',')]
+        ext: Vec<String>,
+    },
+
+    /// List the lines that use a symbol's name, with their enclosing symbols (heuristic, no type resolution)
+    Refs {
+        /// Symbol name to look up
+        symbol: String,
+
+        /// File or directory to scan
+        path: String,
+
+        /// Also report the definitions of the symbol
+        #[arg(long = "include-definitions")]
+        include_definitions: bool,
+
+        /// Directory recursion depth (default: unlimited)
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Filter by file extensions (comma-separated, e.g. --ext rs,ts)
+        #[arg(long, value_delimiter = ',')]
+        ext: Vec<String>,
+    },
+
+    /// Report symbols added, removed, or with changed signatures between two files
+    Compare {
+        /// Original file
+        old: String,
+
+        /// Updated file
+        new: String,
+    },
+
+    /// List the largest functions/methods by line count
+    Hotspots {
+        /// File or directory to scan
+        path: String,
+
+        /// Show only the N largest (default: all)
+        #[arg(long, value_name = "N")]
+        top: Option<usize>,
+
+        /// Also print each function's collapsed signature
+        #[arg(long)]
+        signatures: bool,
+
+        /// Directory recursion depth (default: unlimited)
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Filter by file extensions (comma-separated, e.g. --ext rs,ts)
+        #[arg(long, value_delimiter = ',')]
+        ext: Vec<String>,
+    },
+
+    /// List functions/methods whose body is empty or only a "not implemented" marker
+    Stubs {
+        /// File or directory to scan
+        path: String,
+
+        /// Directory recursion depth (default: unlimited)
+        #[arg(long)]
+        depth: Option<usize>,
+
+        /// Filter by file extensions (comma-separated, e.g. --ext rs,ts)
+        #[arg(long, value_delimiter = ',')]
+        ext: Vec<String>,
     },
 }
 
 fn main() {
     let cli = Cli::parse();
+    let json_errors = cli.json_errors;
     
     match cli.command {
-        Some(Commands::Edit { file, symbol, replace, replace_body, stdin, delete, batch, dry_run, json }) => {
-            if let Err(e) = handle_edit(&file, &symbol, EditOptions { replace, replace_body, stdin, delete, batch, dry_run, json }) {
+        Some(Commands::Edit { file, symbol, replace, replace_body, replace_signature, insert_before, insert_after, add_method, rename, lines, replace_stdin, stdin, delete, keep_attributes, dedup_imports, batch, dry_run, json, diff, respect_editorconfig }) => {
+            if let Err(e) = handle_edit(&file, &symbol, EditOptions { replace, replace_body, replace_signature, insert_before, insert_after, add_method, rename, lines, replace_stdin, stdin, delete, keep_attributes, dedup_imports, batch, dry_run, json, diff, respect_editorconfig }) {
                 eprintln!("Error: {}", e);
                 process::exit(1);
             }
         }
+        Some(Commands::Unused { path, depth, ext }) => {
+            match xref::find_unused(&path, &xref::XrefOptions { depth, ext }) {
+                Ok(unused) => {
+                    print!("{}", xref::format_unused(&unused));
+                }
+                Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+            }
+        }
+        Some(Commands::Refs { symbol, path, include_definitions, depth, ext }) => {
+            match xref::find_references(&symbol, &path, &xref::RefOptions { depth, ext, include_definitions }) {
+                Ok(references) => {
+                    print!("{}", xref::format_references(&references));
+                }
+                Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+            }
+        }
+        Some(Commands::Compare { old, new }) => {
+            match compare::compare_files(&old, &new) {
+                Ok(changes) => {
+                    print!("{}", compare::format_changes(&changes));
+                }
+                Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+            }
+        }
+        Some(Commands::Hotspots { path, top, signatures, depth, ext }) => {
+            match hotspots::find_hotspots(&path, &hotspots::HotspotOptions { depth, ext, top }) {
+                Ok(found) => {
+                    print!("{}", hotspots::format_hotspots(&found, signatures));
+                }
+                Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+            }
+        }
+        Some(Commands::Stubs { path, depth, ext }) => {
+            match stubs::find_stubs(&path, &stubs::StubOptions { depth, ext }) {
+                Ok(found) => {
+                    print!("{}", stubs::format_stubs(&found));
+                }
+                Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+            }
+        }
+        None if cli.kinds_legend => match codeview::kinds_legend(cli.json) {
+            Ok(output) => print!("{}", output),
+            Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+        },
         None => {
-            // Default behavior: process path
-            let path = match cli.path {
-                Some(p) => p,
-                None => {
-                    eprintln!("Error: PATH is required");
-                    process::exit(1);
+            // Default behavior: process path. With --stdin there is no PATH, so a
+            // first positional argument is a symbol.
+            let (path, symbols) = if cli.stdin {
+                (String::new(), cli.path.into_iter().chain(cli.symbols).collect::<Vec<_>>())
+            } else {
+                match cli.path {
+                    Some(p) => (p, cli.symbols),
+                    None => {
+                        exit_with_error("usage", "PATH is required", json_errors);
+                    }
                 }
             };
 
+            // Handle --sexp mode (before --lines, which it can be narrowed by)
+            if cli.sexp {
+                match codeview::dump_sexp(&path, cli.lines.as_deref()) {
+                    Ok(output) => print!("{}", output),
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                }
+                return;
+            }
+
             // Handle --lines mode
             if let Some(lines_arg) = cli.lines {
                 match codeview::extract_lines(&path, &lines_arg) {
                     Ok(output) => {
                         print!("{}", output);
                     }
-                    Err(e) => {
-                        eprintln!("Error: {}", e);
-                        process::exit(1);
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                }
+                return;
+            }
+
+            // Handle --bench-extract mode
+            if let Some(iterations) = cli.bench_extract {
+                match codeview::bench_extract(&path, iterations) {
+                    Ok(elapsed) => {
+                        let per_iter = elapsed.as_secs_f64() * 1000.0 / iterations.max(1) as f64;
+                        println!("{:.3} ms/iter ({} iterations)", per_iter, iterations);
+                    }
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                }
+                return;
+            }
+
+            // Handle --annotate-symbols mode
+            if cli.annotate_symbols {
+                match codeview::annotate_symbols(&path) {
+                    Ok(output) => {
+                        print!("{}", output);
+                    }
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                }
+                return;
+            }
+
+            // Handle --split-to mode
+            if let Some(dir) = cli.split_to {
+                if symbols.is_empty() {
+                    exit_with_error("usage", "--split-to requires at least one symbol", json_errors);
+                }
+                match codeview::split_symbols(&path, &symbols, &dir) {
+                    Ok(written) => {
+                        for file in written {
+                            println!("{}", file.display());
+                        }
                     }
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                }
+                return;
+            }
+
+            // Handle --sig mode
+            if cli.sig {
+                if symbols.is_empty() {
+                    exit_with_error("usage", "--sig requires at least one symbol", json_errors);
+                }
+                for symbol in &symbols {
+                    match codeview::signature_of(&path, symbol) {
+                        Ok(signature) => println!("{}", signature),
+                        Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                    }
+                }
+                return;
+            }
+
+            // Handle --body-only mode
+            if cli.body_only {
+                if symbols.is_empty() {
+                    exit_with_error("usage", "--body-only requires at least one symbol", json_errors);
+                }
+                for symbol in &symbols {
+                    match codeview::body_of(&path, symbol) {
+                        Ok(body) => print!("{}", body),
+                        Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                    }
+                }
+                return;
+            }
+
+            // Handle --contract mode
+            if let Some(names) = cli.contract {
+                match codeview::contract::format_contract(&path, &names[0], &names[1]) {
+                    Ok(output) => {
+                        print!("{}", output);
+                    }
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                }
+                return;
+            }
+
+            // Handle --callgraph mode
+            if cli.callgraph {
+                match callgraph::call_graph(&path) {
+                    Ok(graph) if cli.dot => print!("{}", callgraph::format_dot(&graph)),
+                    Ok(graph) => print!("{}", callgraph::format_edges(&graph)),
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
                 }
                 return;
             }
@@ -161,64 +685,196 @@ fn main() {
                 let search_opts = search::SearchOptions {
                     pattern,
                     case_insensitive: cli.case_insensitive,
+                    whole_word: cli.whole_word,
+                    invert: cli.invert_match,
                     depth: cli.depth,
                     ext: cli.ext,
+                    include_globs: cli.glob,
+                    exclude_globs: cli.exclude,
                     max_results: cli.max_results.or(if is_dir { Some(20) } else { None }),
+                    blame: cli.blame,
+                    expand_matches: cli.expand_matches,
+                    next_symbol: cli.next_symbol,
+                    json: cli.json,
+                    count: cli.count,
+                    before: cli.before_context.or(cli.context).unwrap_or(0),
+                    after: cli.after_context.or(cli.context).unwrap_or(0),
+                    symbol_depth: cli.symbol_depth,
+                    in_kind: cli.in_kind,
+                    lossy: cli.lossy,
                 };
                 match search::search_path(&path, &search_opts) {
                     Ok(output) => {
                         print!("{}", output);
                     }
-                    Err(e) => {
-                        eprintln!("Error: {}", e);
-                        process::exit(1);
-                    }
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
                 }
                 return;
             }
             
             let format = if cli.json {
                 OutputFormat::Json
+            } else if cli.markdown {
+                OutputFormat::Markdown
+            } else if cli.outline {
+                OutputFormat::Outline
+            } else if cli.tags {
+                OutputFormat::Ctags
+            } else if cli.xml {
+                OutputFormat::Xml
             } else {
                 OutputFormat::Plain
             };
             
+            let path_style = match cli.path_style.as_deref() {
+                Some("absolute") => PathStyle::Absolute,
+                Some("relative") => PathStyle::Relative,
+                Some("basename") => PathStyle::Basename,
+                _ => PathStyle::AsGiven,
+            };
+
             let options = ProcessOptions {
-                symbols: cli.symbols,
+                symbols,
                 pub_only: cli.pub_only,
+                include_private: cli.include_private,
                 fns_only: cli.fns,
                 types_only: cli.types,
                 no_tests: cli.no_tests,
                 depth: cli.depth,
                 format,
                 stats: cli.stats,
+                type_api: cli.type_api,
+                merge: cli.merge,
+                fingerprint: cli.fingerprint,
+                targets: cli.targets,
+                grep_defs: cli.grep_defs,
                 ext: cli.ext,
+                dir_lang: cli.dir_lang,
+                include_globs: cli.glob,
+                exclude_globs: cli.exclude,
                 signatures: cli.signatures,
                 max_lines: cli.max_lines,
                 list_symbols: cli.list_symbols,
+                max_symbols: cli.max_symbols,
+                limit: cli.limit,
+                lossy: cli.lossy,
+                preview: cli.preview,
+                no_collapse: cli.no_collapse,
+                method_of: cli.method_of,
+                filter: cli.filter,
+                nest_summary: cli.nest_summary,
+                collapse_match: cli.collapse_match,
+                collapse_strings: cli.collapse_strings,
+                body_depth: cli.body_depth,
+                recursive_expand: cli.recursive_expand,
+                dedent: cli.dedent,
+                debug_kinds: cli.debug_kinds,
+                per_symbol_stream: cli.per_symbol_stream,
+                with_mappings: cli.with_mappings,
+                resolve_reexports: cli.resolve_reexports,
+                show_parents: cli.show_parents,
+                path_style,
+                group_consts: cli.group_consts,
+                emit_empty: cli.emit_empty,
+                git_modified: cli.git_modified,
+                docs: cli.docs,
+                template: cli.template,
+                indent: cli.indent,
             };
+
+            if let Some(language) = cli.lang {
+                let mut source = String::new();
+                if let Err(e) = io::stdin().read_to_string(&mut source) {
+                    exit_with_error("read_error", &format!("Failed to read stdin: {}", e), json_errors);
+                }
+                match codeview::process_source(&source, language, options) {
+                    Ok(output) => print!("{}", output),
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                }
+                return;
+            }
+
+            if cli.watch {
+                run_watch(&path, options);
+            }
+
+            if options.per_symbol_stream {
+                match codeview::stream_symbols(&path, options, &mut io::stdout().lock()) {
+                    // The reader (e.g. `head`) has seen enough
+                    Err(CodeviewError::SerializationError(e)) if e.io_error_kind() == Some(io::ErrorKind::BrokenPipe) => {}
+                    Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
+                    Ok(()) => {}
+                }
+                return;
+            }
             
             match process_path(&path, options) {
                 Ok(output) => {
                     print!("{}", output);
                 }
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    process::exit(1);
-                }
+                Err(e) => exit_with_error(e.kind(), &e.to_string(), json_errors),
             }
         }
     }
 }
 
+/// Report an error and exit with status 1: on stderr, or with `--json-errors`
+/// as `{"error": {"kind", "message"}}` on stdout.
+fn exit_with_error(kind: &str, message: &str, json_errors: bool) -> ! {
+    if json_errors {
+        println!("{}", serde_json::json!({ "error": { "kind": kind, "message": message } }));
+    } else {
+        eprintln!("Error: {}", message);
+    }
+    process::exit(1);
+}
+
+/// Poll interval for `--watch`; also the debounce window for bursts of saves.
+const WATCH_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Render `path`, then clear and re-render on every change. Never returns.
+fn run_watch(path: &str, options: ProcessOptions) -> ! {
+    let mut watcher = match watch::Watcher::new(path, options.depth, &options.ext) {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    loop {
+        // Clear screen and move the cursor home before each render
+        print!("\x1b[2J\x1b[H");
+        match process_path(path, options.clone()) {
+            Ok(output) => print!("{}", output),
+            Err(e) => eprintln!("Error: {}", e),
+        }
+        io::stdout().flush().ok();
+        if let Err(e) = watcher.wait_for_change(WATCH_INTERVAL) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
 struct EditOptions {
     replace: Option<String>,
     replace_body: Option<String>,
+    replace_signature: Option<String>,
+    insert_before: Option<String>,
+    insert_after: Option<String>,
+    add_method: Option<String>,
+    rename: Option<String>,
+    lines: Option<String>,
+    replace_stdin: bool,
     stdin: bool,
     delete: bool,
+    keep_attributes: bool,
+    dedup_imports: bool,
     batch: Option<String>,
     dry_run: bool,
     json: bool,
+    diff: bool,
+    respect_editorconfig: bool,
 }
 
 fn handle_edit(
@@ -226,7 +882,7 @@ fn handle_edit(
     symbol: &str,
     opts: EditOptions,
 ) -> Result<(), CodeviewError> {
-    let EditOptions { replace, replace_body, stdin, delete, batch, dry_run, json } = opts;
+    let EditOptions { replace, replace_body, replace_signature, insert_before, insert_after, add_method, rename, lines, replace_stdin, stdin, delete, keep_attributes, dedup_imports, batch, dry_run, json, diff, respect_editorconfig } = opts;
     let path = Path::new(file);
     if !path.exists() {
         return Err(CodeviewError::PathNotFound(file.to_string()));
@@ -239,6 +895,13 @@ fn handle_edit(
         })?;
     
     let language = detect_language_from_path(path)?;
+
+    let indent_unit = if respect_editorconfig {
+        editor::editorconfig::indent_unit_for(path)
+    } else {
+        None
+    }
+    .unwrap_or_else(|| editor::editorconfig::DEFAULT_INDENT.to_string());
     
     // Compute edit metadata before performing the edit (line ranges from original source)
     let mut edit_results: Vec<EditResult> = Vec::new();
@@ -260,17 +923,27 @@ fn handle_edit(
                     editor::BatchAction::Replace => "replaced",
                     editor::BatchAction::ReplaceBody => "replaced_body",
                     editor::BatchAction::Delete => "deleted",
+                    editor::BatchAction::InsertBefore => "inserted_before",
+                    editor::BatchAction::InsertAfter => "inserted_after",
+                };
+                // Each edit's diff is taken against the original source on its own
+                let diff = if diff {
+                    let edited = editor::batch_with_indent(&source, std::slice::from_ref(edit), language, &indent_unit)?;
+                    Some(editor::diff::unified_diff(&source, &edited, file))
+                } else {
+                    None
                 };
                 edit_results.push(EditResult {
                     symbol: edit.symbol.clone(),
                     action: action.to_string(),
                     line_start,
                     line_end,
+                    diff,
                 });
             }
         }
         
-        editor::batch(&source, &input.edits, language)?
+        editor::batch_with_indent(&source, &input.edits, language, &indent_unit)?
     } else if delete {
         if json {
             let (line_start, line_end) = editor::symbol_line_range(&source, symbol, language)?;
@@ -279,15 +952,32 @@ fn handle_edit(
                 action: "deleted".to_string(),
                 line_start,
                 line_end,
+                diff: None,
             });
         }
-        editor::delete(&source, symbol, language)?
+        if keep_attributes {
+            editor::delete_keep_attributes(&source, symbol, language)?
+        } else {
+            editor::delete(&source, symbol, language)?
+        }
+    } else if dedup_imports {
+        if json {
+            for (line_start, line_end) in editor::duplicate_import_lines(&source, language)? {
+                edit_results.push(EditResult {
+                    symbol: source.lines().nth(line_start - 1).unwrap_or("").trim().to_string(),
+                    action: "deleted_duplicate".to_string(),
+                    line_start,
+                    line_end,
+                    diff: None,
+                });
+            }
+        }
+        editor::dedup_imports(&source, language)?
     } else if let Some(body_content) = replace_body {
         let new_body = if stdin {
-            let mut buf = String::new();
-            io::stdin().read_to_string(&mut buf)
-                .map_err(|e| CodeviewError::ParseError(format!("Failed to read stdin: {}", e)))?;
-            buf
+            read_stdin_replacement()?
+        } else if body_content.is_empty() {
+            return Err(CodeviewError::ParseError("--replace-body needs a body, or --stdin to read one".to_string()));
         } else {
             body_content
         };
@@ -298,15 +988,101 @@ fn handle_edit(
                 action: "replaced_body".to_string(),
                 line_start,
                 line_end,
+                diff: None,
             });
         }
-        editor::replace_body(&source, symbol, &new_body, language)?
+        editor::replace_body_with_indent(&source, symbol, &new_body, language, &indent_unit)?
+    } else if let Some(signature) = replace_signature {
+        let new_signature = if stdin {
+            read_stdin_replacement()?
+        } else {
+            signature
+        };
+        if json {
+            let (line_start, line_end) = editor::symbol_line_range(&source, symbol, language)?;
+            edit_results.push(EditResult {
+                symbol: symbol.to_string(),
+                action: "replaced_signature".to_string(),
+                line_start,
+                line_end,
+                diff: None,
+            });
+        }
+        editor::replace_signature(&source, symbol, &new_signature, language)?
+    } else if let Some(method) = add_method {
+        let new_method = if stdin {
+            read_stdin_replacement()?
+        } else {
+            method
+        };
+        if json {
+            let (line_start, line_end) = editor::symbol_line_range(&source, symbol, language)?;
+            edit_results.push(EditResult {
+                symbol: symbol.to_string(),
+                action: "added_method".to_string(),
+                line_start,
+                line_end,
+                diff: None,
+            });
+        }
+        editor::add_method_with_indent(&source, symbol, &new_method, language, &indent_unit)?
+    } else if let Some(new_name) = rename {
+        if json {
+            let (line_start, line_end) = editor::symbol_line_range(&source, symbol, language)?;
+            edit_results.push(EditResult {
+                symbol: symbol.to_string(),
+                action: "renamed".to_string(),
+                line_start,
+                line_end,
+                diff: None,
+            });
+        }
+        editor::rename(&source, symbol, &new_name, language)?
+    } else if let Some((content, before)) = insert_before.map(|c| (c, true)).or(insert_after.map(|c| (c, false))) {
+        let new_content = if stdin {
+            read_stdin_replacement()?
+        } else {
+            content
+        };
+        if json {
+            let (line_start, line_end) = editor::symbol_line_range(&source, symbol, language)?;
+            edit_results.push(EditResult {
+                symbol: symbol.to_string(),
+                action: if before { "inserted_before" } else { "inserted_after" }.to_string(),
+                line_start,
+                line_end,
+                diff: None,
+            });
+        }
+        if before {
+            editor::insert_before(&source, symbol, &new_content, language)?
+        } else {
+            editor::insert_after(&source, symbol, &new_content, language)?
+        }
+    } else if let Some(range) = lines {
+        let (start_line, end_line) = codeview::parse_line_range(&range)?;
+        let new_content = match replace {
+            Some(replacement) if !stdin => replacement,
+            _ if stdin || replace_stdin => read_stdin_replacement()?,
+            _ => {
+                return Err(CodeviewError::ParseError(
+                    "--lines needs --replace or --replace-stdin".to_string()
+                ))
+            }
+        };
+        if json {
+            edit_results.push(EditResult {
+                symbol: range.clone(),
+                action: "replaced_lines".to_string(),
+                line_start: start_line,
+                line_end: end_line,
+                diff: None,
+            });
+        }
+        editor::replace_range(&source, start_line, end_line, &new_content, language)?
     } else if let Some(replacement) = replace {
         let new_content = if stdin {
-            let mut buf = String::new();
-            io::stdin().read_to_string(&mut buf)
-                .map_err(|e| CodeviewError::ParseError(format!("Failed to read stdin: {}", e)))?;
-            buf
+            read_stdin_replacement()?
         } else {
             replacement
         };
@@ -317,18 +1093,30 @@ fn handle_edit(
                 action: "replaced".to_string(),
                 line_start,
                 line_end,
+                diff: None,
             });
         }
         editor::replace(&source, symbol, &new_content, language)?
     } else {
         return Err(CodeviewError::ParseError(
-            "Must specify --replace, --replace-body, --delete, or --batch".to_string()
+            "Must specify --replace, --replace-body, --replace-signature, --insert-before, --insert-after, --add-method, --rename, --lines, --delete, --dedup-imports, or --batch".to_string()
         ));
     };
     
-    if dry_run {
+    if diff && json && edit_results.len() == 1 && edit_results[0].diff.is_none() {
+        edit_results[0].diff = Some(editor::diff::unified_diff(&source, &result, file));
+    }
+
+    // With --json the diff goes in the metadata instead
+    if diff {
+        if !json {
+            print!("{}", editor::diff::unified_diff(&source, &result, file));
+        }
+    } else if dry_run {
         print!("{}", result);
-    } else {
+    }
+    // --diff previews the change, so it never touches the file
+    if !dry_run && !diff {
         fs::write(path, &result)
             .map_err(|e| CodeviewError::ReadError {
                 path: file.to_string(),
@@ -347,6 +1135,36 @@ fn handle_edit(
     Ok(())
 }
 
+/// Read the replacement text for an edit from stdin.
+fn read_stdin_replacement() -> Result<String, CodeviewError> {
+    let mut buf = String::new();
+    io::stdin().read_to_string(&mut buf)
+        .map_err(|e| CodeviewError::ParseError(format!("Failed to read stdin: {}", e)))?;
+    Ok(buf)
+}
+
+/// Parse a `--dir-lang` value of the form `GLOB=LANG`.
+fn parse_dir_lang(value: &str) -> Result<(String, Language), String> {
+    let (glob, lang) = value
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected GLOB=LANG, got '{}'", value))?;
+    Ok((glob.to_string(), parse_lang(lang)?))
+}
+
+/// Parse a language name such as `rust` or `py`.
+fn parse_lang(name: &str) -> Result<Language, String> {
+    codeview::parse_language(name.trim())
+        .ok_or_else(|| format!("unknown language '{}' (expected rust, typescript, tsx, javascript, jsx, or python)", name))
+}
+
+/// Parse one `--in-kind` item kind.
+fn parse_item_kind(value: &str) -> Result<ItemKind, String> {
+    ItemKind::from_name(value.trim()).ok_or_else(|| {
+        let names: Vec<String> = ItemKind::ALL.iter().map(|kind| format!("{:?}", kind).to_lowercase()).collect();
+        format!("unknown kind '{}' (expected one of: {})", value, names.join(", "))
+    })
+}
+
 fn detect_language_from_path(path: &Path) -> Result<Language, CodeviewError> {
     let ext = path.extension()
         .and_then(|e| e.to_str())
@@ -0,0 +1,82 @@
+//! Classic `tags` file output for Vim/Emacs tag navigation.
+//!
+//! Each named item becomes `name<TAB>path<TAB>/^line$/;"<TAB>kind`, where the
+//! search pattern is the source line holding the item's name. Lines are sorted
+//! by name (byte order), as tag readers binary-search the file.
+
+use super::targets::name_position;
+use super::Truncation;
+use crate::error::CodeviewError;
+use crate::extractor::{Item, ItemKind};
+use crate::read_source;
+use std::path::Path;
+
+/// Format a sorted tags file covering every named item in `files`. What
+/// `--limit` cut is noted in a `!_TAG_OMITTED` pseudo-tag.
+pub fn format_output(files: &[(String, Vec<Item>)], truncated: Option<Truncation>) -> Result<String, CodeviewError> {
+    let mut tags: Vec<(String, String)> = Vec::new();
+    for (path, items) in files {
+        if items.is_empty() {
+            continue;
+        }
+        let source = read_source(Path::new(path), false)?;
+        let lines: Vec<&str> = source.lines().collect();
+        for item in items {
+            let Some(name) = item.name.as_deref() else { continue };
+            let (line, _) = name_position(&lines, item, name);
+            let text = lines.get(line - 1).copied().unwrap_or("");
+            tags.push((
+                name.to_string(),
+                format!("{}\t{}\t/^{}$/;\"\t{}", name, path, escape_pattern(text), kind_letter(&item.kind)),
+            ));
+        }
+    }
+    // Stable, so one name's tags stay in scan order
+    tags.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut out = String::from("!_TAG_FILE_FORMAT\t2\t/extended format/\n!_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted/\n");
+    if let Some(t) = truncated {
+        out.push_str(&format!("!_TAG_OMITTED\t{}\t/items cut by --limit, across {} files/\n", t.omitted, t.files));
+    }
+    for (_, line) in tags {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Single-letter ctags kind for an item kind.
+fn kind_letter(kind: &ItemKind) -> char {
+    match kind {
+        ItemKind::Function => 'f',
+        ItemKind::Method => 'm',
+        ItemKind::Struct => 's',
+        ItemKind::Enum => 'g',
+        ItemKind::Trait => 't',
+        ItemKind::Impl => 'i',
+        ItemKind::Mod => 'n',
+        ItemKind::Use => 'u',
+        ItemKind::Const => 'C',
+        ItemKind::Static => 'v',
+        ItemKind::TypeAlias => 'T',
+        ItemKind::MacroDef => 'd',
+        ItemKind::Class => 'c',
+        ItemKind::Variant => 'e',
+        ItemKind::Field => 'p',
+    }
+}
+
+/// Escape a source line for use inside a `/^...$/` search pattern.
+fn escape_pattern(line: &str) -> String {
+    line.replace('\\', "\\\\").replace('/', "\\/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_pattern_slashes_and_backslashes() {
+        assert_eq!(escape_pattern(r#"let p = "a/b\n";"#), r#"let p = "a\/b\\n";"#);
+    }
+}
@@ -0,0 +1,32 @@
+//! `--grep-defs` output: one `path:line: signature` line per definition, a flat
+//! greppable index of a tree's API surface.
+
+use super::targets::name_position;
+use crate::error::CodeviewError;
+use crate::extractor::{Item, ItemKind};
+use crate::read_source;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Format one `path:line: signature` line per item, imports left out. The line
+/// is the one holding the item's name; items without a signature show their
+/// first line of content. Files are reread `lossy` as they were extracted.
+pub fn format_output(files: &[(String, Vec<Item>)], lossy: bool) -> Result<String, CodeviewError> {
+    let mut out = String::new();
+    for (path, items) in files {
+        if items.iter().all(|item| item.kind == ItemKind::Use) {
+            continue;
+        }
+        let source = read_source(Path::new(path), lossy)?;
+        let lines: Vec<&str> = source.lines().collect();
+        for item in items.iter().filter(|item| item.kind != ItemKind::Use) {
+            let (line, _) = name_position(&lines, item, item.name.as_deref().unwrap_or(""));
+            let signature = match item.signature.as_deref() {
+                Some(signature) => signature,
+                None => item.content.lines().next().unwrap_or("").trim(),
+            };
+            writeln!(out, "{}:{}: {}", path, line, signature).unwrap();
+        }
+    }
+    Ok(out)
+}
@@ -1,63 +1,177 @@
+use super::Truncation;
 use crate::error::CodeviewError;
-use crate::extractor::Item;
+use crate::extractor::collapse::build_source_line_mappings;
+use crate::extractor::{Item, Modifiers, Parameter};
 use serde::Serialize;
 use serde_json;
+use std::io::Write;
 
 #[derive(Serialize)]
 struct JsonOutput {
     files: Vec<FileOutput>,
+    /// Set when `--limit` left items out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    truncated: Option<Truncation>,
 }
 
 #[derive(Serialize)]
 struct FileOutput {
     path: String,
     items: Vec<JsonItem>,
+    /// Items dropped by `--max-symbols`; present only when it is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    omitted: Option<usize>,
 }
 
 #[derive(Serialize)]
-struct JsonItem {
-    kind: String,
+pub(super) struct JsonItem {
+    pub(super) kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) name: Option<String>,
+    pub(super) visibility: String,
+    pub(super) line_start: usize,
+    pub(super) line_end: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) parameters: Option<Vec<Parameter>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(super) type_parameters: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) return_doc: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<String>,
-    visibility: String,
-    line_start: usize,
-    line_end: usize,
+    pub(super) source_module: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    signature: Option<String>,
+    pub(super) resolved: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    body: Option<String>,
-    content: String,
+    pub(super) parent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) node_kind: Option<String>,
+    pub(super) is_test: bool,
+    #[serde(flatten)]
+    pub(super) modifiers: Modifiers,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(super) decorators: Vec<String>,
+    pub(super) content: String,
+    /// `[line_number, text]` per output line of `content`, as the plain gutter shows it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) line_mappings: Option<Vec<(usize, String)>>,
+}
+
+/// One line of `--per-symbol-stream` output: an item tagged with its file.
+#[derive(Serialize)]
+struct StreamItem<'a> {
+    file: &'a str,
+    #[serde(flatten)]
+    item: JsonItem,
 }
 
-/// Format items as JSON
-pub fn format_output(files: &[(String, Vec<Item>)]) -> Result<String, CodeviewError> {
+pub(super) fn json_item(item: &Item, debug_kinds: bool, with_mappings: bool) -> JsonItem {
+    JsonItem {
+        kind: format!("{:?}", item.kind).to_lowercase(),
+        name: item.name.clone(),
+        visibility: format!("{:?}", item.visibility).to_lowercase(),
+        line_start: item.line_start,
+        line_end: item.line_end,
+        signature: item.signature.clone(),
+        body: item.body.clone(),
+        parameters: item.parameters.clone(),
+        type_parameters: item.type_parameters.clone(),
+        return_doc: item.return_doc.clone(),
+        source_module: item.source_module.clone(),
+        resolved: item.resolved.clone(),
+        parent: item.parent.clone(),
+        node_kind: debug_kinds.then(|| item.node_kind.clone()),
+        is_test: item.is_test,
+        modifiers: item.modifiers,
+        decorators: item.decorators.clone(),
+        content: item.content.clone(),
+        line_mappings: with_mappings.then(|| {
+            item.line_mappings
+                .clone()
+                .unwrap_or_else(|| build_source_line_mappings(&item.content, item.line_start))
+        }),
+    }
+}
+
+/// Format items as JSON. `omitted` holds, per file, how many items `--max-symbols`
+/// dropped (empty without it), `truncated` what `--limit` cut overall.
+/// `debug_kinds` adds each item's raw tree-sitter `node_kind`, `with_mappings`
+/// the source line number of each content line.
+pub fn format_output(
+    files: &[(String, Vec<Item>)],
+    omitted: &[usize],
+    truncated: Option<Truncation>,
+    debug_kinds: bool,
+    with_mappings: bool,
+) -> Result<String, CodeviewError> {
     let files_output: Vec<FileOutput> = files
         .iter()
-        .map(|(path, items)| {
-            let json_items: Vec<JsonItem> = items
-                .iter()
-                .map(|item| JsonItem {
-                    kind: format!("{:?}", item.kind).to_lowercase(),
-                    name: item.name.clone(),
-                    visibility: format!("{:?}", item.visibility).to_lowercase(),
-                    line_start: item.line_start,
-                    line_end: item.line_end,
-                    signature: item.signature.clone(),
-                    body: item.body.clone(),
-                    content: item.content.clone(),
-                })
-                .collect();
+        .enumerate()
+        .map(|(i, (path, items))| {
+            let json_items: Vec<JsonItem> = items.iter().map(|item| json_item(item, debug_kinds, with_mappings)).collect();
 
             FileOutput {
                 path: path.clone(),
                 items: json_items,
+                omitted: omitted.get(i).copied(),
             }
         })
         .collect();
 
     let output = JsonOutput {
         files: files_output,
+        truncated,
     };
 
     Ok(serde_json::to_string_pretty(&output)?)
 }
+
+/// Format items as NDJSON: one compact object per item, with its `file` path
+/// alongside the usual item fields.
+pub fn format_symbol_stream(files: &[(String, Vec<Item>)], debug_kinds: bool, with_mappings: bool) -> Result<String, CodeviewError> {
+    let mut output = Vec::new();
+    for (path, items) in files {
+        write_symbol_stream(&mut output, path, items, debug_kinds, with_mappings)?;
+    }
+    Ok(String::from_utf8(output).expect("serde_json writes UTF-8"))
+}
+
+/// Last line of a truncated `--per-symbol-stream`: how many items were dropped
+/// and how many files were never read once the limit was reached.
+#[derive(Serialize)]
+struct StreamSummary {
+    omitted: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    files_not_scanned: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// Write the closing `{"omitted": N}` record of a truncated stream.
+pub fn write_stream_summary(out: &mut impl Write, omitted: usize, files_not_scanned: usize) -> Result<(), CodeviewError> {
+    let mut line = serde_json::to_string(&StreamSummary { omitted, files_not_scanned })?;
+    line.push('\n');
+    out.write_all(line.as_bytes()).map_err(serde_json::Error::io)?;
+    Ok(())
+}
+
+/// Write one file's items to `out` as NDJSON, one `write_all` per item.
+pub fn write_symbol_stream(
+    out: &mut impl Write,
+    path: &str,
+    items: &[Item],
+    debug_kinds: bool,
+    with_mappings: bool,
+) -> Result<(), CodeviewError> {
+    for item in items {
+        let mut line = serde_json::to_string(&StreamItem { file: path, item: json_item(item, debug_kinds, with_mappings) })?;
+        line.push('\n');
+        out.write_all(line.as_bytes()).map_err(serde_json::Error::io)?;
+    }
+    Ok(())
+}
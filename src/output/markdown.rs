@@ -0,0 +1,75 @@
+//! Markdown formatter for documentation generation.
+//!
+//! Each file becomes a `## path` section with a bullet list of its symbols and
+//! their line ranges, followed by one fenced code block per item.
+
+use super::plain::kind_label;
+use crate::error::CodeviewError;
+use crate::extractor::Item;
+use crate::languages::{self, Language};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Format items as Markdown, skipping files without items.
+pub fn format_output(files: &[(String, Vec<Item>)]) -> Result<String, CodeviewError> {
+    let mut output = String::new();
+
+    for (path, items) in files {
+        if items.is_empty() {
+            continue;
+        }
+        let tag = languages::detect_language(Path::new(path)).map(fence_tag).unwrap_or("");
+
+        writeln!(output, "## {}\n", path).unwrap();
+        for item in items {
+            let name = item.name.as_deref().unwrap_or("-");
+            let lines = if item.line_start == item.line_end {
+                format!("L{}", item.line_start)
+            } else {
+                format!("L{}-{}", item.line_start, item.line_end)
+            };
+            writeln!(output, "- `{} {}` ({})", kind_label(&item.kind), name, lines).unwrap();
+        }
+        writeln!(output).unwrap();
+
+        for item in items {
+            let fence = fence_for(&item.content);
+            writeln!(output, "{}{}\n{}\n{}\n", fence, tag, item.content.trim_end(), fence).unwrap();
+        }
+    }
+
+    Ok(output)
+}
+
+/// Info-string language tag for a fenced code block.
+fn fence_tag(language: Language) -> &'static str {
+    match language {
+        Language::Rust => "rust",
+        Language::TypeScript => "typescript",
+        Language::Tsx => "tsx",
+        Language::Python => "python",
+        Language::JavaScript => "javascript",
+        Language::Jsx => "jsx",
+    }
+}
+
+/// A backtick fence longer than any backtick run inside `content`.
+fn fence_for(content: &str) -> String {
+    let longest = content
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fence_outgrows_backticks_in_content() {
+        assert_eq!(fence_for("fn a() {}"), "```");
+        assert_eq!(fence_for("/// ```\n/// x\n/// ```"), "````");
+    }
+}
@@ -1,9 +1,60 @@
+use std::path::Path;
+
 pub mod plain;
 pub mod json;
+pub mod markdown;
+pub mod outline;
 pub mod stats;
+pub mod fingerprint;
+pub mod targets;
+pub mod defs;
+pub mod ctags;
+pub mod xml;
+pub mod template;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum OutputFormat {
+    #[default]
     Plain,
     Json,
+    Markdown,
+    Outline,
+    Ctags,
+    Xml,
+}
+
+/// What `--limit` cut from the output: how many items, from how many files.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct Truncation {
+    pub omitted: usize,
+    pub files: usize,
+}
+
+/// How file paths are printed in plain, JSON, and stats output.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PathStyle {
+    /// Paths as produced by the scan (derived from the path argument)
+    #[default]
+    AsGiven,
+    /// Canonical absolute paths
+    Absolute,
+    /// Relative to the scanned directory (the file name when scanning a single file)
+    Relative,
+    /// File name only
+    Basename,
+}
+
+impl PathStyle {
+    /// Render `path`, found while scanning `root`, in this style.
+    pub fn apply(self, path: &str, root: &Path) -> String {
+        let p = Path::new(path);
+        let styled = match self {
+            PathStyle::AsGiven => None,
+            PathStyle::Absolute => std::fs::canonicalize(p).ok(),
+            PathStyle::Relative if root.is_file() => p.file_name().map(Into::into),
+            PathStyle::Relative => p.strip_prefix(root).ok().map(Path::to_path_buf),
+            PathStyle::Basename => p.file_name().map(Into::into),
+        };
+        styled.map_or_else(|| path.to_string(), |s| s.to_string_lossy().to_string())
+    }
 }
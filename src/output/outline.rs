@@ -0,0 +1,84 @@
+//! Outline formatter: a tree of symbol names per file, without bodies.
+//!
+//! Nesting is reconstructed from line ranges. An item whose lines fall inside an
+//! earlier item's range (a method in an impl, a variant in an enum) is drawn
+//! beneath it with box-drawing connectors.
+
+use super::plain::kind_label;
+use crate::error::CodeviewError;
+use crate::extractor::{Item, ItemKind};
+use std::fmt::Write;
+
+/// Format items as one symbol tree per file, skipping files without items.
+/// Each nesting level is indented by `indent` columns (at least 1).
+pub fn format_output(files: &[(String, Vec<Item>)], indent: usize) -> Result<String, CodeviewError> {
+    let indent = indent.max(1);
+    let mut output = String::new();
+
+    for (path, items) in files {
+        if items.is_empty() {
+            continue;
+        }
+        writeln!(output, "{}", path).unwrap();
+        let (roots, children) = nest(items);
+        write_level(&mut output, items, &children, &roots, "", indent);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Split items (in source order) into top-level indices and, per item, the
+/// indices of the items directly inside it.
+fn nest(items: &[Item]) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let mut roots = Vec::new();
+    let mut children = vec![Vec::new(); items.len()];
+    let mut open: Vec<usize> = Vec::new();
+
+    for (i, item) in items.iter().enumerate() {
+        while let Some(&top) = open.last() {
+            if contains(&items[top], item) {
+                break;
+            }
+            open.pop();
+        }
+        match open.last() {
+            Some(&parent) => children[parent].push(i),
+            None => roots.push(i),
+        }
+        open.push(i);
+    }
+
+    (roots, children)
+}
+
+/// Whether `inner` lies within `outer`'s lines. Equal ranges only nest members
+/// (a one-line `impl` with its method), not two one-line siblings.
+fn contains(outer: &Item, inner: &Item) -> bool {
+    let within = outer.line_start <= inner.line_start && inner.line_end <= outer.line_end;
+    let same_range = outer.line_start == inner.line_start && outer.line_end == inner.line_end;
+    within && (!same_range || matches!(inner.kind, ItemKind::Method | ItemKind::Variant | ItemKind::Field))
+}
+
+fn write_level(output: &mut String, items: &[Item], children: &[Vec<usize>], level: &[usize], prefix: &str, indent: usize) {
+    let rule = "─".repeat(indent - 1);
+    let pad = " ".repeat(indent - 1);
+    for (n, &i) in level.iter().enumerate() {
+        let last = n + 1 == level.len();
+        let item = &items[i];
+        let label = match item.name.as_deref() {
+            Some(name) => format!("{} {}", kind_label(&item.kind), name),
+            // Unnamed items (imports) show their first line instead
+            None => item.content.lines().next().unwrap_or("").trim().to_string(),
+        };
+        let lines = if item.line_start == item.line_end {
+            format!("L{}", item.line_start)
+        } else {
+            format!("L{}-{}", item.line_start, item.line_end)
+        };
+        writeln!(output, "{}{}{} {}  {}", prefix, if last { "└" } else { "├" }, rule, label, lines).unwrap();
+
+        let child_prefix = format!("{}{}{}", prefix, if last { " " } else { "│" }, pad);
+        write_level(output, items, children, &children[i], &child_prefix, indent);
+    }
+}
@@ -1,19 +1,51 @@
 use crate::CodeviewError;
 use crate::extractor::{Item, ItemKind};
 
-/// Format items as plain text with line numbers
-pub fn format_output(files: &[(String, Vec<Item>)], expand_mode: bool, max_lines: Option<usize>) -> Result<String, CodeviewError> {
+/// Format items as plain text with line numbers.
+/// `omitted` holds, per file, how many items were dropped by `--max-symbols` (may be empty).
+/// With `group_consts`, runs of two or more consecutive const/static items are printed
+/// as one compact block under a `// constants` heading (interface mode only).
+/// With `emit_empty`, files without items are listed with a `// (no items)` marker
+/// instead of being skipped (interface mode only).
+/// With `merge`, one summary line leads the output and each file starts with a
+/// `// ==== path ====` banner instead of its plain path header.
+pub fn format_output(files: &[(String, Vec<Item>)], omitted: &[usize], expand_mode: bool, max_lines: Option<usize>, group_consts: bool, emit_empty: bool, merge: bool) -> Result<String, CodeviewError> {
     let mut output = String::new();
 
-    for (file_path, items) in files {
+    if merge {
+        let shown = files.iter().filter(|(_, items)| !items.is_empty() || (emit_empty && !expand_mode)).count();
+        let total: usize = files.iter().map(|(_, items)| items.len()).sum();
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        output.push_str(&format!("// merged: {} file{}, {} item{}\n\n", shown, plural(shown), total, plural(total)));
+    }
+    let header = |path: &str| if merge { format!("// ==== {} ====\n", path) } else { format!("{}\n", path) };
+
+    for (i, (file_path, items)) in files.iter().enumerate() {
+        let file_omitted = omitted.get(i).copied().unwrap_or(0);
         if items.is_empty() {
+            if file_omitted > 0 {
+                // Capped to nothing: still say what was left out
+                output.push_str(&header(file_path));
+                push_omitted_marker(&mut output, file_omitted);
+            } else if emit_empty && !expand_mode {
+                output.push_str(&format!("{}// (no items)\n\n", header(file_path)));
+            }
             continue;
         }
 
         if expand_mode {
+            if merge {
+                output.push_str(&header(file_path));
+            }
             // Expand mode: each item gets a header with file::symbol [start:end]
+            // (just symbol [start:end] under a merge banner)
             for item in items {
-                if let Some(ref name) = item.name {
+                if merge {
+                    output.push_str(&format!(
+                        "{} [{}:{}]\n",
+                        item.name.as_deref().unwrap_or("-"), item.line_start, item.line_end
+                    ));
+                } else if let Some(ref name) = item.name {
                     output.push_str(&format!(
                         "{}::{} [{}:{}]\n",
                         file_path, name, item.line_start, item.line_end
@@ -44,60 +76,113 @@ pub fn format_output(files: &[(String, Vec<Item>)], expand_mode: bool, max_lines
             }
         } else {
             // Interface mode: file header once, then all items
-            output.push_str(file_path);
-            output.push('\n');
+            output.push_str(&header(file_path));
 
-            for item in items {
-                output.push_str(&format_item(item));
-                output.push('\n');
+            let mut rest = items.as_slice();
+            while let Some(item) = rest.first() {
+                let run = if group_consts {
+                    rest.iter().take_while(|i| is_const_like(i)).count()
+                } else {
+                    0
+                };
+                if run >= 2 {
+                    let width = rest[..run].iter().map(|i| i.line_end.to_string().len()).max().unwrap_or(1);
+                    output.push_str("// constants\n");
+                    for item in &rest[..run] {
+                        output.push_str(&format_item_with_width(item, width));
+                    }
+                    output.push('\n');
+                    rest = &rest[run..];
+                } else {
+                    output.push_str(&format_item(item));
+                    output.push('\n');
+                    rest = &rest[1..];
+                }
             }
         }
+
+        push_omitted_marker(&mut output, file_omitted);
     }
 
     Ok(output)
 }
 
-pub fn format_list_symbols(files: &[(String, Vec<Item>)]) -> Result<String, CodeviewError> {
+fn push_omitted_marker(output: &mut String, omitted: usize) {
+    if omitted > 0 {
+        output.push_str(&format!("// ... and {} more symbols\n", omitted));
+    }
+}
+
+pub fn format_list_symbols(files: &[(String, Vec<Item>)], omitted: &[usize]) -> Result<String, CodeviewError> {
     use std::fmt::Write;
     let mut output = String::new();
 
-    for (file_path, items) in files {
-        if items.is_empty() {
+    for (i, (file_path, items)) in files.iter().enumerate() {
+        let file_omitted = omitted.get(i).copied().unwrap_or(0);
+        if items.is_empty() && file_omitted == 0 {
             continue;
         }
 
         writeln!(output, "{}", file_path).unwrap();
 
         for item in items {
-            let kind_label = match item.kind {
-                ItemKind::Function => "fn",
-                ItemKind::Method => "fn",
-                ItemKind::Struct => "struct",
-                ItemKind::Enum => "enum",
-                ItemKind::Trait => "trait",
-                ItemKind::Impl => "impl",
-                ItemKind::Mod => "mod",
-                ItemKind::Use => "use",
-                ItemKind::Const => "const",
-                ItemKind::Static => "static",
-                ItemKind::TypeAlias => "type",
-                ItemKind::MacroDef => "macro",
-                ItemKind::Class => "class",
-            };
             let name = item.name.as_deref().unwrap_or("-");
-            writeln!(output, "  {} {:<30} L{}", kind_label, name, item.line_start).unwrap();
+            write!(output, "  {} {:<30} L{}", kind_label(&item.kind), name, item.line_start).unwrap();
+            // Fields carry their declaration, and generic or bounded types their header,
+            // so neither needs expanding to see its type or bounds
+            let show_signature = |signature: &str| match item.kind {
+                ItemKind::Field => true,
+                ItemKind::Struct | ItemKind::Enum | ItemKind::Trait => {
+                    !item.type_parameters.is_empty() || signature.contains(':')
+                }
+                _ => false,
+            };
+            match item.signature.as_deref() {
+                Some(signature) if show_signature(signature) => writeln!(output, "  {}", signature).unwrap(),
+                _ => output.push('\n'),
+            }
         }
+
+        push_omitted_marker(&mut output, file_omitted);
     }
 
     Ok(output)
 }
 
-fn format_item(item: &Item) -> String {
-    let mut result = String::new();
+/// Short keyword label for an item kind (`fn`, `struct`, ...).
+pub(crate) fn kind_label(kind: &ItemKind) -> &'static str {
+    match kind {
+        ItemKind::Function => "fn",
+        ItemKind::Method => "fn",
+        ItemKind::Struct => "struct",
+        ItemKind::Enum => "enum",
+        ItemKind::Trait => "trait",
+        ItemKind::Impl => "impl",
+        ItemKind::Mod => "mod",
+        ItemKind::Use => "use",
+        ItemKind::Const => "const",
+        ItemKind::Static => "static",
+        ItemKind::TypeAlias => "type",
+        ItemKind::MacroDef => "macro",
+        ItemKind::Class => "class",
+        ItemKind::Variant => "variant",
+        ItemKind::Field => "field",
+    }
+}
+
+fn is_const_like(item: &Item) -> bool {
+    matches!(item.kind, ItemKind::Const | ItemKind::Static)
+}
 
+/// Format a single item's content with right-aligned line numbers.
+pub(crate) fn format_item(item: &Item) -> String {
     // Calculate max line number width for alignment
-    let max_line_num = item.line_end;
-    let width = max_line_num.to_string().len();
+    format_item_with_width(item, item.line_end.to_string().len())
+}
+
+/// Like [`format_item`], padding line numbers to `width` columns.
+fn format_item_with_width(item: &Item, width: usize) -> String {
+    let mut result = String::new();
 
     // Use explicit line mappings if available (for interface mode with collapsed bodies)
     if let Some(ref mappings) = item.line_mappings {
@@ -123,17 +208,7 @@ mod tests {
     use crate::extractor::{Item, ItemKind, Visibility};
 
     fn make_item(name: &str, content: &str, line_start: usize, line_end: usize) -> Item {
-        Item {
-            kind: ItemKind::Function,
-            name: Some(name.to_string()),
-            visibility: Visibility::Public,
-            line_start,
-            line_end,
-            signature: None,
-            body: None,
-            content: content.to_string(),
-            line_mappings: None,
-        }
+        Item { visibility: Visibility::Public, ..Item::new(ItemKind::Function, Some(name), content, line_start, line_end) }
     }
 
     #[test]
@@ -159,7 +234,7 @@ mod tests {
     fn format_output_interface_mode() {
         let item = make_item("bar", "fn bar() {}", 1, 1);
         let files = vec![("src/lib.rs".to_string(), vec![item])];
-        let result = format_output(&files, false, None).unwrap();
+        let result = format_output(&files, &[], false, None, false, false, false).unwrap();
         assert!(result.starts_with("src/lib.rs\n"));
         assert!(result.contains("fn bar() {}"));
     }
@@ -168,7 +243,7 @@ mod tests {
     fn format_output_expand_mode() {
         let item = make_item("bar", "fn bar() {}", 1, 1);
         let files = vec![("src/lib.rs".to_string(), vec![item])];
-        let result = format_output(&files, true, None).unwrap();
+        let result = format_output(&files, &[], true, None, false, false, false).unwrap();
         assert!(result.contains("src/lib.rs::bar [1:1]"));
     }
 
@@ -177,14 +252,14 @@ mod tests {
         let mut item = make_item("bar", "use std::io;", 1, 1);
         item.name = None;
         let files = vec![("src/lib.rs".to_string(), vec![item])];
-        let result = format_output(&files, true, None).unwrap();
+        let result = format_output(&files, &[], true, None, false, false, false).unwrap();
         assert!(result.contains("src/lib.rs [1:1]"));
     }
 
     #[test]
     fn format_output_skips_empty_files() {
         let files = vec![("empty.rs".to_string(), vec![])];
-        let result = format_output(&files, false, None).unwrap();
+        let result = format_output(&files, &[], false, None, false, false, false).unwrap();
         assert!(result.is_empty());
     }
 }
@@ -1,5 +1,5 @@
 use crate::error::CodeviewError;
-use crate::extractor::Item;
+use crate::extractor::{Item, ItemKind};
 use super::OutputFormat;
 use std::collections::BTreeMap;
 use std::fmt::Write;
@@ -56,8 +56,9 @@ pub fn format_output(
     format: OutputFormat,
 ) -> Result<String, CodeviewError> {
     match format {
-        OutputFormat::Plain => format_plain(files, source_sizes),
+        OutputFormat::Plain | OutputFormat::Outline | OutputFormat::Ctags | OutputFormat::Xml => format_plain(files, source_sizes),
         OutputFormat::Json => format_json(files, source_sizes),
+        OutputFormat::Markdown => format_markdown(files, source_sizes),
     }
 }
 
@@ -100,6 +101,27 @@ fn format_plain(
     Ok(out)
 }
 
+/// A Markdown table of per-file line and item counts, with a totals row.
+fn format_markdown(
+    files: &[(String, Vec<Item>)],
+    source_sizes: &[(usize, usize)],
+) -> Result<String, CodeviewError> {
+    let (file_stats, total_lines, _, total_items, _) = gather_stats(files, source_sizes);
+
+    let mut out = String::new();
+    writeln!(out, "| file | lines | items |").unwrap();
+    writeln!(out, "|------|------:|------:|").unwrap();
+    for f in &file_stats {
+        if f.items == 0 && file_stats.len() > 1 {
+            continue;
+        }
+        writeln!(out, "| {} | {} | {} |", f.path, f.lines, f.items).unwrap();
+    }
+    writeln!(out, "| **total** | {} | {} |", total_lines, total_items).unwrap();
+
+    Ok(out)
+}
+
 fn format_json(
     files: &[(String, Vec<Item>)],
     source_sizes: &[(usize, usize)],
@@ -151,3 +173,101 @@ fn format_json(
 
     Ok(serde_json::to_string_pretty(&output)?)
 }
+
+/// A public type and how many public methods its impls/class body define.
+struct TypeApi<'a> {
+    kind: &'static str,
+    name: &'a str,
+    line: usize,
+    public_methods: usize,
+}
+
+/// Per file, each public struct/enum/class with its count of public methods and
+/// associated functions. Methods are matched to types by their enclosing
+/// impl/class name within the same file, ignoring generic arguments. Methods of
+/// a Rust trait impl count as public: they are as visible as the trait.
+pub fn format_type_api(files: &[(String, Vec<Item>)], format: OutputFormat) -> Result<String, CodeviewError> {
+    let per_file: Vec<(&str, Vec<TypeApi>)> = files
+        .iter()
+        .map(|(path, items)| (path.as_str(), type_api(items)))
+        .filter(|(_, types)| !types.is_empty())
+        .collect();
+
+    let mut out = String::new();
+    match format {
+        OutputFormat::Json => {
+            use serde::Serialize;
+
+            #[derive(Serialize)]
+            struct TypeApiJson<'a> {
+                path: &'a str,
+                kind: &'static str,
+                name: &'a str,
+                line: usize,
+                public_methods: usize,
+            }
+
+            let types: Vec<TypeApiJson> = per_file
+                .iter()
+                .flat_map(|(path, types)| types.iter().map(move |t| TypeApiJson {
+                    path,
+                    kind: t.kind,
+                    name: t.name,
+                    line: t.line,
+                    public_methods: t.public_methods,
+                }))
+                .collect();
+            out = serde_json::to_string_pretty(&types)?;
+        }
+        OutputFormat::Markdown => {
+            writeln!(out, "| file | type | public methods |").unwrap();
+            writeln!(out, "|------|------|---------------:|").unwrap();
+            for (path, types) in &per_file {
+                for t in types {
+                    writeln!(out, "| {} | `{} {}` | {} |", path, t.kind, t.name, t.public_methods).unwrap();
+                }
+            }
+        }
+        OutputFormat::Plain | OutputFormat::Outline | OutputFormat::Ctags | OutputFormat::Xml => {
+            for (path, types) in &per_file {
+                writeln!(out, "{}", path).unwrap();
+                for t in types {
+                    writeln!(out, "  {} {:<30} {} public methods", t.kind, t.name, t.public_methods).unwrap();
+                }
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Whether `method` lies inside an `impl Trait for Type` block among `items`.
+fn in_trait_impl(items: &[Item], method: &Item) -> bool {
+    items.iter().any(|block| {
+        matches!(block.kind, ItemKind::Impl)
+            && block.line_start <= method.line_start
+            && method.line_end <= block.line_end
+            && block.signature.as_deref().is_some_and(|header| header.contains(" for "))
+    })
+}
+
+fn type_api(items: &[Item]) -> Vec<TypeApi<'_>> {
+    items
+        .iter()
+        .filter(|item| item.is_public() && matches!(item.kind, ItemKind::Struct | ItemKind::Enum | ItemKind::Class))
+        .filter_map(|item| {
+            let name = item.name.as_deref()?;
+            let public_methods = items
+                .iter()
+                .filter(|m| matches!(m.kind, ItemKind::Method) && (m.is_public() || in_trait_impl(items, m)))
+                .filter(|m| m.parent.as_deref().is_some_and(|p| p.split('<').next().unwrap_or(p).trim() == name))
+                .count();
+            Some(TypeApi {
+                kind: super::plain::kind_label(&item.kind),
+                name,
+                line: item.line_start,
+                public_methods,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,64 @@
+//! Jump-target lines (`path:line:col:kind:name`) for editor pickers.
+
+use super::plain::kind_label;
+use crate::error::CodeviewError;
+use crate::extractor::Item;
+use crate::read_source;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Format one `path:line:col:kind:name` line per item. Line and column point
+/// at the item's name where it can be found, else at the start of the item.
+pub fn format_output(files: &[(String, Vec<Item>)]) -> Result<String, CodeviewError> {
+    let mut out = String::new();
+    for (path, items) in files {
+        if items.is_empty() {
+            continue;
+        }
+        let source = read_source(Path::new(path), false)?;
+        let lines: Vec<&str> = source.lines().collect();
+        for item in items {
+            let name = item.name.as_deref().unwrap_or("-");
+            let (line, col) = name_position(&lines, item, name);
+            writeln!(out, "{}:{}:{}:{}:{}", path, line, col, kind_label(&item.kind), name).unwrap();
+        }
+    }
+    Ok(out)
+}
+
+/// 1-indexed (line, byte column) of the first whole-word occurrence of `name`
+/// within the item's lines, falling back to the first non-blank column of its first line.
+pub(crate) fn name_position(lines: &[&str], item: &Item, name: &str) -> (usize, usize) {
+    for line_no in item.line_start..=item.line_end.min(lines.len()) {
+        let text = lines[line_no - 1];
+        if let Some(col) = find_word(text, name) {
+            return (line_no, col + 1);
+        }
+    }
+    let first = lines.get(item.line_start.wrapping_sub(1)).copied().unwrap_or("");
+    (item.line_start, first.len() - first.trim_start().len() + 1)
+}
+
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    if word.is_empty() {
+        return None;
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    text.match_indices(word).map(|(i, _)| i).find(|&i| {
+        let before = text[..i].chars().next_back().is_none_or(|c| !is_ident(c));
+        let after = text[i + word.len()..].chars().next().is_none_or(|c| !is_ident(c));
+        before && after
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_word_skips_partial_matches() {
+        assert_eq!(find_word("fn get_user_id(user: u32)", "user"), Some(15));
+        assert_eq!(find_word("pub struct User {", "User"), Some(11));
+        assert_eq!(find_word("fn users()", "user"), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! `--template` output: one line per item, laid out by a format string such as
+//! `{kind} {name} ({file}:{line_start})`.
+//!
+//! Placeholders are `{kind}` (as in JSON output), `{name}`, `{visibility}`,
+//! `{line_start}`, `{line_end}`, `{file}` and `{signature}`. `{{` and `}}` stand
+//! for literal braces. A missing name or signature renders as an empty string.
+
+use crate::error::CodeviewError;
+use crate::extractor::Item;
+
+const PLACEHOLDERS: [(&str, Placeholder); 7] = [
+    ("kind", Placeholder::Kind),
+    ("name", Placeholder::Name),
+    ("visibility", Placeholder::Visibility),
+    ("line_start", Placeholder::LineStart),
+    ("line_end", Placeholder::LineEnd),
+    ("file", Placeholder::File),
+    ("signature", Placeholder::Signature),
+];
+
+/// A parsed `--template` format string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placeholder {
+    Kind,
+    Name,
+    Visibility,
+    LineStart,
+    LineEnd,
+    File,
+    Signature,
+}
+
+impl Template {
+    /// Parse a format string, rejecting unknown placeholders and unbalanced braces.
+    pub fn parse(input: &str) -> Result<Template, CodeviewError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = input.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest.find('}').ok_or_else(|| error("unclosed '{'".to_string()))?;
+                    let name = &rest[..end];
+                    let placeholder = PLACEHOLDERS.iter().find(|(n, _)| *n == name).map(|&(_, p)| p).ok_or_else(|| {
+                        let known: Vec<&str> = PLACEHOLDERS.iter().map(|(n, _)| *n).collect();
+                        error(format!("unknown placeholder '{{{}}}' (expected one of {})", name, known.join(", ")))
+                    })?;
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Placeholder(placeholder));
+                    chars = rest[end + 1..].chars();
+                }
+                '}' => return Err(error("unmatched '}' (write '}}' for a literal brace)".to_string())),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Template { parts })
+    }
+
+    /// Render `item`, found in `file`.
+    pub fn render(&self, file: &str, item: &Item) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => out.push_str(text),
+                Part::Placeholder(placeholder) => match placeholder {
+                    Placeholder::Kind => out.push_str(&format!("{:?}", item.kind).to_lowercase()),
+                    Placeholder::Name => out.push_str(item.name.as_deref().unwrap_or("")),
+                    Placeholder::Visibility => out.push_str(&format!("{:?}", item.visibility).to_lowercase()),
+                    Placeholder::LineStart => out.push_str(&item.line_start.to_string()),
+                    Placeholder::LineEnd => out.push_str(&item.line_end.to_string()),
+                    Placeholder::File => out.push_str(file),
+                    Placeholder::Signature => out.push_str(item.signature.as_deref().unwrap_or("")),
+                },
+            }
+        }
+        out
+    }
+}
+
+/// Format every item as one line rendered from `template`.
+pub fn format_output(files: &[(String, Vec<Item>)], template: &Template) -> Result<String, CodeviewError> {
+    let mut output = String::new();
+    for (file, items) in files {
+        for item in items {
+            output.push_str(&template.render(file, item));
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+fn error(message: String) -> CodeviewError {
+    CodeviewError::ParseError(format!("invalid template: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_name_the_problem() {
+        let err = |s| Template::parse(s).unwrap_err().to_string();
+        assert!(err("{kind} {size}").contains("unknown placeholder '{size}'"));
+        assert!(err("{name").contains("unclosed '{'"));
+        assert!(err("name}").contains("unmatched '}'"));
+    }
+
+    #[test]
+    fn escaped_braces_are_literal() {
+        let template = Template::parse("{{{name}}}").unwrap();
+        assert_eq!(
+            template.parts,
+            vec![Part::Literal("{".to_string()), Part::Placeholder(Placeholder::Name), Part::Literal("}".to_string())]
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! XML formatter for tooling that does not ingest JSON.
+//!
+//! Items are built with the JSON formatter's `JsonItem`, so both formats carry
+//! the same fields: scalars become attributes of `<item>`, parameters and type
+//! parameters become child elements, and the source text goes in a `<content>` element.
+
+use super::json::{json_item, JsonItem};
+use super::Truncation;
+use crate::error::CodeviewError;
+use crate::extractor::{Item, Parameter};
+use std::fmt::Write;
+
+/// Format items as an XML document rooted at `<codeview>`. What `--limit` cut
+/// is recorded in a closing `<truncated omitted=".." files=".."/>` element.
+pub fn format_output(files: &[(String, Vec<Item>)], truncated: Option<Truncation>, debug_kinds: bool) -> Result<String, CodeviewError> {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<codeview>\n");
+
+    for (path, items) in files {
+        writeln!(out, "  <file path=\"{}\">", escape(path)).unwrap();
+        for item in items {
+            write_item(&mut out, json_item(item, debug_kinds, false))?;
+        }
+        out.push_str("  </file>\n");
+    }
+
+    if let Some(t) = truncated {
+        writeln!(out, "  <truncated omitted=\"{}\" files=\"{}\"/>", t.omitted, t.files).unwrap();
+    }
+    out.push_str("</codeview>\n");
+    Ok(out)
+}
+
+fn write_item(out: &mut String, item: JsonItem) -> Result<(), CodeviewError> {
+    // Destructured without `..` so a new JsonItem field cannot be silently left out here
+    let JsonItem {
+        kind,
+        name,
+        visibility,
+        line_start,
+        line_end,
+        signature,
+        body,
+        parameters,
+        type_parameters,
+        return_doc,
+        source_module,
+        resolved,
+        parent,
+        node_kind,
+        is_test,
+        modifiers,
+        decorators,
+        content,
+        // Only requested for JSON (--with-mappings)
+        line_mappings: _,
+    } = item;
+
+    out.push_str("    <item");
+    let attrs = [
+        ("kind", Some(kind)),
+        ("name", name),
+        ("visibility", Some(visibility)),
+        ("line_start", Some(line_start.to_string())),
+        ("line_end", Some(line_end.to_string())),
+        ("signature", signature),
+        ("body", body),
+        ("return_doc", return_doc),
+        ("source_module", source_module),
+        ("resolved", resolved),
+        ("parent", parent),
+        ("node_kind", node_kind),
+        ("is_test", Some(is_test.to_string())),
+    ];
+    for (key, value) in attrs {
+        if let Some(value) = value {
+            push_attr(out, key, &value);
+        }
+    }
+    // Only set modifiers serialize, as in JSON
+    if let serde_json::Value::Object(flags) = serde_json::to_value(modifiers)? {
+        for (key, value) in flags {
+            push_attr(out, &key, &value.to_string());
+        }
+    }
+    out.push_str(">\n");
+
+    if let Some(parameters) = parameters {
+        out.push_str("      <parameters>\n");
+        for Parameter { name, type_annotation, doc, kind } in parameters {
+            out.push_str("        <parameter");
+            push_attr(out, "name", &name);
+            if let Some(ty) = type_annotation {
+                push_attr(out, "type", &ty);
+            }
+            if let Some(doc) = doc {
+                push_attr(out, "doc", &doc);
+            }
+            if let Some(serde_json::Value::String(kind)) = kind.map(serde_json::to_value).transpose()? {
+                push_attr(out, "kind", &kind);
+            }
+            out.push_str("/>\n");
+        }
+        out.push_str("      </parameters>\n");
+    }
+    if !type_parameters.is_empty() {
+        out.push_str("      <type_parameters>\n");
+        for param in type_parameters {
+            writeln!(out, "        <type_parameter>{}</type_parameter>", escape(&param)).unwrap();
+        }
+        out.push_str("      </type_parameters>\n");
+    }
+    if !decorators.is_empty() {
+        out.push_str("      <decorators>\n");
+        for decorator in decorators {
+            writeln!(out, "        <decorator>{}</decorator>", escape(&decorator)).unwrap();
+        }
+        out.push_str("      </decorators>\n");
+    }
+
+    writeln!(out, "      <content>{}</content>", escape(&content)).unwrap();
+    out.push_str("    </item>\n");
+    Ok(())
+}
+
+fn push_attr(out: &mut String, key: &str, value: &str) {
+    // Newlines are encoded so attribute-value normalization keeps them
+    write!(out, " {}=\"{}\"", key, escape(value).replace('\n', "&#10;")).unwrap();
+}
+
+/// Escape markup characters. Control characters XML 1.0 cannot represent are
+/// replaced with U+FFFD.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => escaped.push('\u{FFFD}'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_markup_and_quotes() {
+        assert_eq!(escape(r#"if a < b && c > "d" { 'e' }"#), "if a &lt; b &amp;&amp; c &gt; &quot;d&quot; { &apos;e&apos; }");
+        assert_eq!(escape("a\u{1}b\tc"), "a\u{FFFD}b\tc");
+    }
+}
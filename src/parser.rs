@@ -1,11 +1,49 @@
 use crate::error::CodeviewError;
 use crate::languages::Language;
+use std::cell::RefCell;
 use tree_sitter::{Parser, Tree};
 
-/// Parse source code into a Tree-sitter AST
+thread_local! {
+    static PARSERS: RefCell<ParserCache> = RefCell::new(ParserCache::new());
+}
+
+/// Parse source code into a Tree-sitter AST, reusing this thread's parser for
+/// the language.
 pub fn parse(source: &str, language: Language) -> Result<Tree, CodeviewError> {
-    let mut parser = Parser::new();
-    
+    PARSERS.with(|parsers| parsers.borrow_mut().parse(source, language))
+}
+
+/// Tree-sitter parsers kept per language, so repeated parses skip creating a
+/// `Parser` and setting its language.
+#[derive(Default)]
+pub struct ParserCache {
+    parsers: Vec<(Language, Parser)>,
+}
+
+impl ParserCache {
+    pub fn new() -> ParserCache {
+        ParserCache::default()
+    }
+
+    /// Parse `source`, creating the parser for `language` on first use.
+    pub fn parse(&mut self, source: &str, language: Language) -> Result<Tree, CodeviewError> {
+        let index = match self.parsers.iter().position(|(l, _)| *l == language) {
+            Some(index) => index,
+            None => {
+                self.parsers.push((language, new_parser(language)?));
+                self.parsers.len() - 1
+            }
+        };
+        let parser = &mut self.parsers[index].1;
+        parser.parse(source, None).ok_or_else(|| {
+            parser.reset();
+            CodeviewError::ParseError("Failed to parse source code".to_string())
+        })
+    }
+}
+
+/// A parser with `language` set.
+pub(crate) fn new_parser(language: Language) -> Result<Parser, CodeviewError> {
     let ts_language = match language {
         Language::Rust => tree_sitter_rust::LANGUAGE.into(),
         Language::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
@@ -14,13 +52,11 @@ pub fn parse(source: &str, language: Language) -> Result<Tree, CodeviewError> {
         Language::JavaScript | Language::Jsx => tree_sitter_javascript::LANGUAGE.into(),
     };
 
+    let mut parser = Parser::new();
     parser
         .set_language(&ts_language)
         .map_err(|e| CodeviewError::ParseError(format!("Failed to set language: {}", e)))?;
-
-    parser
-        .parse(source, None)
-        .ok_or_else(|| CodeviewError::ParseError("Failed to parse source code".to_string()))
+    Ok(parser)
 }
 
 
@@ -60,6 +96,17 @@ impl Foo {
         assert_eq!(tree.root_node().child_count(), 0);
     }
 
+    #[test]
+    fn parser_cache_keeps_one_parser_per_language() {
+        let mut cache = ParserCache::new();
+        let rust = cache.parse("fn a() {}", Language::Rust).unwrap();
+        cache.parse("def a(): pass", Language::Python).unwrap();
+        let again = cache.parse("fn b() {}", Language::Rust).unwrap();
+        assert_eq!(cache.parsers.len(), 2);
+        assert_eq!(rust.root_node().kind(), again.root_node().kind());
+        assert!(!again.root_node().has_error());
+    }
+
     #[test]
     fn parse_returns_tree_even_for_partial_errors() {
         // tree-sitter is error-tolerant, so garbage still parses (with error nodes)
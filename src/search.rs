@@ -1,29 +1,80 @@
 use crate::error::CodeviewError;
+use crate::extractor::{expand, ItemKind};
+use crate::git::{self, BlameInfo};
 use crate::languages::{self, Language};
 use crate::parser;
+use crate::read_source;
 use crate::walk;
 use regex::{Regex, RegexBuilder};
+use serde::Serialize;
 use std::collections::BTreeMap;
 use std::fmt::Write;
-use std::fs;
 use std::path::Path;
 use tree_sitter::{Node, Tree};
 
 /// A single search match with its line number, content, and enclosing symbol path.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SearchMatch {
     pub line_number: usize,
     pub line_content: String,
     pub symbol_path: Vec<String>,
+    /// Last author/commit for the line (populated with `blame`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame: Option<BlameInfo>,
+    /// First top-level symbol after the match's top-level symbol (populated with `next_symbol`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_symbol: Option<String>,
+    /// Up to `before` source lines preceding the match (populated with `before`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<ContextLine>,
+    /// Up to `after` source lines following the match (populated with `after`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<ContextLine>,
+}
+
+/// A source line shown around a match.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextLine {
+    pub line_number: usize,
+    pub line_content: String,
 }
 
 /// Options for structural search.
+#[derive(Default)]
 pub struct SearchOptions {
     pub pattern: String,
     pub case_insensitive: bool,
+    /// Only match the pattern as a whole word
+    pub whole_word: bool,
+    /// Report the lines that do not match the pattern instead
+    pub invert: bool,
     pub depth: Option<usize>,
     pub ext: Vec<String>,
+    /// Path globs (relative to the searched directory) a file must match to be searched
+    pub include_globs: Vec<String>,
+    /// Path globs (relative to the searched directory) whose files are skipped
+    pub exclude_globs: Vec<String>,
     pub max_results: Option<usize>,
+    /// Annotate each match with `git blame` author and short sha
+    pub blame: bool,
+    /// Print each enclosing symbol in full, with match lines marked, instead of single lines
+    pub expand_matches: bool,
+    /// Note the next top-level symbol after each match's enclosing one
+    pub next_symbol: bool,
+    /// Emit results as JSON instead of grouped text
+    pub json: bool,
+    /// Print per-file match counts and a total instead of the matches
+    pub count: bool,
+    /// Context lines to show before each match
+    pub before: usize,
+    /// Context lines to show after each match
+    pub after: usize,
+    /// Keep only the innermost N components of each match's symbol path
+    pub symbol_depth: Option<usize>,
+    /// Keep only matches whose innermost enclosing symbol is one of these kinds
+    pub in_kind: Vec<ItemKind>,
+    /// Decode non-UTF-8 files with replacement characters instead of skipping them
+    pub lossy: bool,
 }
 
 /// Perform structural search on a path (file or directory).
@@ -31,7 +82,13 @@ pub fn search_path(
     path: &str,
     options: &SearchOptions,
 ) -> Result<String, CodeviewError> {
-    let regex = RegexBuilder::new(&options.pattern)
+    // Grouped so the boundaries apply to every alternative of the pattern
+    let pattern = if options.whole_word {
+        format!(r"\b(?:{})\b", options.pattern)
+    } else {
+        options.pattern.clone()
+    };
+    let regex = RegexBuilder::new(&pattern)
         .case_insensitive(options.case_insensitive)
         .build()
         .map_err(|e| CodeviewError::ParseError(format!("Invalid regex pattern: {}", e)))?;
@@ -41,23 +98,24 @@ pub fn search_path(
         return Err(CodeviewError::PathNotFound(path.display().to_string()));
     }
 
-    let file_results: Vec<(String, Vec<SearchMatch>)> = if path.is_file() {
+    let mut file_results: Vec<(String, Vec<SearchMatch>)> = if path.is_file() {
         let lang = languages::detect_language(path)?;
-        let matches = search_file(path, &regex, lang)?;
+        let matches = search_file(path, &regex, lang, options)?;
         if matches.is_empty() {
             vec![]
         } else {
             vec![(path.to_string_lossy().to_string(), matches)]
         }
     } else if path.is_dir() {
-        let files = walk::walk_directory(path, options.depth, &options.ext)?;
+        let globs = walk::PathGlobs::new(&options.include_globs, &options.exclude_globs)?;
+        let files = walk::walk_directory_with(path, options.depth, &options.ext, &walk::LanguageOverrides::default(), &globs)?;
         let mut results = Vec::new();
         for file_path in files {
             let lang = match languages::detect_language(&file_path) {
                 Ok(l) => l,
                 Err(_) => continue,
             };
-            match search_file(&file_path, &regex, lang) {
+            match search_file(&file_path, &regex, lang, options) {
                 Ok(matches) if !matches.is_empty() => {
                     results.push((file_path.to_string_lossy().to_string(), matches));
                 }
@@ -69,7 +127,19 @@ pub fn search_path(
         return Err(CodeviewError::InvalidPath(path.display().to_string()));
     };
 
-    // Apply max_results cap
+    if options.count {
+        return format_search_counts(&file_results, options.json);
+    }
+
+    if let Some(depth) = options.symbol_depth {
+        for m in file_results.iter_mut().flat_map(|(_, matches)| matches.iter_mut()) {
+            let outer = m.symbol_path.len().saturating_sub(depth);
+            m.symbol_path.drain(..outer);
+        }
+    }
+
+    // Apply max_results cap, remembering what was left out for the summary
+    let mut overflow_summary = None;
     if let Some(max) = options.max_results {
         let total_matches: usize = file_results.iter().map(|(_, m)| m.len()).sum();
         if total_matches > max {
@@ -99,13 +169,35 @@ pub fn search_path(
             let shown_files = capped_results.len();
             let extra_files = total_files_with_matches - shown_files;
 
-            let mut output = format_search_results(&capped_results);
+            file_results = capped_results;
+            overflow_summary = Some((overflow, extra_files));
+        }
+    }
+
+    // Only the matches that get printed are blamed: one `git blame` per shown file
+    if options.blame {
+        annotate_blame(&mut file_results);
+    }
+
+    match overflow_summary {
+        Some(summary) if options.json => format_search_json(&file_results, Some(summary)),
+        Some((overflow, extra_files)) => {
+            let mut output = render_results(&file_results, options)?;
             writeln!(output, "\n... and {} more matches across {} files", overflow, extra_files).unwrap();
-            return Ok(output);
+            Ok(output)
         }
+        None => render_results(&file_results, options),
     }
+}
 
-    Ok(format_search_results(&file_results))
+fn render_results(file_results: &[(String, Vec<SearchMatch>)], options: &SearchOptions) -> Result<String, CodeviewError> {
+    if options.json {
+        format_search_json(file_results, None)
+    } else if options.expand_matches {
+        format_expanded_matches(file_results, options.lossy)
+    } else {
+        Ok(format_search_results(file_results))
+    }
 }
 
 /// Search a single file and return matches with structural context.
@@ -113,24 +205,40 @@ fn search_file(
     path: &Path,
     regex: &Regex,
     language: Language,
+    options: &SearchOptions,
 ) -> Result<Vec<SearchMatch>, CodeviewError> {
-    let source = fs::read_to_string(path).map_err(|e| CodeviewError::ReadError {
-        path: path.display().to_string(),
-        source: e,
-    })?;
+    let source = read_source(path, options.lossy)?;
 
     let tree = parser::parse(&source, language)?;
     let lines: Vec<&str> = source.lines().collect();
 
     let mut matches = Vec::new();
     for (idx, line) in lines.iter().enumerate() {
-        if regex.is_match(line) {
+        if regex.is_match(line) != options.invert {
             let line_number = idx + 1; // 1-indexed
-            let symbol_path = find_enclosing_symbols(&tree, &source, idx, language);
+            let symbols = enclosing_symbols(&tree, &source, idx, language);
+            if !options.in_kind.is_empty() && !symbols.last().is_some_and(|(_, kind)| options.in_kind.contains(kind)) {
+                continue;
+            }
+            let symbol_path = symbols.into_iter().map(|(name, _)| name).collect();
+            let next_symbol = if options.next_symbol {
+                find_next_top_level_symbol(&tree, &source, idx, language)
+            } else {
+                None
+            };
+            let context = |range: std::ops::Range<usize>| -> Vec<ContextLine> {
+                range
+                    .map(|i| ContextLine { line_number: i + 1, line_content: lines[i].to_string() })
+                    .collect()
+            };
             matches.push(SearchMatch {
                 line_number,
                 line_content: line.to_string(),
                 symbol_path,
+                blame: None,
+                next_symbol,
+                context_before: context(idx.saturating_sub(options.before)..idx),
+                context_after: context(idx + 1..(idx + 1 + options.after).min(lines.len())),
             });
         }
     }
@@ -138,6 +246,17 @@ fn search_file(
     Ok(matches)
 }
 
+/// Attach `git blame` info to every match, batching one `git` call per file.
+fn annotate_blame(file_results: &mut [(String, Vec<SearchMatch>)]) {
+    for (file_path, matches) in file_results.iter_mut() {
+        let lines: Vec<usize> = matches.iter().map(|m| m.line_number).collect();
+        let blame = git::blame_lines(Path::new(file_path), &lines);
+        for m in matches.iter_mut() {
+            m.blame = blame.get(&m.line_number).cloned();
+        }
+    }
+}
+
 /// Find the enclosing symbol hierarchy for a given line (0-indexed).
 pub fn find_enclosing_symbols(
     tree: &Tree,
@@ -145,50 +264,123 @@ pub fn find_enclosing_symbols(
     line_idx: usize,
     language: Language,
 ) -> Vec<String> {
-    let root = tree.root_node();
+    enclosing_symbols(tree, source, line_idx, language).into_iter().map(|(name, _)| name).collect()
+}
+
+/// Enclosing symbols for a line (0-indexed), outermost first, with their kinds.
+fn enclosing_symbols(tree: &Tree, source: &str, line_idx: usize, language: Language) -> Vec<(String, ItemKind)> {
     let mut symbols = Vec::new();
-    find_symbols_at_line(root, source, line_idx, language, &mut symbols);
+    find_symbols_at_line(tree.root_node(), source, line_idx, language, &mut symbols);
     symbols
 }
 
-/// Recursively find named symbols that contain the given line.
+/// Enclosing symbol hierarchy for every line of `source` (index = 0-indexed line),
+/// computed in a single tree walk. Entry `i` equals `find_enclosing_symbols(.., i, ..)`.
+pub fn enclosing_symbols_by_line(tree: &Tree, source: &str, language: Language) -> Vec<Vec<String>> {
+    let mut by_line = vec![Vec::new(); source.lines().count()];
+    collect_symbols_by_line(tree.root_node(), source, language, &mut by_line);
+    by_line
+}
+
+/// Pre-order walk, so outer symbols are pushed onto each line before inner ones.
+fn collect_symbols_by_line(node: Node, source: &str, language: Language, by_line: &mut [Vec<String>]) {
+    if let Some((name, _)) = extract_symbol_name(node, source, language) {
+        let last = node.end_position().row.min(by_line.len().saturating_sub(1));
+        for line in by_line.iter_mut().take(last + 1).skip(node.start_position().row) {
+            line.push(name.clone());
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_symbols_by_line(child, source, language, by_line);
+    }
+}
+
+/// Find named symbols that contain the given line, outermost first.
+///
+/// Walks only the nodes spanning the line, in pre-order, with an explicit stack
+/// so deeply nested files can't exhaust the call stack.
 fn find_symbols_at_line(
-    node: Node,
+    root: Node,
     source: &str,
     line_idx: usize,
     language: Language,
-    symbols: &mut Vec<String>,
+    symbols: &mut Vec<(String, ItemKind)>,
 ) {
-    let start_line = node.start_position().row;
-    let end_line = node.end_position().row;
-
-    if line_idx < start_line || line_idx > end_line {
+    if line_idx < root.start_position().row || line_idx > root.end_position().row {
         return;
     }
 
-    // Check if this node is a named symbol
-    if let Some(name) = extract_symbol_name(node, source, language) {
-        symbols.push(name);
+    let mut stack = vec![root];
+    let mut spanning = Vec::new();
+    while let Some(node) = stack.pop() {
+        if let Some(symbol) = extract_symbol_name(node, source, language) {
+            symbols.push(symbol);
+        }
+
+        // Children are in source order: skip those ending before the line and
+        // stop at the first one starting after it
+        let mut cursor = node.walk();
+        spanning.extend(
+            node.children(&mut cursor)
+                .skip_while(|child| child.end_position().row < line_idx)
+                .take_while(|child| child.start_position().row <= line_idx),
+        );
+        stack.extend(spanning.drain(..).rev());
     }
+}
 
-    // Recurse into children
-    let mut cursor = node.walk();
-    for child in node.children(&mut cursor) {
-        find_symbols_at_line(child, source, line_idx, language, symbols);
+/// Name of the first named top-level item that starts after the top-level item
+/// containing `line_idx` (0-indexed), or after the line itself when it is not
+/// inside any item.
+fn find_next_top_level_symbol(tree: &Tree, source: &str, line_idx: usize, language: Language) -> Option<String> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let mut after_line = line_idx;
+    for child in root.named_children(&mut cursor) {
+        let (start, end) = (child.start_position().row, child.end_position().row);
+        if start <= line_idx && end >= line_idx {
+            after_line = end;
+        } else if start > after_line {
+            if let Some(name) = top_level_symbol_name(child, source, language) {
+                return Some(name);
+            }
+        }
     }
+    None
+}
+
+/// Symbol name of a top-level node, looking through `export`/decorator wrappers.
+fn top_level_symbol_name(node: Node, source: &str, language: Language) -> Option<String> {
+    extract_symbol_name(node, source, language)
+        .or_else(|| {
+            let inner = node
+                .child_by_field_name("declaration")
+                .or_else(|| node.child_by_field_name("definition"))?;
+            extract_symbol_name(inner, source, language)
+        })
+        .map(|(name, _)| name)
 }
 
-/// Extract a symbol name from a node if it represents a named symbol.
-fn extract_symbol_name(node: Node, source: &str, language: Language) -> Option<String> {
+/// Extract a symbol name and kind from a node if it represents a named symbol.
+fn extract_symbol_name(node: Node, source: &str, language: Language) -> Option<(String, ItemKind)> {
     let kind = node.kind();
 
     match language {
         Language::Rust => match kind {
-            "function_item" | "const_item" | "static_item" | "mod_item" | "macro_definition" => {
-                get_child_by_field(node, "name", source)
+            "function_item" => {
+                let in_block = node
+                    .parent()
+                    .filter(|p| p.kind() == "declaration_list")
+                    .and_then(|p| p.parent())
+                    .is_some_and(|p| matches!(p.kind(), "impl_item" | "trait_item"));
+                let item_kind = if in_block { ItemKind::Method } else { ItemKind::Function };
+                get_child_by_field(node, "name", source).map(|n| (n, item_kind))
             }
-            "struct_item" | "enum_item" | "trait_item" | "type_item" => {
-                get_child_by_field(node, "name", source)
+            "const_item" | "static_item" | "mod_item" | "macro_definition" | "struct_item" | "enum_item"
+            | "trait_item" | "type_item" => {
+                let item_kind = ItemKind::from_node_kind(kind)?;
+                get_child_by_field(node, "name", source).map(|n| (n, item_kind))
             }
             "impl_item" => {
                 // Get "impl Type" or "impl Trait for Type"
@@ -205,7 +397,7 @@ fn extract_symbol_name(node: Node, source: &str, language: Language) -> Option<S
                 if name_parts.is_empty() {
                     None
                 } else {
-                    Some(format!("impl {}", name_parts.last().unwrap()))
+                    Some((format!("impl {}", name_parts.last().unwrap()), ItemKind::Impl))
                 }
             }
             _ => None,
@@ -213,24 +405,24 @@ fn extract_symbol_name(node: Node, source: &str, language: Language) -> Option<S
         Language::TypeScript | Language::Tsx => match kind {
             "function_declaration" | "method_definition" | "public_field_definition" => {
                 get_child_by_field(node, "name", source)
-                    .map(|n| if kind == "method_definition" || kind == "function_declaration" {
-                        format!("{}()", n)
-                    } else {
-                        n
+                    .map(|n| match kind {
+                        "function_declaration" => (format!("{}()", n), ItemKind::Function),
+                        "method_definition" => (format!("{}()", n), ItemKind::Method),
+                        _ => (n, ItemKind::Field),
                     })
             }
             "class_declaration" | "abstract_class_declaration" => {
-                get_child_by_field(node, "name", source)
+                get_child_by_field(node, "name", source).map(|n| (n, ItemKind::Class))
             }
             "interface_declaration" => {
-                get_child_by_field(node, "name", source)
+                get_child_by_field(node, "name", source).map(|n| (n, ItemKind::Trait))
             }
             "lexical_declaration" => {
                 // const/let declarations
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "variable_declarator" {
-                        return get_child_by_field(child, "name", source);
+                        return get_child_by_field(child, "name", source).map(|n| (n, ItemKind::Const));
                     }
                 }
                 None
@@ -239,17 +431,18 @@ fn extract_symbol_name(node: Node, source: &str, language: Language) -> Option<S
         },
         Language::JavaScript | Language::Jsx => match kind {
             "function_declaration" | "method_definition" => {
+                let item_kind = if kind == "method_definition" { ItemKind::Method } else { ItemKind::Function };
                 get_child_by_field(node, "name", source)
-                    .map(|n| format!("{}()", n))
+                    .map(|n| (format!("{}()", n), item_kind))
             }
             "class_declaration" => {
-                get_child_by_field(node, "name", source)
+                get_child_by_field(node, "name", source).map(|n| (n, ItemKind::Class))
             }
             "lexical_declaration" | "variable_declaration" => {
                 let mut cursor = node.walk();
                 for child in node.children(&mut cursor) {
                     if child.kind() == "variable_declarator" {
-                        return get_child_by_field(child, "name", source);
+                        return get_child_by_field(child, "name", source).map(|n| (n, ItemKind::Const));
                     }
                 }
                 None
@@ -258,11 +451,21 @@ fn extract_symbol_name(node: Node, source: &str, language: Language) -> Option<S
         },
         Language::Python => match kind {
             "function_definition" => {
+                // A def directly in a class body (possibly decorated) is a method
+                let mut parent = node.parent();
+                if parent.is_some_and(|p| p.kind() == "decorated_definition") {
+                    parent = parent.and_then(|p| p.parent());
+                }
+                let in_class = parent
+                    .filter(|p| p.kind() == "block")
+                    .and_then(|p| p.parent())
+                    .is_some_and(|p| p.kind() == "class_definition");
+                let item_kind = if in_class { ItemKind::Method } else { ItemKind::Function };
                 get_child_by_field(node, "name", source)
-                    .map(|n| format!("{}()", n))
+                    .map(|n| (format!("{}()", n), item_kind))
             }
             "class_definition" => {
-                get_child_by_field(node, "name", source)
+                get_child_by_field(node, "name", source).map(|n| (n, ItemKind::Class))
             }
             _ => None,
         },
@@ -305,8 +508,32 @@ fn format_search_results(file_results: &[(String, Vec<SearchMatch>)]) -> String
             let group = &groups[key];
             writeln!(output).unwrap();
             writeln!(output, "  {}", key).unwrap();
+            // Context is printed once per line: lines already shown, and match
+            // lines (printed as matches), are skipped
+            let match_lines: Vec<usize> = group.iter().map(|m| m.line_number).collect();
+            let mut last_printed = 0;
             for m in group {
-                writeln!(output, "    L{}:{}", m.line_number, m.line_content).unwrap();
+                let has_context = !m.context_before.is_empty() || !m.context_after.is_empty();
+                let first = m.context_before.iter().map(|c| c.line_number).find(|&n| n > last_printed).unwrap_or(m.line_number);
+                if has_context && last_printed > 0 && first > last_printed + 1 {
+                    writeln!(output, "    --").unwrap();
+                }
+                for c in m.context_before.iter().filter(|c| c.line_number > last_printed) {
+                    writeln!(output, "    L{}-{}", c.line_number, c.line_content).unwrap();
+                }
+                write!(output, "    L{}:{}", m.line_number, m.line_content).unwrap();
+                if let Some(b) = &m.blame {
+                    write!(output, "  ({}, {})", b.author, b.short_sha).unwrap();
+                }
+                if let Some(next) = &m.next_symbol {
+                    write!(output, "  (next: {})", next).unwrap();
+                }
+                output.push('\n');
+                last_printed = m.line_number;
+                for c in m.context_after.iter().take_while(|c| !match_lines.contains(&c.line_number)) {
+                    writeln!(output, "    L{}-{}", c.line_number, c.line_content).unwrap();
+                    last_printed = c.line_number;
+                }
             }
         }
     }
@@ -314,6 +541,141 @@ fn format_search_results(file_results: &[(String, Vec<SearchMatch>)]) -> String
     output
 }
 
+/// Format search results as `{ "results": [ { "path", "matches": [...] } ] }`.
+/// `omitted` is the (matches, files) count cut by `max_results`, if any.
+fn format_search_json(file_results: &[(String, Vec<SearchMatch>)], omitted: Option<(usize, usize)>) -> Result<String, CodeviewError> {
+    #[derive(Serialize)]
+    struct SearchOutput<'a> {
+        results: Vec<FileMatches<'a>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        omitted_matches: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        omitted_files: Option<usize>,
+    }
+
+    #[derive(Serialize)]
+    struct FileMatches<'a> {
+        path: &'a str,
+        matches: &'a [SearchMatch],
+    }
+
+    let output = SearchOutput {
+        results: file_results
+            .iter()
+            .map(|(path, matches)| FileMatches { path, matches })
+            .collect(),
+        omitted_matches: omitted.map(|(m, _)| m),
+        omitted_files: omitted.map(|(_, f)| f),
+    };
+    let mut json = serde_json::to_string_pretty(&output)?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Format one `path: N` line per file and a final total, or the same counts as JSON.
+fn format_search_counts(file_results: &[(String, Vec<SearchMatch>)], json: bool) -> Result<String, CodeviewError> {
+    let total: usize = file_results.iter().map(|(_, matches)| matches.len()).sum();
+
+    if json {
+        #[derive(Serialize)]
+        struct CountOutput<'a> {
+            results: Vec<FileCount<'a>>,
+            total: usize,
+        }
+
+        #[derive(Serialize)]
+        struct FileCount<'a> {
+            path: &'a str,
+            count: usize,
+        }
+
+        let output = CountOutput {
+            results: file_results
+                .iter()
+                .map(|(path, matches)| FileCount { path, count: matches.len() })
+                .collect(),
+            total,
+        };
+        let mut json = serde_json::to_string_pretty(&output)?;
+        json.push('\n');
+        return Ok(json);
+    }
+
+    let mut output = String::new();
+    for (path, matches) in file_results {
+        writeln!(output, "{}: {}", path, matches.len()).unwrap();
+    }
+    writeln!(output, "total: {} matches in {} files", total, file_results.len()).unwrap();
+    Ok(output)
+}
+
+/// Format search results as whole enclosing symbols (via expand extraction),
+/// marking matched lines with `>`. Matches outside any expandable symbol are
+/// listed as plain lines.
+fn format_expanded_matches(file_results: &[(String, Vec<SearchMatch>)], lossy: bool) -> Result<String, CodeviewError> {
+    let mut output = String::new();
+
+    for (i, (file_path, matches)) in file_results.iter().enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+        writeln!(output, "{}", file_path).unwrap();
+
+        let path = Path::new(file_path);
+        let source = read_source(path, lossy)?;
+        let language = languages::detect_language(path)?;
+        let tree = parser::parse(&source, language)?;
+
+        // (symbol label, start, end, matched lines) in first-match order
+        let mut symbols: Vec<(String, usize, usize, Vec<usize>)> = Vec::new();
+        let mut loose: Vec<&SearchMatch> = Vec::new();
+
+        for m in matches {
+            let Some(innermost) = m.symbol_path.last() else {
+                loose.push(m);
+                continue;
+            };
+            let name = innermost.trim_end_matches("()");
+            let name = name.strip_prefix("impl ").unwrap_or(name);
+            let enclosing = expand::extract(&source, &tree, &[name.to_string()], language)
+                .into_iter()
+                .filter(|item| item.line_start <= m.line_number && m.line_number <= item.line_end)
+                .min_by_key(|item| item.line_end - item.line_start);
+            match enclosing {
+                Some(item) => {
+                    match symbols.iter_mut().find(|s| s.1 == item.line_start && s.2 == item.line_end) {
+                        Some(s) => s.3.push(m.line_number),
+                        None => symbols.push((m.symbol_path.join(" > "), item.line_start, item.line_end, vec![m.line_number])),
+                    }
+                }
+                None => loose.push(m),
+            }
+        }
+
+        let lines: Vec<&str> = source.lines().collect();
+        for (label, start, end, matched) in &symbols {
+            writeln!(output).unwrap();
+            writeln!(output, "  {} [{}:{}]", label, start, end).unwrap();
+            let width = end.to_string().len();
+            for line_num in *start..=*end {
+                let marker = if matched.contains(&line_num) { '>' } else { ' ' };
+                let text = lines.get(line_num - 1).copied().unwrap_or("");
+                writeln!(output, "  {} {:>width$} | {}", marker, line_num, text, width = width).unwrap();
+            }
+        }
+
+        if !loose.is_empty() {
+            writeln!(output).unwrap();
+            writeln!(output, "  (top-level)").unwrap();
+            for m in loose {
+                writeln!(output, "    L{}:{}", m.line_number, m.line_content).unwrap();
+            }
+        }
+    }
+
+    Ok(output)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,6 +694,38 @@ mod tests {
         path.to_string_lossy().to_string()
     }
 
+    #[test]
+    fn enclosing_symbols_in_deeply_nested_file() {
+        let depth = 2000;
+        let mut source = String::new();
+        for i in 0..depth {
+            source.push_str(&format!("mod m{} {{\n", i));
+        }
+        source.push_str("fn leaf() {\n    todo!()\n}\n");
+        source.push_str(&"}\n".repeat(depth));
+        let tree = crate::parser::parse(&source, Language::Rust).unwrap();
+
+        // Run on a small stack: a recursive walk would need one frame per level
+        let symbols = std::thread::scope(|s| {
+            std::thread::Builder::new()
+                .stack_size(256 * 1024)
+                .spawn_scoped(s, || find_enclosing_symbols(&tree, &source, depth + 1, Language::Rust))
+                .unwrap()
+                .join()
+                .unwrap()
+        });
+        let mut expected: Vec<String> = (0..depth).map(|i| format!("m{}", i)).collect();
+        expected.push("leaf".to_string());
+        assert_eq!(symbols, expected);
+
+        // Same paths as the single-walk lookup, including siblings sharing a line
+        let source = "mod a {\n    struct S; fn f() {}\n    impl S {\n        fn m() { let x = 1; }\n    }\n}\nfn g() {}\n";
+        let tree = crate::parser::parse(source, Language::Rust).unwrap();
+        for (line, path) in enclosing_symbols_by_line(&tree, source, Language::Rust).into_iter().enumerate() {
+            assert_eq!(find_enclosing_symbols(&tree, source, line, Language::Rust), path, "line {}", line);
+        }
+    }
+
     #[test]
     fn test_basic_search_rust() {
         let dir = TempDir::new().unwrap();
@@ -349,6 +743,7 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(result.contains("hello"));
@@ -371,6 +766,7 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(!result.contains("Message"));
@@ -382,11 +778,50 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(result.contains("Message"));
     }
 
+    #[test]
+    fn test_whole_word() {
+        let dir = TempDir::new().unwrap();
+        let path = write_rs_file(&dir, "test.rs", r#"fn login(user: &str) {
+    let userService = 1;
+    let current_user = 2;
+    check(user);
+}
+"#);
+        let opts = SearchOptions {
+            pattern: "user|nobody".to_string(),
+            whole_word: true,
+            ..Default::default()
+        };
+        let result = search_path(&path, &opts).unwrap();
+        assert!(result.contains("L1:") && result.contains("L4:"), "got:\n{result}");
+        assert!(!result.contains("userService") && !result.contains("current_user"), "got:\n{result}");
+    }
+
+    #[test]
+    fn test_invert_match() {
+        let dir = TempDir::new().unwrap();
+        let path = write_rs_file(&dir, "test.rs", r#"fn run() {
+    // documented
+    step();
+}
+"#);
+        let opts = SearchOptions {
+            pattern: r"^\s*//".to_string(),
+            invert: true,
+            ..Default::default()
+        };
+        let result = search_path(&path, &opts).unwrap();
+        assert!(result.contains("run"), "grouped by enclosing symbol:\n{result}");
+        assert!(result.contains("L1:") && result.contains("L3:") && result.contains("L4:"), "got:\n{result}");
+        assert!(!result.contains("L2:"), "got:\n{result}");
+    }
+
     #[test]
     fn test_regex_pattern() {
         let dir = TempDir::new().unwrap();
@@ -402,6 +837,7 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(result.contains("L2:"));
@@ -421,8 +857,9 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
-        let result = search_path(&dir.path().to_string_lossy().as_ref(), &opts).unwrap();
+        let result = search_path(dir.path().to_string_lossy().as_ref(), &opts).unwrap();
         assert!(result.contains("a.rs"));
         assert!(!result.contains("b.rs"));
     }
@@ -437,6 +874,7 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(result.is_empty());
@@ -452,6 +890,7 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(result.contains("(top-level)"));
@@ -476,6 +915,7 @@ fn goodbye() {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(result.contains("MyClass"));
@@ -500,6 +940,7 @@ impl Foo {
             depth: None,
             ext: vec![],
             max_results: None,
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(result.contains("impl Foo"));
@@ -519,8 +960,9 @@ impl Foo {
             depth: None,
             ext: vec![],
             max_results: Some(3),
+            ..Default::default()
         };
-        let result = search_path(&dir.path().to_string_lossy().as_ref(), &opts).unwrap();
+        let result = search_path(dir.path().to_string_lossy().as_ref(), &opts).unwrap();
         // Should contain the summary line
         assert!(result.contains("... and 3 more matches across"));
     }
@@ -535,6 +977,7 @@ impl Foo {
             depth: None,
             ext: vec![],
             max_results: Some(10),
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(!result.contains("... and"));
@@ -555,6 +998,7 @@ impl Foo {
             depth: None,
             ext: vec![],
             max_results: None, // single-file default: no cap
+            ..Default::default()
         };
         let result = search_path(&path, &opts).unwrap();
         assert!(!result.contains("... and"));
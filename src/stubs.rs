@@ -0,0 +1,141 @@
+//! Unfinished-function detection.
+//!
+//! A stub is a function or method whose body is empty or holds nothing but a
+//! "not implemented" marker: `todo!()`/`unimplemented!()` in Rust, `pass`,
+//! `...` or `raise NotImplementedError` in Python, and a thrown
+//! "not implemented" error in TypeScript/JavaScript. Comments and Python
+//! docstrings do not count as body statements.
+
+use crate::error::CodeviewError;
+use crate::languages::Language;
+use crate::xref::parse_files;
+use std::fmt::Write;
+use std::path::Path;
+use tree_sitter::Node;
+
+/// Options for stub scans.
+#[derive(Default)]
+pub struct StubOptions {
+    pub depth: Option<usize>,
+    pub ext: Vec<String>,
+}
+
+/// A function or method without a real body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stub {
+    pub path: String,
+    pub name: String,
+    pub line_start: usize,
+    /// The marker statement on one line, or `None` for an empty body.
+    pub marker: Option<String>,
+}
+
+/// Report stub functions and methods under `path`, in file and source order.
+pub fn find_stubs(path: &str, options: &StubOptions) -> Result<Vec<Stub>, CodeviewError> {
+    let files = parse_files(Path::new(path), options.depth, &options.ext)?;
+
+    let mut stubs = Vec::new();
+    for file in &files {
+        collect_stubs(file.tree.root_node(), &file.source, file.language, &file.path, &mut stubs);
+    }
+    Ok(stubs)
+}
+
+/// Format stubs grouped by file, one line per function.
+pub fn format_stubs(stubs: &[Stub]) -> String {
+    let mut output = String::new();
+    let mut current_path: Option<&str> = None;
+
+    for stub in stubs {
+        if current_path != Some(stub.path.as_str()) {
+            writeln!(output, "{}", stub.path).unwrap();
+            current_path = Some(stub.path.as_str());
+        }
+        let marker = stub.marker.as_deref().unwrap_or("(empty)");
+        writeln!(output, "  fn {:<30} L{}  {}", stub.name, stub.line_start, marker).unwrap();
+    }
+
+    output
+}
+
+fn collect_stubs(node: Node, source: &str, language: Language, path: &str, stubs: &mut Vec<Stub>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        // Only block bodies can be stubs: `() => 1` has an expression body
+        let body = child.child_by_field_name("body").filter(|b| matches!(b.kind(), "block" | "statement_block"));
+        if let (Some(name), Some(body)) = (function_name(child, source), body) {
+            if let Some(marker) = stub_marker(body, source, language) {
+                stubs.push(Stub {
+                    path: path.to_string(),
+                    name,
+                    line_start: child.start_position().row + 1,
+                    marker,
+                });
+            }
+        }
+        collect_stubs(child, source, language, path, stubs);
+    }
+}
+
+/// The name of a function-like node. Anonymous functions count only when
+/// bound to a variable, so inline callbacks like `() => {}` are not reported.
+fn function_name(node: Node, source: &str) -> Option<String> {
+    let named = match node.kind() {
+        "function_item" | "function_definition" | "function_declaration" | "generator_function_declaration"
+        | "method_definition" => node,
+        "arrow_function" | "function_expression" | "function" => {
+            node.parent().filter(|p| p.kind() == "variable_declarator")?
+        }
+        _ => return None,
+    };
+    named.child_by_field_name("name").map(|n| source[n.byte_range()].to_string())
+}
+
+/// `Some(None)` for an empty body, `Some(Some(marker))` for a body holding only
+/// a not-implemented marker, `None` for a real body.
+fn stub_marker(body: Node, source: &str, language: Language) -> Option<Option<String>> {
+    let mut cursor = body.walk();
+    let mut statements: Vec<Node> = body
+        .named_children(&mut cursor)
+        .filter(|c| !c.kind().contains("comment"))
+        .collect();
+    if language == Language::Python && statements.first().is_some_and(|s| is_docstring(*s)) {
+        statements.remove(0);
+    }
+
+    let statement = match statements.as_slice() {
+        [] => return Some(None),
+        [only] => *only,
+        _ => return None,
+    };
+    let is_marker = match language {
+        Language::Rust => {
+            let call = if statement.kind() == "expression_statement" { statement.named_child(0)? } else { statement };
+            call.kind() == "macro_invocation"
+                && call
+                    .child_by_field_name("macro")
+                    .is_some_and(|m| matches!(&source[m.byte_range()], "todo" | "unimplemented"))
+        }
+        Language::Python => match statement.kind() {
+            "pass_statement" => true,
+            "expression_statement" => statement.named_child(0).is_some_and(|e| e.kind() == "ellipsis"),
+            "raise_statement" => statement
+                .named_child(0)
+                .is_some_and(|e| source[e.byte_range()].starts_with("NotImplementedError")),
+            _ => false,
+        },
+        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
+            let text = source[statement.byte_range()].to_lowercase();
+            statement.kind() == "throw_statement" && (text.contains("not implemented") || text.contains("notimplemented"))
+        }
+    };
+
+    let marker = source[statement.byte_range()].split_whitespace().collect::<Vec<_>>().join(" ");
+    is_marker.then_some(Some(marker))
+}
+
+fn is_docstring(statement: Node) -> bool {
+    statement.kind() == "expression_statement"
+        && statement.named_child_count() == 1
+        && statement.named_child(0).is_some_and(|e| e.kind() == "string")
+}
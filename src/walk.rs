@@ -1,11 +1,128 @@
 use crate::error::CodeviewError;
-use crate::languages;
+use crate::languages::{self, Language};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use ignore::WalkBuilder;
 use std::path::{Path, PathBuf};
 
+/// `--ext` filter: bare extensions (`rs`) match exactly, entries containing glob
+/// metacharacters (`*.spec.ts`) match against the file name.
+struct ExtFilter {
+    exts: Vec<String>,
+    globs: GlobSet,
+}
+
+impl ExtFilter {
+    fn new(ext_filter: &[String]) -> Result<Self, CodeviewError> {
+        let mut exts = Vec::new();
+        let mut builder = GlobSetBuilder::new();
+        for entry in ext_filter {
+            if entry.contains(['*', '?', '[', '{']) {
+                let glob = GlobBuilder::new(entry)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| CodeviewError::ParseError(format!("Invalid --ext pattern '{}': {}", entry, e)))?;
+                builder.add(glob);
+            } else {
+                exts.push(entry.clone());
+            }
+        }
+        let globs = builder
+            .build()
+            .map_err(|e| CodeviewError::ParseError(e.to_string()))?;
+        Ok(ExtFilter { exts, globs })
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let ext_match = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.exts.iter().any(|f| f == ext));
+        ext_match || path.file_name().is_some_and(|name| self.globs.is_match(name))
+    }
+}
+
+/// `--dir-lang` mappings: files whose path relative to the walk root matches a
+/// glob are parsed as its language, whatever their extension. Later mappings win.
+#[derive(Default)]
+pub struct LanguageOverrides {
+    globs: GlobSet,
+    languages: Vec<Language>,
+}
+
+impl LanguageOverrides {
+    pub fn new(mappings: &[(String, Language)]) -> Result<Self, CodeviewError> {
+        let mut builder = GlobSetBuilder::new();
+        for (pattern, _) in mappings {
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| CodeviewError::ParseError(format!("Invalid --dir-lang pattern '{}': {}", pattern, e)))?;
+            builder.add(glob);
+        }
+        let globs = builder
+            .build()
+            .map_err(|e| CodeviewError::ParseError(e.to_string()))?;
+        Ok(LanguageOverrides { globs, languages: mappings.iter().map(|(_, l)| *l).collect() })
+    }
+
+    /// The language forced for `rel_path` (relative to the walk root), if any.
+    pub fn language_for(&self, rel_path: &Path) -> Option<Language> {
+        self.globs.matches(rel_path).last().map(|&i| self.languages[i])
+    }
+}
+
+/// `--glob`/`--exclude` filters on paths relative to the walk root. With any
+/// include globs, a file must match one of them; a file matching an exclude
+/// glob is always skipped.
+#[derive(Default)]
+pub struct PathGlobs {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl PathGlobs {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, CodeviewError> {
+        Ok(PathGlobs {
+            include: build_path_globs(include, "--glob")?,
+            exclude: build_path_globs(exclude, "--exclude")?,
+        })
+    }
+
+    /// Whether `rel_path` (relative to the walk root) passes the filters.
+    pub fn allows(&self, rel_path: &Path) -> bool {
+        (self.include.is_empty() || self.include.is_match(rel_path)) && !self.exclude.is_match(rel_path)
+    }
+}
+
+fn build_path_globs(patterns: &[String], flag: &str) -> Result<GlobSet, CodeviewError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| CodeviewError::ParseError(format!("Invalid {} pattern '{}': {}", flag, pattern, e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| CodeviewError::ParseError(e.to_string()))
+}
+
 /// Walk a directory and collect all supported source files.
 /// Respects .gitignore, .ignore, and global gitignore rules.
 pub fn walk_directory(path: &Path, max_depth: Option<usize>, ext_filter: &[String]) -> Result<Vec<PathBuf>, CodeviewError> {
+    walk_directory_with(path, max_depth, ext_filter, &LanguageOverrides::default(), &PathGlobs::default())
+}
+
+/// Like [`walk_directory`], also collecting files of any extension that `overrides` maps to a
+/// language, and keeping only files that `globs` allows.
+pub fn walk_directory_with(
+    path: &Path,
+    max_depth: Option<usize>,
+    ext_filter: &[String],
+    overrides: &LanguageOverrides,
+    globs: &PathGlobs,
+) -> Result<Vec<PathBuf>, CodeviewError> {
     // Verify path exists and is readable before walking
     if !path.is_dir() {
         return Err(CodeviewError::ReadError {
@@ -30,6 +147,8 @@ pub fn walk_directory(path: &Path, max_depth: Option<usize>, ext_filter: &[Strin
         builder.max_depth(Some(d + 1));
     }
 
+    let filter = ExtFilter::new(ext_filter)?;
+
     let mut files = Vec::new();
     for entry in builder.build() {
         let entry = entry.map_err(|e| CodeviewError::ReadError {
@@ -38,15 +157,13 @@ pub fn walk_directory(path: &Path, max_depth: Option<usize>, ext_filter: &[Strin
         })?;
 
         let entry_path = entry.path();
-        if entry_path.is_file() && languages::is_supported_file(entry_path) {
-            if !ext_filter.is_empty() {
-                if let Some(ext) = entry_path.extension().and_then(|e| e.to_str()) {
-                    if !ext_filter.iter().any(|f| f == ext) {
-                        continue;
-                    }
-                } else {
-                    continue;
-                }
+        let mapped = || entry_path.strip_prefix(path).is_ok_and(|rel| overrides.language_for(rel).is_some());
+        if entry_path.is_file() && (languages::is_supported_file(entry_path) || mapped()) {
+            if !ext_filter.is_empty() && !filter.matches(entry_path) {
+                continue;
+            }
+            if !entry_path.strip_prefix(path).is_ok_and(|rel| globs.allows(rel)) {
+                continue;
             }
             files.push(entry_path.to_path_buf());
         }
@@ -171,6 +288,75 @@ mod tests {
         assert_eq!(files.len(), 2);
     }
 
+    #[test]
+    fn walk_ext_filter_glob_selects_spec_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("app.ts"), "export {}").unwrap();
+        fs::write(dir.path().join("app.spec.ts"), "export {}").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/util.spec.ts"), "export {}").unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+        let exts = vec!["*.spec.ts".to_string()];
+        let files = walk_directory(dir.path(), None, &exts).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|f| f.to_string_lossy().ends_with(".spec.ts")));
+    }
+
+    #[test]
+    fn walk_ext_filter_mixes_globs_and_extensions() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("app.spec.ts"), "export {}").unwrap();
+        fs::write(dir.path().join("app.ts"), "export {}").unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+        let exts = vec!["*.spec.ts".to_string(), "rs".to_string()];
+        let files = walk_directory(dir.path(), None, &exts).unwrap();
+        assert_eq!(files.len(), 2);
+    }
+
+    #[test]
+    fn walk_ext_filter_invalid_glob_is_error() {
+        let dir = TempDir::new().unwrap();
+        let exts = vec!["*.{ts".to_string()];
+        assert!(walk_directory(dir.path(), None, &exts).is_err());
+    }
+
+    #[test]
+    fn walk_includes_files_mapped_by_dir_lang() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("scripts")).unwrap();
+        fs::write(dir.path().join("scripts/deploy"), "def run():\n    pass\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "hi").unwrap();
+        let overrides = LanguageOverrides::new(&[("scripts/*".to_string(), Language::Python)]).unwrap();
+        let files = walk_directory_with(dir.path(), None, &[], &overrides, &PathGlobs::default()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(overrides.language_for(Path::new("scripts/deploy")), Some(Language::Python));
+        assert_eq!(overrides.language_for(Path::new("notes.txt")), None);
+    }
+
+    #[test]
+    fn walk_include_and_exclude_globs() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src/generated")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        fs::write(dir.path().join("src/generated/api.rs"), "").unwrap();
+        fs::write(dir.path().join("src/app.ts"), "").unwrap();
+        fs::write(dir.path().join("build.rs"), "").unwrap();
+        let walk = |include: &[&str], exclude: &[&str]| -> Vec<String> {
+            let to_vec = |globs: &[&str]| globs.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+            let globs = PathGlobs::new(&to_vec(include), &to_vec(exclude)).unwrap();
+            walk_directory_with(dir.path(), None, &[], &LanguageOverrides::default(), &globs)
+                .unwrap()
+                .iter()
+                .map(|f| f.strip_prefix(dir.path()).unwrap().to_string_lossy().replace('\\', "/"))
+                .collect()
+        };
+
+        assert_eq!(walk(&["src/**/*.rs"], &[]), ["src/generated/api.rs", "src/lib.rs"]);
+        assert_eq!(walk(&[], &["**/generated/**"]), ["build.rs", "src/app.ts", "src/lib.rs"]);
+        assert_eq!(walk(&["src/**/*.rs"], &["**/generated/**"]), ["src/lib.rs"]);
+        assert!(PathGlobs::new(&["src/[".to_string()], &[]).is_err());
+    }
+
     #[test]
     fn walk_ext_filter_empty_means_all() {
         let dir = TempDir::new().unwrap();
@@ -0,0 +1,107 @@
+//! Polling file watcher for `--watch`.
+//!
+//! Tracks modification time and size of every supported file under a path
+//! (honoring the same depth/extension filters as directory mode) and reports
+//! when the set changes.
+
+use crate::error::CodeviewError;
+use crate::walk;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+/// Watches a file or directory for changes by polling.
+pub struct Watcher {
+    path: PathBuf,
+    depth: Option<usize>,
+    ext: Vec<String>,
+    last: Snapshot,
+}
+
+impl Watcher {
+    /// Start watching `path`, taking the current state as the baseline.
+    pub fn new(path: &str, depth: Option<usize>, ext: &[String]) -> Result<Self, CodeviewError> {
+        let path = PathBuf::from(path);
+        if !path.exists() {
+            return Err(CodeviewError::PathNotFound(path.display().to_string()));
+        }
+        let mut watcher = Watcher {
+            path,
+            depth,
+            ext: ext.to_vec(),
+            last: Snapshot::new(),
+        };
+        watcher.last = watcher.snapshot()?;
+        Ok(watcher)
+    }
+
+    /// Check once for added, removed, or modified files since the last check.
+    pub fn poll_changed(&mut self) -> Result<bool, CodeviewError> {
+        let current = self.snapshot()?;
+        let changed = current != self.last;
+        self.last = current;
+        Ok(changed)
+    }
+
+    /// Block until a change is seen and no further changes arrive within one
+    /// `interval` (so a burst of saves triggers a single refresh).
+    pub fn wait_for_change(&mut self, interval: Duration) -> Result<(), CodeviewError> {
+        while !self.poll_changed()? {
+            thread::sleep(interval);
+        }
+        loop {
+            thread::sleep(interval);
+            if !self.poll_changed()? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Result<Snapshot, CodeviewError> {
+        let files = if self.path.is_dir() {
+            walk::walk_directory(&self.path, self.depth, &self.ext)?
+        } else {
+            vec![self.path.clone()]
+        };
+        Ok(files
+            .into_iter()
+            .filter_map(|f| {
+                let meta = fs::metadata(&f).ok()?;
+                Some((f, (meta.modified().ok()?, meta.len())))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detects_new_and_modified_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        let mut watcher = Watcher::new(dir.path().to_str().unwrap(), None, &[]).unwrap();
+        assert!(!watcher.poll_changed().unwrap());
+
+        fs::write(dir.path().join("b.rs"), "fn b() {}").unwrap();
+        assert!(watcher.poll_changed().unwrap());
+        assert!(!watcher.poll_changed().unwrap());
+
+        fs::write(dir.path().join("a.rs"), "fn a() { 1 }").unwrap();
+        assert!(watcher.poll_changed().unwrap());
+    }
+
+    #[test]
+    fn ignores_filtered_out_files() {
+        let dir = TempDir::new().unwrap();
+        let mut watcher = Watcher::new(dir.path().to_str().unwrap(), None, &["rs".to_string()]).unwrap();
+        fs::write(dir.path().join("app.ts"), "export {}").unwrap();
+        assert!(!watcher.poll_changed().unwrap());
+    }
+}
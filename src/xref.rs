@@ -0,0 +1,257 @@
+//! Cross-file identifier analysis.
+//!
+//! Heuristic and purely textual at the identifier level: an identifier node
+//! whose text equals a symbol's name counts as a use. There is no scope or
+//! type resolution. `unused` reports functions without uses, `refs` lists the
+//! uses of one symbol.
+
+use crate::error::CodeviewError;
+use crate::extractor::{extractor_for, interface, ItemKind};
+use crate::languages::{self, Language};
+use crate::output::plain::kind_label;
+use crate::parser;
+use crate::read_source;
+use crate::search::find_enclosing_symbols;
+use crate::walk;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use tree_sitter::{Node, Tree};
+
+/// Options for cross-reference scans.
+#[derive(Default)]
+pub struct XrefOptions {
+    pub depth: Option<usize>,
+    pub ext: Vec<String>,
+}
+
+/// Options for reference scans.
+#[derive(Default)]
+pub struct RefOptions {
+    pub depth: Option<usize>,
+    pub ext: Vec<String>,
+    /// Also report the name of each definition of the symbol
+    pub include_definitions: bool,
+}
+
+/// A line using a symbol's name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub path: String,
+    pub line: usize,
+    /// Enclosing symbols of the line, outermost first.
+    pub symbol_path: Vec<String>,
+    /// The line, trimmed.
+    pub text: String,
+}
+
+/// A function or method with no usages outside its own definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnusedSymbol {
+    pub path: String,
+    pub name: String,
+    pub kind: ItemKind,
+    pub line_start: usize,
+    pub line_end: usize,
+}
+
+/// A parsed source file kept in memory for the duration of a scan.
+pub(crate) struct ParsedFile {
+    pub(crate) path: String,
+    pub(crate) source: String,
+    pub(crate) tree: Tree,
+    pub(crate) language: Language,
+}
+
+/// Report functions/methods under `path` that are never referenced anywhere in
+/// the scanned files, other than inside their own definition.
+///
+/// Entry points and implicitly-invoked names (`main`, `constructor`, Python
+/// dunder methods) are never reported.
+pub fn find_unused(path: &str, options: &XrefOptions) -> Result<Vec<UnusedSymbol>, CodeviewError> {
+    let files = parse_files(Path::new(path), options.depth, &options.ext)?;
+
+    let mut definitions: Vec<UnusedSymbol> = Vec::new();
+    for file in &files {
+        for item in interface::extract(&file.source, &file.tree, file.language) {
+            if !matches!(item.kind, ItemKind::Function | ItemKind::Method) {
+                continue;
+            }
+            let Some(name) = item.name else { continue };
+            if is_implicit_entry_point(&name) {
+                continue;
+            }
+            definitions.push(UnusedSymbol {
+                path: file.path.clone(),
+                name,
+                kind: item.kind,
+                line_start: item.line_start,
+                line_end: item.line_end,
+            });
+        }
+    }
+
+    let wanted: HashSet<&str> = definitions.iter().map(|d| d.name.as_str()).collect();
+    let mut usages: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    for (file_idx, file) in files.iter().enumerate() {
+        collect_identifiers(file.tree.root_node(), &file.source, &wanted, file_idx, &mut usages);
+    }
+
+    let unused = definitions
+        .into_iter()
+        .filter(|def| {
+            let sites = usages.get(&def.name).map(|v| v.as_slice()).unwrap_or(&[]);
+            !sites.iter().any(|&(file_idx, line)| {
+                files[file_idx].path != def.path || line < def.line_start || line > def.line_end
+            })
+        })
+        .collect();
+
+    Ok(unused)
+}
+
+/// Format unused symbols grouped by file, one line per symbol.
+pub fn format_unused(unused: &[UnusedSymbol]) -> String {
+    let mut output = String::new();
+    let mut current_path: Option<&str> = None;
+
+    for sym in unused {
+        if current_path != Some(sym.path.as_str()) {
+            writeln!(output, "{}", sym.path).unwrap();
+            current_path = Some(sym.path.as_str());
+        }
+        writeln!(output, "  {} {:<30} L{}", kind_label(&sym.kind), sym.name, sym.line_start).unwrap();
+    }
+
+    output
+}
+
+/// Report each line under `path` with an identifier named `symbol`, in file
+/// and line order. Definition names are skipped unless `include_definitions`.
+pub fn find_references(symbol: &str, path: &str, options: &RefOptions) -> Result<Vec<Reference>, CodeviewError> {
+    let files = parse_files(Path::new(path), options.depth, &options.ext)?;
+    let wanted: HashSet<&str> = HashSet::from([symbol]);
+
+    let mut references = Vec::new();
+    for file in &files {
+        let extractor = extractor_for(file.language);
+        let mut sites = Vec::new();
+        collect_identifier_nodes(file.tree.root_node(), &file.source, &wanted, &mut sites);
+        let mut lines: Vec<usize> = sites
+            .into_iter()
+            .filter(|node| {
+                options.include_definitions
+                    || !node.parent().is_some_and(|p| {
+                        extractor.node_kind_to_item_kind(p.kind()).is_some()
+                            && p.child_by_field_name("name").is_some_and(|n| n.id() == node.id())
+                    })
+            })
+            .map(|node| node.start_position().row)
+            .collect();
+        lines.dedup();
+
+        let source_lines: Vec<&str> = file.source.lines().collect();
+        for line_idx in lines {
+            references.push(Reference {
+                path: file.path.clone(),
+                line: line_idx + 1,
+                symbol_path: find_enclosing_symbols(&file.tree, &file.source, line_idx, file.language),
+                text: source_lines.get(line_idx).map_or("", |l| l.trim()).to_string(),
+            });
+        }
+    }
+    Ok(references)
+}
+
+/// Format references grouped by file, one line per use with its enclosing symbols.
+pub fn format_references(references: &[Reference]) -> String {
+    let mut output = String::new();
+    let mut current_path: Option<&str> = None;
+
+    for r in references {
+        if current_path != Some(r.path.as_str()) {
+            writeln!(output, "{}", r.path).unwrap();
+            current_path = Some(r.path.as_str());
+        }
+        if r.symbol_path.is_empty() {
+            writeln!(output, "  L{}: {}", r.line, r.text).unwrap();
+        } else {
+            writeln!(output, "  L{} in {}: {}", r.line, r.symbol_path.join(" > "), r.text).unwrap();
+        }
+    }
+
+    output
+}
+
+/// Parse every supported file under `path` (or `path` itself). Unsupported files
+/// in a directory are skipped; an unsupported single file is an error.
+pub(crate) fn parse_files(path: &Path, depth: Option<usize>, ext: &[String]) -> Result<Vec<ParsedFile>, CodeviewError> {
+    if !path.exists() {
+        return Err(CodeviewError::PathNotFound(path.display().to_string()));
+    }
+    let paths: Vec<PathBuf> = if path.is_dir() {
+        walk::walk_directory(path, depth, ext)?
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut files = Vec::new();
+    for file_path in paths {
+        let language = match languages::detect_language(&file_path) {
+            Ok(l) => l,
+            Err(e) if path.is_file() => return Err(e),
+            Err(_) => continue,
+        };
+        let source = match read_source(&file_path, false) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Warning: Skipping {}", e);
+                continue;
+            }
+        };
+        let tree = parser::parse(&source, language)?;
+        files.push(ParsedFile {
+            path: file_path.to_string_lossy().to_string(),
+            source,
+            tree,
+            language,
+        });
+    }
+    Ok(files)
+}
+
+fn is_implicit_entry_point(name: &str) -> bool {
+    name == "main" || name == "constructor" || (name.starts_with("__") && name.ends_with("__"))
+}
+
+/// Record every identifier-like leaf whose text is in `wanted` as (file, 1-based line).
+fn collect_identifiers(
+    node: Node,
+    source: &str,
+    wanted: &HashSet<&str>,
+    file_idx: usize,
+    usages: &mut HashMap<String, Vec<(usize, usize)>>,
+) {
+    let mut nodes = Vec::new();
+    collect_identifier_nodes(node, source, wanted, &mut nodes);
+    for node in nodes {
+        usages
+            .entry(source[node.byte_range()].to_string())
+            .or_default()
+            .push((file_idx, node.start_position().row + 1));
+    }
+}
+
+/// Every identifier-like leaf under `node` whose text is in `wanted`, in source order.
+fn collect_identifier_nodes<'a>(node: Node<'a>, source: &str, wanted: &HashSet<&str>, found: &mut Vec<Node<'a>>) {
+    if node.child_count() == 0 {
+        if node.is_named() && node.kind().ends_with("identifier") && wanted.contains(&source[node.byte_range()]) {
+            found.push(node);
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_identifier_nodes(child, source, wanted, found);
+    }
+}
@@ -0,0 +1,29 @@
+use codeview::callgraph;
+use std::fs;
+use tempfile::TempDir;
+
+fn graph_of(name: &str, content: &str) -> Vec<callgraph::CallNode> {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join(name);
+    fs::write(&path, content).unwrap();
+    callgraph::call_graph(path.to_str().unwrap()).unwrap()
+}
+
+#[test]
+fn test_rust_edges_between_top_level_functions() {
+    let graph = graph_of(
+        "a.rs",
+        "fn a() {\n    b();\n    let _ = c(b());\n    x.d();\n}\n\nfn b() -> u32 {\n    std::process::id()\n}\n\npub fn c(n: u32) -> u32 {\n    n\n}\n\nfn d() {}\n",
+    );
+    assert_eq!(callgraph::format_edges(&graph), "a -> b\na -> c\n");
+    assert_eq!(graph.iter().map(|n| n.name.as_str()).collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+}
+
+#[test]
+fn test_python_and_typescript_calls() {
+    let graph = graph_of("m.py", "@cache\ndef load():\n    return parse(read())\n\ndef parse(text):\n    return text\n\ndef read():\n    return ''\n");
+    assert_eq!(callgraph::format_edges(&graph), "load -> parse\nload -> read\n");
+
+    let graph = graph_of("m.ts", "export function main(): void {\n  helper();\n}\n\nfunction helper(): void {}\n");
+    assert_eq!(callgraph::format_edges(&graph), "main -> helper\n");
+}
@@ -0,0 +1,40 @@
+use codeview::compare::{self, Change, ChangeKind};
+use codeview::ItemKind;
+use std::fs;
+use tempfile::TempDir;
+
+fn write_file(dir: &TempDir, name: &str, content: &str) -> String {
+    let path = dir.path().join(name);
+    fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_added_function_reported() {
+    let dir = TempDir::new().unwrap();
+    let old = write_file(&dir, "old.rs", "pub fn keep() {}\n");
+    let new = write_file(&dir, "new.rs", "pub fn keep() {}\n\npub fn new_thing(x: u32) -> u32 {\n    x\n}\n");
+
+    let changes = compare::compare_files(&old, &new).unwrap();
+    assert_eq!(changes, vec![Change { change: ChangeKind::Added, kind: ItemKind::Function, name: "new_thing".to_string() }]);
+    assert_eq!(compare::format_changes(&changes), "+ fn new_thing\n");
+}
+
+#[test]
+fn test_removed_and_changed_symbols() {
+    let dir = TempDir::new().unwrap();
+    let old = write_file(&dir, "old.rs", "pub struct Old;\n\npub struct User;\n\nimpl User {\n    pub fn greet(&self) -> String {\n        String::new()\n    }\n}\n");
+    let new = write_file(&dir, "new.rs", "pub struct User;\n\nimpl User {\n    pub fn greet(&self, loud: bool) -> String {\n        String::new()\n    }\n}\n");
+
+    let output = compare::format_changes(&compare::compare_files(&old, &new).unwrap());
+    assert_eq!(output, "- struct Old\n~ fn User::greet (signature)\n");
+}
+
+#[test]
+fn test_body_only_change_is_not_reported() {
+    let dir = TempDir::new().unwrap();
+    let old = write_file(&dir, "old.ts", "export function f(): number {\n  return 1;\n}\n");
+    let new = write_file(&dir, "new.ts", "export function f(): number {\n  return 2;\n}\n");
+
+    assert!(compare::compare_files(&old, &new).unwrap().is_empty());
+}
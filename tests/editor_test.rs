@@ -141,6 +141,21 @@ struct ToKeep {
     assert!(result.contains("struct ToKeep"));
 }
 
+#[test]
+fn test_delete_keep_attributes() {
+    let source = "mod m {\n    /// Helper docs.\n    #[inline]\n    #[cfg(feature = \"fast\")]\n    fn helper() {\n        work();\n    }\n    fn other() {}\n}\n";
+
+    let result = editor::delete_keep_attributes(source, "helper", Language::Rust).unwrap();
+
+    assert_eq!(result, "mod m {\n    /// Helper docs.\n    #[inline]\n    #[cfg(feature = \"fast\")]\n    fn other() {}\n}\n");
+}
+
+#[test]
+fn test_delete_keep_attributes_rejects_dangling_decorator() {
+    let source = "@cache\ndef helper():\n    return 1\n";
+    assert!(editor::delete_keep_attributes(source, "helper", Language::Python).is_err());
+}
+
 #[test]
 fn test_delete_trailing_newline_cleanup() {
     let source = r#"fn first() {}
@@ -169,6 +184,139 @@ fn existing() {
     assert!(result.is_err());
 }
 
+// ============================================================================
+// INSERT TESTS
+// ============================================================================
+
+#[test]
+fn test_insert_before_method_keeps_indentation() {
+    let source = "impl S {\n    #[inline]\n    fn b() {}\n}\n";
+
+    let result = editor::insert_before(source, "b", "    fn a() {}", Language::Rust).unwrap();
+
+    assert_eq!(result, "impl S {\n    fn a() {}\n    #[inline]\n    fn b() {}\n}\n");
+}
+
+#[test]
+fn test_insert_after_function() {
+    let source = "fn a() {}\n\nfn c() {}\n";
+
+    let result = editor::insert_after(source, "a", "\nfn b() {}\n", Language::Rust).unwrap();
+
+    assert_eq!(result, "fn a() {}\n\nfn b() {}\n\nfn c() {}\n");
+}
+
+#[test]
+fn test_insert_after_last_item_adds_trailing_newline() {
+    let source = "fn a() {}";
+
+    let result = editor::insert_after(source, "a", "fn b() {}", Language::Rust).unwrap();
+
+    assert_eq!(result, "fn a() {}\nfn b() {}\n");
+}
+
+#[test]
+fn test_insert_rejects_invalid_syntax() {
+    let source = "fn a() {}\n";
+    assert!(editor::insert_after(source, "a", "fn broken( {", Language::Rust).is_err());
+}
+
+#[test]
+fn test_batch_insert_before_and_after() {
+    let source = "def middle():\n    return 2\n";
+    let edits = vec![
+        BatchEdit { symbol: "middle".to_string(), action: BatchAction::InsertBefore, content: Some("def first():\n    return 1\n".to_string()) },
+        BatchEdit { symbol: "middle".to_string(), action: BatchAction::InsertAfter, content: Some("def last():\n    return 3".to_string()) },
+    ];
+
+    let result = editor::batch(source, &edits, Language::Python).unwrap();
+
+    assert_eq!(result, "def first():\n    return 1\ndef middle():\n    return 2\ndef last():\n    return 3\n");
+    let missing = vec![BatchEdit { symbol: "middle".to_string(), action: BatchAction::InsertAfter, content: None }];
+    assert!(editor::batch(source, &missing, Language::Python).is_err());
+}
+
+// ============================================================================
+// ADD_METHOD TESTS
+// ============================================================================
+
+#[test]
+fn test_add_method_to_inherent_impl() {
+    let source = "struct S;\n\nimpl Display for S {}\n\nimpl S {\n    fn a() {}\n}\n";
+
+    let result = editor::add_method(source, "S", "fn b() -> u32 {\n    1\n}", Language::Rust).unwrap();
+
+    assert_eq!(result, "struct S;\n\nimpl Display for S {}\n\nimpl S {\n    fn a() {}\n\n    fn b() -> u32 {\n        1\n    }\n}\n");
+}
+
+#[test]
+fn test_add_method_to_empty_nested_impl() {
+    let source = "mod m {\n    impl T {}\n}\n";
+
+    let result = editor::add_method(source, "T", "fn c() {}", Language::Rust).unwrap();
+
+    assert_eq!(result, "mod m {\n    impl T {\n        fn c() {}\n    }\n}\n");
+}
+
+#[test]
+fn test_add_method_to_python_class() {
+    let source = "class A:\n    def x(self):\n        return 1\n\nz = 1\n";
+
+    let result = editor::add_method(source, "A", "def y(self):\n    return 2", Language::Python).unwrap();
+
+    assert_eq!(result, "class A:\n    def x(self):\n        return 1\n\n    def y(self):\n        return 2\n\nz = 1\n");
+}
+
+#[test]
+fn test_add_method_to_typescript_class() {
+    let source = "export class C {\n    a() {}\n}\n";
+
+    let result = editor::add_method(source, "C", "b(): number {\n    return 1;\n}", Language::TypeScript).unwrap();
+
+    assert_eq!(result, "export class C {\n    a() {}\n\n    b(): number {\n        return 1;\n    }\n}\n");
+}
+
+#[test]
+fn test_add_method_errors() {
+    let source = "fn free() {}\n\nstruct Plain;\n";
+    let err = editor::add_method(source, "free", "fn m() {}", Language::Rust).unwrap_err();
+    assert!(err.to_string().contains("not an impl or class"), "got: {}", err);
+    assert!(editor::add_method(source, "Missing", "fn m() {}", Language::Rust).is_err());
+    let source = "impl Plain {}\n";
+    assert!(editor::add_method(source, "Plain", "fn broken( {", Language::Rust).is_err());
+}
+
+// ============================================================================
+// RENAME TESTS
+// ============================================================================
+
+#[test]
+fn test_rename_definition_and_references() {
+    let source = "struct User;\n\nimpl User {\n    fn new() -> User { User }\n}\n\nfn greet(u: &User) -> &str {\n    // User greeting\n    \"User\"\n}\n";
+
+    let result = editor::rename(source, "User", "Person", Language::Rust).unwrap();
+
+    assert_eq!(result, "struct Person;\n\nimpl Person {\n    fn new() -> Person { Person }\n}\n\nfn greet(u: &Person) -> &str {\n    // User greeting\n    \"User\"\n}\n");
+}
+
+#[test]
+fn test_rename_typescript_method_calls() {
+    let source = "class Api {\n  fetch() {\n    return 1;\n  }\n  run() {\n    return this.fetch();\n  }\n}\n";
+
+    let result = editor::rename(source, "fetch", "load", Language::TypeScript).unwrap();
+
+    assert!(result.contains("  load() {"));
+    assert!(result.contains("return this.load();"));
+    assert!(!result.contains("fetch"));
+}
+
+#[test]
+fn test_rename_errors() {
+    let source = "def helper():\n    return 1\n";
+    assert!(editor::rename(source, "missing", "other", Language::Python).is_err());
+    assert!(editor::rename(source, "helper", "not valid", Language::Python).is_err());
+}
+
 // ============================================================================
 // REPLACE_BODY TESTS
 // ============================================================================
@@ -371,6 +519,38 @@ fn unchanged() {
     assert_eq!(result, source);
 }
 
+#[test]
+fn test_batch_session_matches_one_shot_batch() {
+    let source: String = (0..50).map(|i| format!("fn f{i}() -> u32 {{\n    {i}\n}}\n\n")).collect();
+    let edits: Vec<BatchEdit> = (0..50)
+        .map(|i| BatchEdit {
+            symbol: format!("f{i}"),
+            action: if i % 10 == 9 { BatchAction::Delete } else { BatchAction::ReplaceBody },
+            content: Some(format!("let x = {i};\nx * 2")),
+        })
+        .collect();
+
+    let mut session = editor::BatchSession::new(&source, Language::Rust).unwrap();
+    for edit in &edits {
+        session.apply(edit).unwrap();
+    }
+    let incremental = session.finish();
+    assert_eq!(incremental, editor::batch(&source, &edits, Language::Rust).unwrap());
+    assert!(incremental.contains("fn f48() -> u32 {\n    let x = 48;\n    x * 2\n}") && !incremental.contains("fn f49"));
+}
+
+#[test]
+fn test_batch_session_rejects_bad_edit_and_keeps_state() {
+    let source = "fn a() {}\n\nfn b() {}\n";
+    let mut session = editor::BatchSession::new(source, Language::Rust).unwrap();
+    let bad = BatchEdit { symbol: "a".to_string(), action: BatchAction::Replace, content: Some("fn a( {".to_string()) };
+    assert!(session.apply(&bad).is_err());
+    let missing = BatchEdit { symbol: "zzz".to_string(), action: BatchAction::Delete, content: None };
+    assert!(session.apply(&missing).is_err());
+    session.apply(&BatchEdit { symbol: "b".to_string(), action: BatchAction::Delete, content: None }).unwrap();
+    assert_eq!(session.finish(), "fn a() {}\n\n");
+}
+
 // ============================================================================
 // TYPESCRIPT TESTS
 // ============================================================================
@@ -517,6 +697,19 @@ def calculate(x, y):
     assert!(!result.contains("{"));
 }
 
+#[test]
+fn test_body_text_round_trips_through_replace_body() {
+    let rust = "impl A {\n    fn f(&self) -> u32 {\n        let x = 1;\n        if x > 0 {\n            x\n        } else {\n            0\n        }\n    }\n}\n";
+    let body = editor::body_text(rust, "f", Language::Rust).unwrap();
+    assert_eq!(body, "let x = 1;\nif x > 0 {\n    x\n} else {\n    0\n}\n");
+    assert_eq!(editor::replace_body(rust, "f", &body, Language::Rust).unwrap(), rust);
+
+    let python = "class A:\n    def f(self):\n        x = 1\n        if x:\n            return x\n\n    def g(self):\n        pass\n";
+    let body = editor::body_text(python, "f", Language::Python).unwrap();
+    assert_eq!(body, "x = 1\nif x:\n    return x\n");
+    assert_eq!(editor::replace_body(python, "f", &body, Language::Python).unwrap(), python);
+}
+
 #[test]
 fn test_python_delete_class() {
     let source = "
@@ -584,3 +777,222 @@ function third() {
     assert!(result.contains("function third()"));
     assert!(!result.contains("function second()"));
 }
+
+// ============================================================================
+// EDITORCONFIG TESTS
+// ============================================================================
+
+#[test]
+fn test_replace_body_respects_editorconfig_two_spaces() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join(".editorconfig"),
+        "root = true\n\n[*.rs]\nindent_style = space\nindent_size = 2\n",
+    )
+    .unwrap();
+    let file = dir.path().join("lib.rs");
+    std::fs::write(&file, "fn foo() {\n  old();\n}\n").unwrap();
+
+    let unit = editor::editorconfig::indent_unit_for(&file).unwrap();
+    assert_eq!(unit, "  ");
+
+    let source = std::fs::read_to_string(&file).unwrap();
+    let result = editor::replace_body_with_indent(&source, "foo", "first();\nsecond();", Language::Rust, &unit).unwrap();
+    assert!(result.contains("\n  first();\n  second();\n}"));
+    assert!(!result.contains("    first();"));
+}
+
+#[test]
+fn test_edit_cli_respect_editorconfig() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join(".editorconfig"), "root = true\n[*]\nindent_size = 2\n").unwrap();
+    let file = dir.path().join("lib.rs");
+    std::fs::write(&file, "fn foo() {\n  old();\n}\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["edit", file.to_str().unwrap(), "foo", "--replace-body", "updated();", "--respect-editorconfig"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let result = std::fs::read_to_string(&file).unwrap();
+    assert_eq!(result, "fn foo() {\n  updated();\n}\n");
+}
+
+#[test]
+fn test_editorconfig_absent_falls_back_to_default() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join(".editorconfig"), "root = true\n[*.py]\nindent_size = 2\n").unwrap();
+    let file = dir.path().join("lib.rs");
+    std::fs::write(&file, "fn foo() {}\n").unwrap();
+    assert_eq!(editor::editorconfig::indent_unit_for(&file), None);
+}
+
+// ============================================================================
+// REPLACE_SIGNATURE TESTS
+// ============================================================================
+
+#[test]
+fn test_replace_signature_keeps_body() {
+    let source = r#"
+/// Adds things.
+#[inline]
+fn calculate(x: i32, y: i32) -> i32 {
+    let sum = x + y;
+    sum
+}
+"#;
+
+    let result = editor::replace_signature(source, "calculate", "/// Adds things.\n#[inline]\npub fn calculate(x: i64, y: i64, z: i64) -> i64", Language::Rust).unwrap();
+
+    assert!(result.contains("pub fn calculate(x: i64, y: i64, z: i64) -> i64 {\n    let sum = x + y;\n    sum\n}"), "{}", result);
+    assert!(!result.contains("x: i32"));
+    assert_eq!(result.matches("#[inline]").count(), 1);
+}
+
+#[test]
+fn test_replace_signature_python() {
+    let source = "def greet(name):\n    return f\"hi {name}\"\n";
+    let result = editor::replace_signature(source, "greet", "def greet(name: str, punct: str = \"!\") -> str", Language::Python).unwrap();
+    assert_eq!(result, "def greet(name: str, punct: str = \"!\") -> str:\n    return f\"hi {name}\"\n");
+}
+
+#[test]
+fn test_replace_signature_invalid_result() {
+    let source = "fn valid(x: i32) -> i32 {\n    x + 1\n}\n";
+    assert!(editor::replace_signature(source, "valid", "fn valid(x: i32 ->", Language::Rust).is_err());
+}
+
+// ============================================================================
+// DEDUP_IMPORTS TESTS
+// ============================================================================
+
+#[test]
+fn test_dedup_imports_typescript() {
+    let source = "import { a } from './a';\nimport { b } from './b';\nimport {  a  } from './a';\n\nexport function f() {\n  return a + b;\n}\n";
+    let result = editor::dedup_imports(source, Language::TypeScript).unwrap();
+    assert_eq!(result.matches("from './a'").count(), 1);
+    assert_eq!(result, "import { a } from './a';\nimport { b } from './b';\n\nexport function f() {\n  return a + b;\n}\n");
+}
+
+#[test]
+fn test_dedup_imports_rust_keeps_distinct() {
+    let source = "use std::fmt;\nuse std::io;\nuse std::fmt;\n\nfn main() {}\n";
+    let result = editor::dedup_imports(source, Language::Rust).unwrap();
+    assert_eq!(result, "use std::fmt;\nuse std::io;\n\nfn main() {}\n");
+    assert_eq!(editor::duplicate_import_lines(source, Language::Rust).unwrap(), vec![(3, 3)]);
+}
+
+#[test]
+fn test_dedup_imports_no_duplicates_is_noop() {
+    let source = "import os\nimport sys\n";
+    assert_eq!(editor::dedup_imports(source, Language::Python).unwrap(), source);
+}
+
+// ============================================================================
+// DIFF TESTS
+// ============================================================================
+
+#[test]
+fn test_edit_cli_diff_dry_run() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    let source = "fn foo() {\n    old();\n}\n\nfn bar() {}\n";
+    std::fs::write(&file, source).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["edit", file.to_str().unwrap(), "foo", "--replace-body", "updated();", "--diff", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("@@ -1,5 +1,5 @@\n fn foo() {\n-    old();\n+    updated();\n }\n"), "{}", stdout);
+    assert!(!stdout.contains("fn bar() {}\n\n"));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), source);
+}
+
+#[test]
+fn test_edit_cli_diff_does_not_write_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    let source = "fn foo() {\n    old();\n}\n";
+    std::fs::write(&file, source).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["edit", file.to_str().unwrap(), "foo", "--replace-body", "updated();", "--diff"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("+    updated();\n"));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), source);
+}
+
+#[test]
+fn test_edit_cli_diff_json_batch() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    std::fs::write(&file, "fn foo() {}\n\nfn bar() {}\n").unwrap();
+    let batch = dir.path().join("edits.json");
+    std::fs::write(&batch, r#"{"edits": [{"symbol": "foo", "action": "delete"}, {"symbol": "bar", "action": "replace", "content": "fn bar() -> u8 { 1 }"}]}"#).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["edit", file.to_str().unwrap(), "_", "--batch", batch.to_str().unwrap(), "--diff", "--json", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let results: Vec<editor::EditResult> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(results[0].diff.as_deref().unwrap().contains("-fn foo() {}\n"));
+    assert!(!results[0].diff.as_deref().unwrap().contains("+fn bar"));
+    assert!(results[1].diff.as_deref().unwrap().contains("-fn bar() {}\n+fn bar() -> u8 { 1 }\n"));
+}
+
+// ============================================================================
+// REPLACE_RANGE TESTS
+// ============================================================================
+
+#[test]
+fn test_replace_range_keeps_surrounding_lines() {
+    let source = "fn main() {\n    let f = |x| x + 1;\n    f(1);\n}\n";
+    let result = editor::replace_range(source, 2, 3, "    let f = |x| x * 2;\n    f(2);\n", Language::Rust).unwrap();
+    assert_eq!(result, "fn main() {\n    let f = |x| x * 2;\n    f(2);\n}\n");
+}
+
+#[test]
+fn test_replace_range_last_line_without_newline() {
+    let source = "x = 1\ny = 2";
+    let result = editor::replace_range(source, 2, 2, "y = 3\n", Language::Python).unwrap();
+    assert_eq!(result, "x = 1\ny = 3");
+}
+
+#[test]
+fn test_replace_range_out_of_bounds() {
+    let source = "fn a() {}\nfn b() {}\n";
+    let err = editor::replace_range(source, 2, 5, "fn c() {}", Language::Rust).unwrap_err();
+    assert!(err.to_string().contains("beyond end of file (2 lines)"), "{}", err);
+    assert!(editor::replace_range(source, 0, 1, "fn c() {}", Language::Rust).is_err());
+}
+
+#[test]
+fn test_replace_range_invalid_result() {
+    let source = "fn a() {\n    1\n}\n";
+    assert!(editor::replace_range(source, 1, 1, "fn a(", Language::Rust).is_err());
+}
+
+#[test]
+fn test_edit_cli_lines_replace_stdin() {
+    use std::io::Write;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    std::fs::write(&file, "fn a() {}\n\nconst X: u8 = 1;\n").unwrap();
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["edit", file.to_str().unwrap(), "--lines", "3-3", "--replace-stdin"])
+        .stdin(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"const X: u8 = 2;\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "fn a() {}\n\nconst X: u8 = 2;\n");
+}
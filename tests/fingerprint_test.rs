@@ -0,0 +1,51 @@
+use codeview::{process_path, ProcessOptions};
+use std::fs;
+use tempfile::TempDir;
+
+fn fingerprint(path: &str) -> serde_json::Value {
+    let output = process_path(path, ProcessOptions { fingerprint: true, ..Default::default() }).unwrap();
+    serde_json::from_str(output.lines().next().unwrap()).unwrap()
+}
+
+#[test]
+fn fingerprint_has_counts_and_hash() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("lib.rs");
+    fs::write(&path, "fn a() {}\nfn b() {}\n").unwrap();
+    let fp = fingerprint(path.to_str().unwrap());
+
+    assert_eq!(fp["lines"], 2);
+    assert_eq!(fp["bytes"], 20);
+    assert_eq!(fp["items"], 2);
+    assert_eq!(fp["sha256"].as_str().unwrap().len(), 64);
+    assert!(fp.get("content").is_none(), "fingerprint must not emit code");
+}
+
+#[test]
+fn fingerprint_stable_until_content_changes() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("lib.rs");
+    let path_str = path.to_str().unwrap();
+    fs::write(&path, "fn a() {}\n").unwrap();
+
+    let first = fingerprint(path_str);
+    let second = fingerprint(path_str);
+    assert_eq!(first["sha256"], second["sha256"]);
+
+    fs::write(&path, "fn a() { 1 }\n").unwrap();
+    let changed = fingerprint(path_str);
+    assert_ne!(first["sha256"], changed["sha256"]);
+}
+
+#[test]
+fn fingerprint_one_line_per_file() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+    fs::write(dir.path().join("b.ts"), "export function b() {}\n").unwrap();
+    let output = process_path(
+        dir.path().to_str().unwrap(),
+        ProcessOptions { fingerprint: true, ..Default::default() },
+    )
+    .unwrap();
+    assert_eq!(output.lines().count(), 2);
+}
@@ -0,0 +1,50 @@
+use codeview::hotspots::{self, HotspotOptions};
+use std::fs;
+use tempfile::TempDir;
+
+fn write_file(dir: &TempDir, name: &str, content: &str) {
+    fs::write(dir.path().join(name), content).unwrap();
+}
+
+fn sample_dir() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "a.rs", "fn small() {}\n\nfn medium() {\n    one();\n    two();\n}\n");
+    write_file(&dir, "b.ts", "export function largest(x: number): number {\n  const a = x + 1;\n  const b = a + 1;\n  const c = b + 1;\n  return c;\n}\n");
+    write_file(&dir, "c.rs", "struct S;\n\nimpl S {\n    fn method(&self) {\n        one();\n        two();\n        three();\n    }\n}\n");
+    dir
+}
+
+#[test]
+fn test_top_function_is_largest() {
+    let dir = sample_dir();
+    let options = HotspotOptions { top: Some(2), ..Default::default() };
+    let found = hotspots::find_hotspots(dir.path().to_str().unwrap(), &options).unwrap();
+
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].name, "largest");
+    assert_eq!(found[0].lines, 6);
+    assert!(found[0].path.ends_with("b.ts"));
+    assert_eq!(found[1].name, "method");
+}
+
+#[test]
+fn test_without_top_lists_all_functions_by_size() {
+    let dir = sample_dir();
+    let found = hotspots::find_hotspots(dir.path().to_str().unwrap(), &HotspotOptions::default()).unwrap();
+    let names: Vec<&str> = found.iter().map(|h| h.name.as_str()).collect();
+    assert_eq!(names, vec!["largest", "method", "medium", "small"]);
+}
+
+#[test]
+fn test_format_with_signatures() {
+    let dir = sample_dir();
+    let options = HotspotOptions { top: Some(1), ..Default::default() };
+    let found = hotspots::find_hotspots(dir.path().to_str().unwrap(), &options).unwrap();
+    let output = hotspots::format_hotspots(&found, true);
+
+    let mut lines = output.lines();
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("6  "), "got: {header}");
+    assert!(header.ends_with("b.ts:1  largest"), "got: {header}");
+    assert_eq!(lines.next().unwrap().trim(), "export function largest(x: number): number { ... }");
+}
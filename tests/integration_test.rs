@@ -1,4 +1,4 @@
-use codeview::{process_path, ProcessOptions, OutputFormat};
+use codeview::{process_path, ProcessOptions, OutputFormat, PathStyle};
 
 const FIXTURE_PATH: &str = "tests/fixtures/sample.rs";
 const FIXTURE_DIR: &str = "tests/fixtures";
@@ -16,6 +16,7 @@ fn test_interface_mode_basic() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -46,6 +47,7 @@ fn test_expand_mode() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -73,6 +75,7 @@ fn test_expand_function() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -98,6 +101,7 @@ fn test_pub_filter() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -126,6 +130,7 @@ fn test_fns_filter() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -155,6 +160,7 @@ fn test_types_filter() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -185,6 +191,7 @@ fn test_combined_pub_fns() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -217,6 +224,7 @@ fn test_json_output() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -250,6 +258,7 @@ fn test_nonexistent_path() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -272,6 +281,7 @@ fn test_directory_mode() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -297,6 +307,7 @@ fn test_expand_nonexistent_symbol() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
     
@@ -326,6 +337,7 @@ fn test_no_tests_filter() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
 
@@ -358,6 +370,7 @@ fn test_no_tests_filter_disabled() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
 
@@ -384,6 +397,7 @@ fn test_stats_output_plain() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
 
@@ -414,6 +428,7 @@ fn test_stats_output_json() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
 
@@ -430,6 +445,25 @@ fn test_stats_output_json() {
             "Stats JSON should be an object or array");
 }
 
+#[test]
+fn test_type_api_counts_public_methods() {
+    let options = ProcessOptions { type_api: true, ..Default::default() };
+    let output = process_path("tests/fixtures/sample.rs", options).unwrap();
+
+    // `new` and `greeting` are public; `validate_email` is not
+    assert!(output.contains("  struct User                           2 public methods\n"), "got:\n{output}");
+    assert!(output.contains("  enum Role                           0 public methods\n"));
+}
+
+#[test]
+fn test_type_api_counts_trait_impl_methods_as_public() {
+    let options = ProcessOptions { type_api: true, ..Default::default() };
+    let output = process_path("tests/fixtures/test_impl.rs", options).unwrap();
+
+    // `new`, `display` and `Greeter::greet`; `validate` is private
+    assert!(output.contains("  struct User                           3 public methods\n"), "got:\n{output}");
+}
+
 #[test]
 fn test_stats_with_directory() {
     let options = ProcessOptions {
@@ -445,6 +479,7 @@ fn test_stats_with_directory() {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     
 };
 
@@ -455,3 +490,527 @@ fn test_stats_with_directory() {
     // Directory stats should show totals for multiple files
     assert!(!output.is_empty(), "Stats for directory should not be empty");
 }
+
+#[test]
+fn test_json_is_test_flag() {
+    let options = ProcessOptions {
+        symbols: vec!["test_user_creation".to_string(), "public_utility".to_string()],
+        format: OutputFormat::Json,
+        ..Default::default()
+    };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let test_fn = items.iter().find(|i| i["name"] == "test_user_creation").unwrap();
+    assert_eq!(test_fn["is_test"], true);
+    let utility = items.iter().find(|i| i["name"] == "public_utility").unwrap();
+    assert_eq!(utility["is_test"], false);
+}
+
+#[test]
+fn test_json_is_test_cfg_test_module() {
+    let options = ProcessOptions { format: OutputFormat::Json, ..Default::default() };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let tests_mod = items.iter().find(|i| i["name"] == "tests").unwrap();
+    assert_eq!(tests_mod["is_test"], true);
+    assert!(items.iter().filter(|i| i["name"] != "tests").all(|i| i["is_test"] == false));
+}
+
+#[test]
+fn test_targets_output() {
+    let options = ProcessOptions { targets: true, ..Default::default() };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+
+    assert!(output.lines().any(|l| l == format!("{}:45:8:fn:public_utility", FIXTURE_PATH)), "got:\n{output}");
+    assert!(output.lines().any(|l| l == format!("{}:5:12:struct:User", FIXTURE_PATH)), "got:\n{output}");
+    assert!(output.lines().all(|l| l.starts_with(FIXTURE_PATH) && l.split(':').count() == 5));
+}
+
+#[test]
+fn test_path_style_basename_json() {
+    let options = ProcessOptions {
+        format: OutputFormat::Json,
+        path_style: PathStyle::Basename,
+        ..Default::default()
+    };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(parsed["files"][0]["path"], "sample.rs");
+}
+
+#[test]
+fn test_path_style_relative_directory() {
+    let options = ProcessOptions { path_style: PathStyle::Relative, ..Default::default() };
+    let output = process_path(FIXTURE_DIR, options).unwrap();
+
+    assert!(output.lines().any(|l| l == "sample.rs"), "got:\n{output}");
+    assert!(!output.contains(FIXTURE_DIR));
+}
+
+#[test]
+fn test_path_style_absolute() {
+    let options = ProcessOptions { path_style: PathStyle::Absolute, list_symbols: true, ..Default::default() };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+    let first = output.lines().next().unwrap();
+
+    assert!(std::path::Path::new(first).is_absolute(), "got: {first}");
+    assert!(first.ends_with("tests/fixtures/sample.rs"));
+}
+
+#[test]
+fn test_emit_empty_lists_files_without_items() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+    std::fs::write(dir.path().join("empty.rs"), "// nothing to see here\n").unwrap();
+    let dir_path = dir.path().to_str().unwrap();
+
+    let output = process_path(dir_path, ProcessOptions { emit_empty: true, ..Default::default() }).unwrap();
+    let empty = dir.path().join("empty.rs").to_string_lossy().to_string();
+    assert!(output.contains(&format!("{}\n// (no items)\n", empty)), "got:\n{output}");
+    assert!(output.contains("fn a() { ... }"));
+
+    let output = process_path(dir_path, ProcessOptions::default()).unwrap();
+    assert!(!output.contains("empty.rs"));
+}
+
+#[test]
+fn test_dir_lang_parses_mapped_files_as_language() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("scripts")).unwrap();
+    std::fs::write(dir.path().join("scripts/deploy.txt"), "def deploy(target):\n    return target\n").unwrap();
+    std::fs::write(dir.path().join("readme.txt"), "def not_code():\n").unwrap();
+    std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+    let dir_path = dir.path().to_str().unwrap();
+
+    let options = ProcessOptions {
+        dir_lang: vec![("scripts/**".to_string(), codeview::Language::Python)],
+        list_symbols: true,
+        ..Default::default()
+    };
+    let output = process_path(dir_path, options).unwrap();
+    assert!(output.contains("deploy.txt\n  fn deploy"), "got:\n{output}");
+    assert!(output.contains("fn main"));
+    assert!(!output.contains("readme.txt"), "unmapped files keep extension-based detection");
+
+    let output = process_path(dir_path, ProcessOptions { list_symbols: true, ..Default::default() }).unwrap();
+    assert!(!output.contains("deploy"));
+}
+
+#[test]
+fn test_dump_sexp_whole_file_and_line_range() {
+    let output = codeview::dump_sexp(FIXTURE_PATH, None).unwrap();
+    assert!(output.starts_with("(source_file\n  (use_declaration\n"), "got:\n{output}");
+    assert_eq!(output.matches('(').count(), output.matches(')').count());
+
+    let output = codeview::dump_sexp(FIXTURE_PATH, Some("45-47")).unwrap();
+    assert!(output.starts_with("(function_item\n  (visibility_modifier)\n  name: (identifier)\n"), "got:\n{output}");
+    assert!(!output.contains("private_helper"));
+
+    assert!(codeview::dump_sexp(FIXTURE_DIR, None).is_err());
+}
+
+#[test]
+fn test_bench_extract_runs_on_file_only() {
+    assert!(codeview::bench_extract(FIXTURE_PATH, 2).is_ok());
+    assert!(codeview::bench_extract(FIXTURE_DIR, 2).is_err());
+}
+
+#[test]
+fn test_invalid_utf8_file_is_a_clear_error_or_skipped() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let bad = dir.path().join("bad.rs");
+    std::fs::write(&bad, b"fn ok() {}\n// caf\xe9\n").unwrap();
+    std::fs::write(dir.path().join("good.rs"), "fn good() {}\n").unwrap();
+
+    let err = process_path(bad.to_str().unwrap(), ProcessOptions::default()).unwrap_err();
+    assert!(matches!(err, codeview::CodeviewError::InvalidUtf8(_)));
+    assert!(err.to_string().ends_with("file is not valid UTF-8"), "got: {err}");
+
+    let output = process_path(dir.path().to_str().unwrap(), ProcessOptions::default()).unwrap();
+    assert!(output.contains("fn good()"));
+    assert!(!output.contains("bad.rs"));
+
+    let output = process_path(bad.to_str().unwrap(), ProcessOptions { lossy: true, ..Default::default() }).unwrap();
+    assert!(output.contains("fn ok() { ... }"), "got:\n{output}");
+}
+
+#[test]
+fn test_per_symbol_stream_emits_one_object_per_line() {
+    let options = ProcessOptions {
+        format: OutputFormat::Json,
+        per_symbol_stream: true,
+        ..Default::default()
+    };
+    let output = process_path(FIXTURE_DIR, options).unwrap();
+
+    let objects: Vec<serde_json::Value> = output.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert!(objects.len() > 3);
+    assert!(objects.iter().all(|o| o["file"].as_str().is_some_and(|f| f.starts_with(FIXTURE_DIR)) && o["kind"].is_string()));
+    let user = objects.iter().find(|o| o["name"] == "User" && o["kind"] == "struct").unwrap();
+    assert_eq!(user["file"], FIXTURE_PATH);
+}
+
+/// Records every `write` call and how many had happened at each `flush`.
+#[derive(Default)]
+struct RecordingSink {
+    writes: Vec<Vec<u8>>,
+    flushed_at: Vec<usize>,
+}
+
+impl std::io::Write for RecordingSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writes.push(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flushed_at.push(self.writes.len());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_stream_symbols_writes_each_item_and_flushes_per_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "pub fn one() {}\npub fn two() {}\n").unwrap();
+    std::fs::write(dir.path().join("b.rs"), "pub fn three() {}\n").unwrap();
+    let options = ProcessOptions { format: OutputFormat::Json, per_symbol_stream: true, ..Default::default() };
+    let mut sink = RecordingSink::default();
+    codeview::stream_symbols(dir.path().to_str().unwrap(), options, &mut sink).unwrap();
+
+    let names: Vec<String> = sink
+        .writes
+        .iter()
+        .map(|w| serde_json::from_slice::<serde_json::Value>(w).unwrap()["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["one", "two", "three"]);
+    // a.rs is flushed before b.rs is written
+    assert_eq!(sink.flushed_at, [2, 3]);
+}
+
+#[test]
+fn test_stream_symbols_reports_truncation_and_stops_at_limit() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.rs"), "pub fn one() {}\npub fn two() {}\n").unwrap();
+    std::fs::write(dir.path().join("b.rs"), "pub fn three() {}\n").unwrap();
+    std::fs::write(dir.path().join("c.rs"), "pub fn four() {}\n").unwrap();
+    let root = dir.path().to_str().unwrap();
+    let stream = |options: ProcessOptions| {
+        let mut out = Vec::new();
+        codeview::stream_symbols(root, ProcessOptions { format: OutputFormat::Json, per_symbol_stream: true, ..options }, &mut out).unwrap();
+        String::from_utf8(out).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect::<Vec<serde_json::Value>>()
+    };
+
+    let records = stream(ProcessOptions { max_symbols: Some(1), ..Default::default() });
+    let names: Vec<&str> = records.iter().filter_map(|r| r["name"].as_str()).collect();
+    assert_eq!(names, ["one", "three", "four"]);
+    assert_eq!(records.last().unwrap(), &serde_json::json!({ "omitted": 1 }));
+
+    // The limit is used up after b.rs, so c.rs is never parsed
+    let records = stream(ProcessOptions { limit: Some(3), ..Default::default() });
+    assert_eq!(records.len(), 4);
+    assert_eq!(records.last().unwrap(), &serde_json::json!({ "omitted": 0, "files_not_scanned": 1 }));
+
+    let records = stream(ProcessOptions::default());
+    assert!(records.iter().all(|r| r.get("file").is_some()));
+}
+
+#[test]
+fn test_with_mappings_aligns_collapsed_lines_with_source_lines() {
+    let options = ProcessOptions { format: OutputFormat::Json, with_mappings: true, ..Default::default() };
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(FIXTURE_PATH, options).unwrap()).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+    let user_impl = items.iter().find(|i| i["kind"] == "impl").unwrap();
+    let mappings = user_impl["line_mappings"].as_array().unwrap();
+    assert_eq!(mappings[0], serde_json::json!([11, "impl User {"]));
+    assert_eq!(mappings[1], serde_json::json!([12, "    pub fn new(name: String, age: u32, email: String) -> Self { ... }"]));
+    // The collapsed `new` body spans lines 12-14, so the next output line is source line 15
+    assert_eq!(mappings[2][0], 15);
+    assert_eq!(mappings[3], serde_json::json!([16, "    pub fn greeting(&self) -> String { ... }"]));
+    assert_eq!(mappings.len(), user_impl["content"].as_str().unwrap().lines().count());
+
+    let options = ProcessOptions { format: OutputFormat::Json, ..Default::default() };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+    assert!(!output.contains("line_mappings"));
+}
+
+#[test]
+fn test_grep_defs_prints_one_signature_per_definition() {
+    let options = ProcessOptions { grep_defs: true, ..Default::default() };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+    let line = |n: usize, signature: &str| format!("{}:{}: {}", FIXTURE_PATH, n, signature);
+
+    assert!(output.lines().any(|l| l == line(5, "pub struct User")), "got:\n{output}");
+    assert!(output.contains(&line(12, "pub fn new(name: String, age: u32, email: String) -> Self")));
+    assert!(output.contains(&line(45, "pub fn public_utility(input: &str) -> String")));
+    assert!(output.contains(&line(37, "pub const MAX_USERS: usize = 1000")));
+    assert!(!output.contains("{ ... }") && !output.contains("use "), "got:\n{output}");
+
+    let py = tempfile::Builder::new().suffix(".py").tempfile().unwrap();
+    std::fs::write(py.path(), "class Repo(Base):\n    def find(self, key: str) -> int:\n        return 0\n").unwrap();
+    let path = py.path().to_str().unwrap();
+    let output = process_path(path, ProcessOptions { grep_defs: true, ..Default::default() }).unwrap();
+    assert_eq!(output, format!("{path}:1: class Repo(Base)\n{path}:2: def find(self, key: str) -> int\n"));
+
+    // Outside --grep-defs Python methods stay inside their class
+    let output = process_path(path, ProcessOptions { format: OutputFormat::Json, ..Default::default() }).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let kinds: Vec<&str> = parsed["files"][0]["items"].as_array().unwrap().iter().map(|i| i["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, ["class"]);
+}
+
+#[test]
+fn test_grep_defs_rereads_files_lossily() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let bad = dir.path().join("bad.rs");
+    std::fs::write(&bad, b"// caf\xe9\nfn ok() {}\n").unwrap();
+
+    let output = process_path(dir.path().to_str().unwrap(), ProcessOptions { grep_defs: true, lossy: true, ..Default::default() }).unwrap();
+    assert_eq!(output, format!("{}:2: fn ok()\n", bad.display()));
+}
+
+#[test]
+fn test_markdown_output_sections_and_fences() {
+    let options = ProcessOptions { format: OutputFormat::Markdown, ..Default::default() };
+    let output = process_path("tests/fixtures/test_impl.rs", options).unwrap();
+
+    assert!(output.starts_with("## tests/fixtures/test_impl.rs\n\n- `struct User` (L2-5)\n"), "got:\n{output}");
+    assert!(output.contains("- `trait Greeter` (L21-23)\n"));
+    assert!(output.contains("```rust\npub struct User {\n"));
+    assert_eq!(output.matches("```rust\n").count(), 4);
+}
+
+#[test]
+fn test_outline_nests_methods_under_impl() {
+    let options = ProcessOptions { format: OutputFormat::Outline, ..Default::default() };
+    let output = process_path("tests/fixtures/test_impl.rs", options).unwrap();
+
+    let expected = [
+        "tests/fixtures/test_impl.rs",
+        "├─ struct User  L2-5",
+        "│ ├─ field name  L3",
+        "│ └─ field age  L4",
+        "├─ impl User  L7-19",
+        "│ ├─ fn new  L8-10",
+        "│ ├─ fn validate  L12-14",
+        "│ └─ fn display  L16-18",
+        "├─ trait Greeter  L21-23",
+        "└─ impl Greeter  L25-29",
+        "  └─ fn greet  L26-28",
+    ];
+    assert_eq!(output, expected.join("\n") + "\n\n");
+}
+
+#[test]
+fn test_outline_indent_sets_nesting_width() {
+    let options = ProcessOptions { format: OutputFormat::Outline, indent: Some(4), ..Default::default() };
+    let output = process_path("tests/fixtures/test_impl.rs", options).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[1], "├─── struct User  L2-5");
+    assert_eq!(lines[2], "│   ├─── field name  L3");
+    assert_eq!(lines[10], "    └─── fn greet  L26-28");
+    let column = |line: &str, label: &str| line[..line.find(label).unwrap()].chars().count();
+    assert_eq!(column(lines[2], "field"), column(lines[1], "struct") + 4);
+}
+
+#[test]
+fn test_tags_sorted_with_patterns_and_kinds() {
+    let options = ProcessOptions { format: OutputFormat::Ctags, ..Default::default() };
+    let output = process_path(FIXTURE_DIR, options).unwrap();
+
+    let tags: Vec<&str> = output.lines().filter(|l| !l.starts_with("!_TAG_")).collect();
+    let mut names: Vec<&str> = tags.iter().map(|l| l.split('\t').next().unwrap()).collect();
+    let in_scan_order = names.clone();
+    names.sort();
+    assert_eq!(names, in_scan_order, "tags must be sorted by name");
+    assert!(tags.contains(&"validate_email\ttests/fixtures/sample.rs\t/^    fn validate_email(&self) -> bool {$/;\"\tm"), "got:\n{output}");
+    assert!(tags.contains(&"User\ttests/fixtures/sample.rs\t/^pub struct User {$/;\"\ts"));
+    assert!(tags.contains(&"Authenticatable\ttests/fixtures/sample.rs\t/^pub trait Authenticatable {$/;\"\tt"));
+}
+
+#[test]
+fn test_xml_output_escapes_content() {
+    let options = ProcessOptions { format: OutputFormat::Xml, fns_only: true, ..Default::default() };
+    let output = process_path(FIXTURE_PATH, options).unwrap();
+
+    assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<codeview>\n  <file path=\""), "got:\n{output}");
+    assert!(output.contains("<item kind=\"function\" name=\"public_utility\" visibility=\"public\" line_start=\"45\" line_end=\"47\""));
+    assert!(output.contains("<parameter name=\"input\" type=\"&amp;str\" kind=\"concrete\"/>"));
+    assert!(output.contains("<content>pub fn public_utility(input: &amp;str) -&gt; String { ... }</content>"));
+    assert!(output.trim_end().ends_with("</codeview>"));
+}
+
+#[test]
+fn test_xml_rejects_stats() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args([FIXTURE_PATH, "--xml", "--stats"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+}
+
+#[test]
+fn test_merge_separates_files_with_banners() {
+    let options = ProcessOptions { merge: true, pub_only: true, ..Default::default() };
+    let output = process_path(FIXTURE_DIR, options).unwrap();
+
+    assert!(output.starts_with("// merged: 3 files, 9 items\n\n// ==== tests/fixtures/sample.rs ====\n"), "got:\n{output}");
+    assert_eq!(output.matches("// ==== ").count(), 3);
+    assert!(output.contains("\n\n// ==== tests/fixtures/test_impl.rs ====\n"));
+    assert!(!output.lines().any(|l| l == "tests/fixtures/sample.rs"), "no plain per-file headers");
+}
+
+#[test]
+fn test_markdown_stats_table() {
+    let options = ProcessOptions { format: OutputFormat::Markdown, stats: true, ..Default::default() };
+    let output = process_path(FIXTURE_DIR, options).unwrap();
+
+    assert!(output.starts_with("| file | lines | items |\n|------|------:|------:|\n"));
+    assert!(output.contains("| tests/fixtures/test_impl.rs | 29 | 4 |\n"), "got:\n{output}");
+    assert!(output.lines().last().unwrap().starts_with("| **total** |"));
+}
+
+#[test]
+fn test_glob_and_exclude_filter_directory_walk() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("src/generated")).unwrap();
+    std::fs::write(dir.path().join("src/lib.rs"), "pub fn kept() {}\n").unwrap();
+    std::fs::write(dir.path().join("src/generated/api.rs"), "pub fn generated() {}\n").unwrap();
+    std::fs::write(dir.path().join("tool.py"), "def other():\n    pass\n").unwrap();
+
+    let output = process_path(
+        dir.path().to_str().unwrap(),
+        ProcessOptions {
+            include_globs: vec!["src/**".to_string()],
+            exclude_globs: vec!["**/generated/**".to_string()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(output.contains("pub fn kept()"), "got:\n{output}");
+    assert!(!output.contains("generated()") && !output.contains("other"), "got:\n{output}");
+}
+
+#[test]
+fn test_signature_of_returns_only_the_signature() {
+    let signature = codeview::signature_of(FIXTURE_PATH, "public_utility").unwrap();
+    assert_eq!(signature, "pub fn public_utility(input: &str) -> String");
+
+    let err = codeview::signature_of(FIXTURE_PATH, "no_such_symbol").unwrap_err();
+    assert!(err.to_string().contains("Symbol not found: no_such_symbol"));
+}
+
+#[test]
+fn test_body_of_returns_only_the_body() {
+    let body = codeview::body_of(FIXTURE_PATH, "greeting").unwrap();
+    assert_eq!(body, "format!(\"Hello, {}!\", self.name)\n");
+
+    let err = codeview::body_of(FIXTURE_PATH, "no_such_symbol").unwrap_err();
+    assert!(err.to_string().contains("no_such_symbol"), "{err}");
+}
+
+#[test]
+fn test_process_source_reads_in_memory_buffer() {
+    let source = "pub fn unsaved(x: u32) -> u32 {\n    x + 1\n}\n";
+    let output = codeview::process_source(source, codeview::Language::Rust, ProcessOptions::default()).unwrap();
+    assert_eq!(output, "<stdin>\n1 | pub fn unsaved(x: u32) -> u32 { ... }\n\n");
+
+    let options = ProcessOptions { symbols: vec!["unsaved".to_string()], ..Default::default() };
+    let output = codeview::process_source(source, codeview::Language::Rust, options).unwrap();
+    assert!(output.contains("2 |     x + 1"), "got:\n{output}");
+}
+
+#[test]
+fn test_stdin_flag_pipes_source_through_cli() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["--stdin", "--lang", "py", "greet"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"def greet(name):\n    return name\n\ndef other():\n    pass\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("<stdin>::greet [1:2]\n"), "got:\n{stdout}");
+    assert!(stdout.contains("return name") && !stdout.contains("other"), "got:\n{stdout}");
+}
+
+#[test]
+fn test_kinds_legend_lists_language_names() {
+    let legend = codeview::kinds_legend(false).unwrap();
+    let trait_row = legend.lines().find(|l| l.starts_with("trait ")).expect("trait row");
+    assert!(trait_row.contains("interface"), "TS shows traits as interfaces: {}", trait_row);
+    assert_eq!(legend.lines().count(), 16, "header plus one row per kind:\n{}", legend);
+
+    let json: serde_json::Value = serde_json::from_str(&codeview::kinds_legend(true).unwrap()).unwrap();
+    let kinds = json["kinds"].as_array().unwrap();
+    let trait_kind = kinds.iter().find(|k| k["kind"] == "trait").unwrap();
+    assert_eq!(trait_kind["names"]["typescript"], "interface");
+    assert_eq!(trait_kind["names"]["rust"], "trait");
+    assert!(trait_kind["names"].get("python").is_none());
+}
+
+#[test]
+fn test_git_modified_processes_only_changed_files() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(["-c", "user.name=Tester", "-c", "user.email=tester@example.com"])
+            .args(args)
+            .output()
+            .expect("failed to run git");
+        assert!(output.status.success(), "git {:?} failed", args);
+    };
+    std::fs::write(dir.path().join("changed.rs"), "fn changed() {}\n").unwrap();
+    std::fs::write(dir.path().join("clean.rs"), "fn clean() {}\n").unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+    std::fs::write(dir.path().join("changed.rs"), "fn changed() {}\n\nfn added() {}\n").unwrap();
+
+    let opts = ProcessOptions { git_modified: true, ..Default::default() };
+    let output = process_path(dir.path().to_str().unwrap(), opts).unwrap();
+    assert!(output.contains("fn added"), "got:\n{}", output);
+    assert!(!output.contains("clean"), "unchanged file processed:\n{}", output);
+
+    // Outside a repository nothing is reported as modified, with a warning
+    let plain = tempfile::TempDir::new().unwrap();
+    std::fs::write(plain.path().join("lib.rs"), "fn lib() {}\n").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .arg(plain.path())
+        .arg("--git-modified")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("fn lib"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Warning: not a git repository"), "stderr:\n{}", stderr);
+}
+
+#[test]
+fn test_template_renders_one_line_per_item() {
+    let opts = ProcessOptions {
+        template: Some(codeview::Template::parse("{kind} {name} ({file}:{line_start}-{line_end}) {visibility}").unwrap()),
+        fns_only: true,
+        ..Default::default()
+    };
+    let output = process_path("tests/fixtures/sample.rs", opts).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines.contains(&"function private_helper (tests/fixtures/sample.rs:41-43) private"), "got:\n{}", output);
+    assert!(lines.contains(&"function public_utility (tests/fixtures/sample.rs:45-47) public"), "got:\n{}", output);
+    assert!(!output.contains(" | "), "no source lines in template output:\n{}", output);
+}
@@ -16,6 +16,7 @@ fn opts() -> ProcessOptions {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     }
 
 }
@@ -296,3 +297,17 @@ fn javascript_stats_mode() {
     assert!(output.contains("lines:"), "Missing lines count");
     assert!(output.contains("items:"), "Missing items count");
 }
+
+// --- Re-exports ---
+
+#[test]
+fn js_barrel_reexports_listed() {
+    let f = write_js("export { a, b } from './ab.js';\nexport * from './rest.js';\nexport { local };\nconst local = 1;\n");
+    let mut o = opts();
+    o.list_symbols = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("use a, b"), "Missing named re-export: {}", output);
+    assert!(output.contains("use *"), "Missing star re-export: {}", output);
+    assert!(output.contains("use local"), "Missing local export list: {}", output);
+}
@@ -34,9 +34,27 @@ fn test_edit_result_serialization() {
         action: "replaced".to_string(),
         line_start: 1,
         line_end: 3,
+        diff: None,
     };
     let json = serde_json::to_string(&result).unwrap();
     assert!(json.contains("symbol"));
     assert!(json.contains("foo"));
     assert!(json.contains("line_start"));
 }
+
+#[test]
+fn test_json_errors_reports_unsupported_extension_on_stdout() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("notes.zzz");
+    std::fs::write(&path, "hello\n").unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args([path.to_str().unwrap(), "--json", "--json-errors"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stderr.is_empty());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(parsed["error"]["kind"], "unsupported_extension");
+    assert_eq!(parsed["error"]["message"], "Unsupported file extension: zzz");
+}
@@ -103,3 +103,31 @@ fn lines_zero_start_errors() {
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("1-indexed"));
 }
+
+#[test]
+fn annotate_symbols_nested_method_has_full_path() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(
+        &dir,
+        "svc.ts",
+        "export class Service {\n  run() {\n    return 1;\n  }\n}\n\nconst x = 2;\n",
+    );
+    let result = codeview::annotate_symbols(&path).unwrap();
+    let lines: Vec<&str> = result.lines().collect();
+
+    assert_eq!(lines.len(), 7);
+    assert_eq!(lines[2], "L3 [Service > run()]:     return 1;");
+    assert_eq!(lines[0], "L1 [Service]: export class Service {");
+    assert_eq!(lines[5], "L6 []: ");
+    assert_eq!(lines[6], "L7 [x]: const x = 2;");
+}
+
+#[test]
+fn annotate_symbols_matches_per_line_lookup() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "lib.rs", "mod inner {\n    impl S {\n        fn m(&self) {\n            todo!()\n        }\n    }\n}\n");
+    let result = codeview::annotate_symbols(&path).unwrap();
+    assert!(result.contains("L4 [inner > impl S > m]:             todo!()"), "{}", result);
+    let context = codeview::extract_lines(&path, "4-4").unwrap();
+    assert!(context.contains("// Inside: inner > impl S > m"));
+}
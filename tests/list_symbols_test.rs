@@ -17,6 +17,7 @@ fn default_options() -> ProcessOptions {
         signatures: false,
         max_lines: None,
         list_symbols: true,
+        ..Default::default()
     }
 }
 
@@ -133,3 +134,82 @@ fn test_list_symbols_directory() {
     // Directory mode should work
     assert!(!output.is_empty());
 }
+
+#[test]
+fn test_list_symbols_show_parents_prefixes_methods() {
+    let mut opts = default_options();
+    opts.fns_only = true;
+    opts.show_parents = true;
+    let output = process_path(FIXTURE_PATH, opts).unwrap();
+    assert!(output.contains("fn User::greeting"), "Method should have parent prefix: {}", output);
+    assert!(output.contains("fn private_helper"), "Free functions stay unqualified: {}", output);
+    assert!(!output.contains("::private_helper"));
+}
+
+#[test]
+fn test_list_symbols_show_parents_typescript() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("svc.ts");
+    std::fs::write(&path, "export class UserService {\n    getUser(id: string) {\n        return id;\n    }\n}\n").unwrap();
+    let mut opts = default_options();
+    opts.fns_only = true;
+    opts.show_parents = true;
+    let output = process_path(path.to_str().unwrap(), opts).unwrap();
+    assert!(output.contains("fn UserService > getUser"), "Missing class prefix: {}", output);
+}
+
+#[test]
+fn test_list_symbols_show_parents_prefixes_fields() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("point.rs");
+    std::fs::write(&path, "pub struct Point {\n    x: f64,\n    y: f64,\n}\n").unwrap();
+    let mut opts = default_options();
+    opts.show_parents = true;
+    let output = process_path(path.to_str().unwrap(), opts).unwrap();
+    assert!(output.contains("struct Point "), "Struct stays unqualified: {}", output);
+    assert!(output.contains("field Point::x "), "Missing struct prefix: {}", output);
+    assert!(output.contains("field Point::y "), "Missing struct prefix: {}", output);
+}
+
+#[test]
+fn test_list_symbols_show_parents_prefixes_variants() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("shape.rs");
+    std::fs::write(&path, "pub enum Shape {\n    Circle(f64),\n    Rect { w: f64, h: f64 },\n}\n").unwrap();
+    let mut opts = default_options();
+    opts.show_parents = true;
+    let output = process_path(path.to_str().unwrap(), opts).unwrap();
+    assert!(output.contains("variant Shape::Circle "), "Missing enum prefix: {}", output);
+    assert!(output.contains("variant Shape::Rect "), "Missing enum prefix: {}", output);
+}
+
+#[test]
+fn test_list_symbols_fields_with_types() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let cases = [
+        ("cfg.rs", "pub struct Config {\n    pub timeout: u64,\n    name: String,\n}\n", "pub timeout: u64"),
+        ("cfg.ts", "export class Config {\n  private timeout: number;\n}\ninterface Opts {\n  retries?: number;\n}\n", "private timeout: number"),
+        ("cfg.py", "class Config:\n    timeout: int = 30\n    label = 'x'\n", "timeout: int = 30"),
+    ];
+    for (file, source, declaration) in cases {
+        let path = dir.path().join(file);
+        std::fs::write(&path, source).unwrap();
+        let output = process_path(path.to_str().unwrap(), default_options()).unwrap();
+        let line = output.lines().find(|l| l.contains("field timeout")).unwrap_or_else(|| panic!("no timeout field in {}:\n{}", file, output));
+        assert!(line.ends_with(declaration), "{}: {}", file, line);
+    }
+
+    let ts = process_path(dir.path().join("cfg.ts").to_str().unwrap(), default_options()).unwrap();
+    assert!(ts.contains("field retries"), "interface properties are fields:\n{}", ts);
+    let py = process_path(dir.path().join("cfg.py").to_str().unwrap(), default_options()).unwrap();
+    assert!(!py.contains("field label"), "unannotated class attributes are not fields:\n{}", py);
+
+    let mut opts = default_options();
+    opts.list_symbols = false;
+    opts.format = OutputFormat::Json;
+    let json: serde_json::Value = serde_json::from_str(&process_path(dir.path().join("cfg.rs").to_str().unwrap(), opts).unwrap()).unwrap();
+    let name = json["files"][0]["items"].as_array().unwrap().iter().find(|i| i["name"] == "name").unwrap();
+    assert_eq!(name["kind"], "field");
+    assert_eq!(name["visibility"], "private");
+    assert_eq!(name["parent"], "Config");
+}
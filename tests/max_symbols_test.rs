@@ -0,0 +1,130 @@
+use codeview::{process_path, ProcessOptions};
+use std::fs;
+use tempfile::TempDir;
+
+fn many_functions(dir: &TempDir, count: usize) -> String {
+    let mut source = String::new();
+    for i in 0..count {
+        source.push_str(&format!("fn generated_{}() {{}}\n\n", i));
+    }
+    let path = dir.path().join("generated.rs");
+    fs::write(&path, source).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_max_symbols_caps_items_with_marker() {
+    let dir = TempDir::new().unwrap();
+    let path = many_functions(&dir, 10);
+    let options = ProcessOptions {
+        max_symbols: Some(3),
+        ..Default::default()
+    };
+    let output = process_path(&path, options).unwrap();
+    assert!(output.contains("fn generated_0()"));
+    assert!(output.contains("fn generated_2()"));
+    assert!(!output.contains("fn generated_3()"));
+    assert!(output.contains("// ... and 7 more symbols"), "missing marker: {}", output);
+}
+
+#[test]
+fn test_max_symbols_no_marker_when_under_cap() {
+    let dir = TempDir::new().unwrap();
+    let path = many_functions(&dir, 2);
+    let options = ProcessOptions {
+        max_symbols: Some(5),
+        ..Default::default()
+    };
+    let output = process_path(&path, options).unwrap();
+    assert!(output.contains("fn generated_1()"));
+    assert!(!output.contains("more symbols"));
+}
+
+#[test]
+fn test_max_symbols_applies_after_filtering() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("mixed.rs");
+    fs::write(&path, "fn a() {}\npub fn b() {}\nfn c() {}\npub fn d() {}\npub fn e() {}\n").unwrap();
+    let options = ProcessOptions {
+        pub_only: true,
+        max_symbols: Some(2),
+        list_symbols: true,
+        ..Default::default()
+    };
+    let output = process_path(path.to_str().unwrap(), options).unwrap();
+    assert!(output.contains(" b "));
+    assert!(output.contains(" d "));
+    assert!(!output.contains(" e "));
+    assert!(output.contains("// ... and 1 more symbols"));
+}
+
+#[test]
+fn test_limit_caps_items_across_files() {
+    let dir = TempDir::new().unwrap();
+    for name in ["a", "b", "c"] {
+        let source: String = (0..4).map(|i| format!("fn {}_{}() {{}}\n\n", name, i)).collect();
+        fs::write(dir.path().join(format!("{}.rs", name)), source).unwrap();
+    }
+    let options = ProcessOptions {
+        limit: Some(6),
+        ..Default::default()
+    };
+    let output = process_path(dir.path().to_str().unwrap(), options).unwrap();
+    assert!(output.contains("fn a_3()"));
+    assert!(output.contains("fn b_1()"));
+    assert!(!output.contains("fn b_2()"));
+    assert!(!output.contains("c.rs"), "fully cut file should be dropped: {}", output);
+    assert!(output.ends_with("\n... 6 more items across 2 files\n"), "missing summary: {}", output);
+}
+
+#[test]
+fn test_max_symbols_zero_still_reports_omitted() {
+    let dir = TempDir::new().unwrap();
+    let path = many_functions(&dir, 4);
+    let options = ProcessOptions { max_symbols: Some(0), ..Default::default() };
+    let output = process_path(&path, options).unwrap();
+    assert_eq!(output, format!("{}\n// ... and 4 more symbols\n", path));
+
+    let options = ProcessOptions { max_symbols: Some(0), list_symbols: true, ..Default::default() };
+    let output = process_path(&path, options).unwrap();
+    assert_eq!(output, format!("{}\n// ... and 4 more symbols\n", path));
+}
+
+#[test]
+fn test_max_symbols_json_reports_omitted_count() {
+    let dir = TempDir::new().unwrap();
+    let path = many_functions(&dir, 4);
+    let options = ProcessOptions { max_symbols: Some(0), format: codeview::OutputFormat::Json, ..Default::default() };
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(&path, options).unwrap()).unwrap();
+    assert_eq!(parsed["files"][0]["items"].as_array().unwrap().len(), 0);
+    assert_eq!(parsed["files"][0]["omitted"], 4);
+
+    let options = ProcessOptions { format: codeview::OutputFormat::Json, ..Default::default() };
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(&path, options).unwrap()).unwrap();
+    assert!(parsed["files"][0].get("omitted").is_none());
+}
+
+#[test]
+fn test_limit_recorded_in_json_xml_and_tags() {
+    let dir = TempDir::new().unwrap();
+    for name in ["a", "b", "c"] {
+        let source: String = (0..4).map(|i| format!("fn {}_{}() {{}}\n\n", name, i)).collect();
+        fs::write(dir.path().join(format!("{}.rs", name)), source).unwrap();
+    }
+    let root = dir.path().to_str().unwrap();
+    let limited = |format| ProcessOptions { limit: Some(6), format, ..Default::default() };
+
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(root, limited(codeview::OutputFormat::Json)).unwrap()).unwrap();
+    assert_eq!(parsed["truncated"], serde_json::json!({ "omitted": 6, "files": 2 }));
+    assert_eq!(parsed["files"].as_array().unwrap().len(), 2);
+
+    let xml = process_path(root, limited(codeview::OutputFormat::Xml)).unwrap();
+    assert!(xml.ends_with("  <truncated omitted=\"6\" files=\"2\"/>\n</codeview>\n"), "got:\n{}", xml);
+
+    let tags = process_path(root, limited(codeview::OutputFormat::Ctags)).unwrap();
+    assert!(tags.contains("!_TAG_OMITTED\t6\t/items cut by --limit, across 2 files/\n"), "got:\n{}", tags);
+
+    let unlimited = ProcessOptions { format: codeview::OutputFormat::Json, ..Default::default() };
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(root, unlimited).unwrap()).unwrap();
+    assert!(parsed.get("truncated").is_none());
+}
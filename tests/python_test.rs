@@ -16,6 +16,7 @@ fn opts() -> ProcessOptions {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     }
 
 }
@@ -85,6 +86,29 @@ fn python_interface_mode_basic() {
     assert!(output.contains("{ ... }"), "Missing collapsed bodies");
 }
 
+#[test]
+fn python_interface_keeps_decorators_with_arguments() {
+    let f = write_py("@app.route(\"/users\", methods=[\"GET\", \"POST\"])\n@login_required\ndef users(request):\n    return list_users(request)\n\n@dataclass(\n    frozen=True,\n)\nclass Point:\n    x: int\n");
+    let output = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+    assert!(
+        output.contains("1 | @app.route(\"/users\", methods=[\"GET\", \"POST\"])\n2 | @login_required\n3 | def users(request):\n4 |     { ... }"),
+        "Decorators not rendered in full:\n{}", output
+    );
+    assert!(output.contains("@dataclass(\n") && output.contains("frozen=True,") && output.contains("class Point:"), "{}", output);
+    assert!(!output.contains("list_users"), "Body not collapsed:\n{}", output);
+}
+
+#[test]
+fn python_docs_shows_first_docstring_line() {
+    let f = write_py("def users(request):\n    \"\"\"List users.\n\n    Paginated.\n    \"\"\"\n    return 1\n\ndef bare():\n    pass\n");
+    let mut o = opts();
+    o.docs = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    assert!(output.contains("def users(request):\n2 |     \"\"\"List users.\"\"\"\n3 |     { ... }"), "got:\n{}", output);
+    assert!(!output.contains("Paginated"), "got:\n{}", output);
+    assert!(output.contains("def bare():\n"), "got:\n{}", output);
+}
+
 // --- Expand mode ---
 
 #[test]
@@ -269,3 +293,87 @@ fn python_expand_nonexistent() {
     let output = process_path(f.path().to_str().unwrap(), o).unwrap();
     assert!(!output.contains("def "), "Should not contain any functions");
 }
+
+// --- Nest summary ---
+
+const NESTED_PY: &str = r#"def outer(x):
+    def helper(y):
+        z = y * 2
+        return z
+
+    return helper(x)
+"#;
+
+#[test]
+fn py_nest_summary_collapses_inner_def() {
+    let f = write_py(NESTED_PY);
+    let mut o = opts();
+    o.symbols = vec!["outer".to_string()];
+    o.nest_summary = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("def helper(y):"), "Inner signature should stay: {}", output);
+    assert!(output.contains("3 |         ..."), "Inner body should collapse: {}", output);
+    assert!(!output.contains("z = y * 2"), "Inner body should be hidden: {}", output);
+    assert!(output.contains("6 |     return helper(x)"), "Outer body should stay visible: {}", output);
+}
+
+#[test]
+fn py_expand_without_nest_summary_keeps_inner_body() {
+    let f = write_py(NESTED_PY);
+    let mut o = opts();
+    o.symbols = vec!["outer".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("z = y * 2"), "Inner body should be shown by default: {}", output);
+}
+
+// --- Test detection ---
+
+#[test]
+fn py_test_functions_flagged() {
+    let f = write_py("def test_addition():\n    assert 1 + 1 == 2\n\ndef add(a, b):\n    return a + b\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let test_fn = items.iter().find(|i| i["name"] == "test_addition").unwrap();
+    assert_eq!(test_fn["is_test"], true);
+    let add = items.iter().find(|i| i["name"] == "add").unwrap();
+    assert_eq!(add["is_test"], false);
+}
+
+// --- Modifiers ---
+
+#[test]
+fn py_decorator_and_async_modifiers_in_json() {
+    let f = write_py("import abc\n\nclass Repo:\n    @staticmethod\n    def create():\n        return Repo()\n\n    @abc.abstractmethod\n    def name(self):\n        ...\n\nasync def fetch():\n    pass\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    o.symbols = vec!["create".to_string(), "name".to_string(), "fetch".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let find = |name: &str| items.iter().find(|i| i["name"] == name).unwrap();
+    assert_eq!(find("create")["is_static"], true);
+    assert_eq!(find("name")["is_abstract"], true);
+    assert_eq!(find("fetch")["is_async"], true);
+}
+
+#[test]
+fn py_decorator_names_in_json() {
+    let f = write_py("@dataclass(frozen=True)\nclass Point:\n    x: int\n\n@app.route(\"/\")\ndef index():\n    pass\n\ndef plain():\n    pass\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let find = |name: &str| items.iter().find(|i| i["name"] == name).unwrap();
+    assert_eq!(find("Point")["decorators"], serde_json::json!(["dataclass"]));
+    assert_eq!(find("index")["decorators"], serde_json::json!(["app.route"]));
+    assert!(find("plain").get("decorators").is_none());
+}
@@ -0,0 +1,52 @@
+use codeview::xref::{self, RefOptions};
+use std::fs;
+use tempfile::TempDir;
+
+fn write_file(dir: &TempDir, name: &str, content: &str) {
+    fs::write(dir.path().join(name), content).unwrap();
+}
+
+#[test]
+fn test_refs_report_enclosing_functions() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "model.rs", "pub struct User {\n    pub name: String,\n}\n");
+    write_file(&dir, "service.rs", "fn load() -> User {\n    User { name: String::new() }\n}\n\nfn save(user: &User) {}\n\nfn unrelated() {}\n");
+
+    let refs = xref::find_references("User", dir.path().to_str().unwrap(), &RefOptions::default()).unwrap();
+
+    let found: Vec<(usize, Vec<String>)> = refs.iter().map(|r| (r.line, r.symbol_path.clone())).collect();
+    assert_eq!(found, vec![
+        (1, vec!["load".to_string()]),
+        (2, vec!["load".to_string()]),
+        (5, vec!["save".to_string()]),
+    ]);
+    assert!(refs.iter().all(|r| r.path.ends_with("service.rs")), "the definition is excluded: {:?}", refs);
+}
+
+#[test]
+fn test_refs_include_definitions() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "lib.py", "class User:\n    pass\n\ndef make():\n    return User()\n");
+
+    let options = RefOptions { include_definitions: true, ..RefOptions::default() };
+    let refs = xref::find_references("User", dir.path().to_str().unwrap(), &options).unwrap();
+
+    let lines: Vec<usize> = refs.iter().map(|r| r.line).collect();
+    assert_eq!(lines, vec![1, 5]);
+}
+
+#[test]
+fn test_refs_cli_output() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "app.ts", "export class User {}\n\nexport function create(): User {\n  return new User();\n}\n");
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["refs", "User", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("app.ts"));
+    assert!(stdout.contains("  L3 in create(): export function create(): User {"), "got:\n{stdout}");
+    assert!(stdout.contains("  L4 in create(): return new User();"), "got:\n{stdout}");
+    assert!(!stdout.contains("L1"), "the definition is excluded:\n{stdout}");
+}
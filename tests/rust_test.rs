@@ -0,0 +1,597 @@
+use codeview::{process_path, FilterExpr, ProcessOptions};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+fn opts() -> ProcessOptions {
+    ProcessOptions::default()
+}
+
+fn write_rs(content: &str) -> NamedTempFile {
+    let mut f = tempfile::Builder::new().suffix(".rs").tempfile().unwrap();
+    f.write_all(content.as_bytes()).unwrap();
+    f.flush().unwrap();
+    f
+}
+
+// --- Trait headers ---
+
+#[test]
+fn rs_trait_supertraits_in_interface_header() {
+    let src = r#"pub trait Foo: Clone + Debug {
+    fn name(&self) -> String {
+        String::new()
+    }
+    fn id(&self) -> u32;
+}
+"#;
+    let f = write_rs(src);
+    let output = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+
+    assert!(output.contains("pub trait Foo: Clone + Debug {"), "Missing supertrait bounds: {}", output);
+    assert!(output.contains("fn name(&self) -> String { ... }"), "Default method should collapse");
+}
+
+#[test]
+fn rs_type_signatures_show_generics_and_supertraits() {
+    let src = "#[derive(Clone)]\npub struct Foo<T: Clone> {\n    x: T,\n}\n\npub struct Pair<T>(T, T) where T: Copy;\n\npub trait Bar: Baz + Send {\n    fn f(&self);\n}\n\nstruct Plain;\n";
+    let f = write_rs(src);
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(f.path().to_str().unwrap(), o).unwrap()).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+    let signature = |name: &str| items.iter().find(|i| i["name"] == name).unwrap()["signature"].clone();
+    assert_eq!(signature("Foo"), "pub struct Foo<T: Clone>");
+    assert_eq!(signature("Pair"), "pub struct Pair<T> where T: Copy");
+    assert_eq!(signature("Bar"), "pub trait Bar: Baz + Send");
+    assert_eq!(signature("Plain"), "struct Plain");
+
+    let mut o = opts();
+    o.list_symbols = true;
+    let listing = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let line = |name: &str| listing.lines().find(|l| l.contains(name)).unwrap().to_string();
+    assert!(line("struct Foo").ends_with("pub struct Foo<T: Clone>"), "{listing}");
+    assert!(line("trait Bar").ends_with("pub trait Bar: Baz + Send"), "{listing}");
+    assert!(line("struct Plain").ends_with("L12"), "plain types list without a header: {listing}");
+}
+
+#[test]
+fn rs_derives_in_json() {
+    let f = write_rs("#[derive(Debug, Clone, serde::Serialize)]\n#[serde(rename_all = \"camelCase\")]\npub struct Foo {\n    x: u32,\n}\n\n#[inline]\nfn plain() {}\n");
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(f.path().to_str().unwrap(), o).unwrap()).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+    let find = |name: &str| items.iter().find(|i| i["name"] == name).unwrap();
+    assert_eq!(find("Foo")["decorators"], serde_json::json!(["Debug", "Clone", "serde::Serialize"]));
+    assert!(find("plain").get("decorators").is_none(), "non-derive attributes are not listed");
+}
+
+#[test]
+fn rs_trait_generic_bounds_and_where_clause_in_header() {
+    let src = "trait Convert<T>: Into<T> where T: Copy {\n    fn go(&self) -> T;\n}\n";
+    let f = write_rs(src);
+    let output = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+
+    assert!(output.contains("trait Convert<T>: Into<T> where T: Copy {"), "Missing bounds: {}", output);
+}
+
+// --- Preview ---
+
+#[test]
+fn rs_preview_shows_first_statements_then_marker() {
+    let src = r#"pub fn run(x: u32) -> u32 {
+    let a = x + 1;
+    let b = a * 2;
+    let c = b - 3;
+    c
+}
+"#;
+    let f = write_rs(src);
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { preview: Some(2), ..opts() },
+    )
+    .unwrap();
+
+    assert!(output.contains("2 |     let a = x + 1;"), "Missing first statement: {}", output);
+    assert!(output.contains("3 |     let b = a * 2;"), "Missing second statement: {}", output);
+    assert!(output.contains("4 |     ..."), "Missing preview marker: {}", output);
+    assert!(!output.contains("let c"), "Third statement should be hidden");
+    assert!(!output.contains("{ ... }"), "Body should not be fully collapsed");
+}
+
+#[test]
+fn rs_preview_applies_inside_impl_blocks() {
+    let src = "impl S {\n    fn m(&self) {\n        one();\n        two();\n    }\n}\n";
+    let f = write_rs(src);
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { preview: Some(1), ..opts() },
+    )
+    .unwrap();
+
+    assert!(output.contains("one();"), "Missing first statement: {}", output);
+    assert!(output.contains("        ..."), "Missing preview marker: {}", output);
+    assert!(!output.contains("two();"));
+}
+
+// --- Method-of filter ---
+
+#[test]
+fn rs_method_of_shows_only_that_types_methods() {
+    let src = r#"pub struct User;
+pub struct Admin;
+
+impl User {
+    pub fn new() -> Self { User }
+}
+
+impl Admin {
+    pub fn promote(&self) {}
+}
+
+impl Greeter for User {
+    fn greet(&self) -> String { String::new() }
+}
+
+pub fn free_function() {}
+"#;
+    let f = write_rs(src);
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { method_of: Some("User".to_string()), ..opts() },
+    )
+    .unwrap();
+
+    assert!(output.contains("pub fn new() -> Self { ... }"), "Missing inherent method: {}", output);
+    assert!(output.contains("fn greet(&self) -> String { ... }"), "Missing trait impl method: {}", output);
+    assert!(!output.contains("promote"), "Other type's methods should be hidden");
+    assert!(!output.contains("free_function"), "Free functions should be hidden");
+    assert!(!output.contains("pub struct"), "Types should be hidden");
+}
+
+#[test]
+fn rs_method_of_ignores_generic_arguments() {
+    let src = "struct Wrap<T>(T);\n\nimpl<T> Wrap<T> {\n    fn get(&self) -> &T { &self.0 }\n}\n";
+    let f = write_rs(src);
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { method_of: Some("Wrap".to_string()), ..opts() },
+    )
+    .unwrap();
+
+    assert!(output.contains("fn get(&self) -> &T { ... }"), "Missing generic impl method: {}", output);
+}
+
+#[test]
+fn rs_method_of_combines_with_kind_filters() {
+    let src = "pub struct User;\n\nimpl User {\n    pub fn new() -> Self { User }\n}\n\nimpl fmt::Display for models::User {\n    fn fmt(&self) {}\n}\n";
+    let f = write_rs(src);
+    let path = f.path().to_str().unwrap();
+    let method_of = || Some("User".to_string());
+
+    // Methods are not types, so --types leaves nothing
+    let output = process_path(path, ProcessOptions { method_of: method_of(), types_only: true, ..opts() }).unwrap();
+    assert!(!output.contains("fn "), "--types should drop methods: {}", output);
+    assert!(!output.contains("pub struct User"), "--method-of should drop the type: {}", output);
+
+    let output = process_path(path, ProcessOptions { method_of: method_of(), fns_only: true, ..opts() }).unwrap();
+    assert!(output.contains("pub fn new() -> Self { ... }"), "Missing method with --fns: {}", output);
+    assert!(output.contains("fn fmt(&self) { ... }"), "Path-qualified impl type should match: {}", output);
+}
+
+// --- Where expressions ---
+
+#[test]
+fn rs_where_selects_public_functions() {
+    let src = "pub fn open() {}\n\nfn close() {}\n\npub struct Door;\n\npub const SIZE: u32 = 2;\n";
+    let f = write_rs(src);
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { filter: Some(FilterExpr::parse("pub and kind=fn").unwrap()), ..opts() },
+    )
+    .unwrap();
+
+    assert!(output.contains("pub fn open()"), "Missing public function: {}", output);
+    assert!(!output.contains("close"), "Private functions should be hidden");
+    assert!(!output.contains("Door") && !output.contains("SIZE"), "Other kinds should be hidden");
+}
+
+#[test]
+fn rs_where_selects_methods_and_fields() {
+    let path = "tests/fixtures/sample.rs";
+    let where_opts = |expr: &str| ProcessOptions { filter: Some(FilterExpr::parse(expr).unwrap()), ..opts() };
+
+    let output = process_path(path, where_opts("kind=method")).unwrap();
+    assert!(output.contains("pub fn greeting(&self) -> String"), "got:\n{output}");
+    assert!(!output.contains("impl User") && !output.contains("public_utility"), "got:\n{output}");
+
+    let mut o = where_opts("kind=field");
+    o.format = codeview::OutputFormat::Json;
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(path, o).unwrap()).unwrap();
+    let names: Vec<&str> = parsed["files"][0]["items"].as_array().unwrap().iter().map(|i| i["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["name", "age", "email"]);
+}
+
+#[test]
+fn rs_where_keeps_members_inside_their_rendered_owner() {
+    let src = "pub struct Point<T> {\n    x: T,\n    y: T,\n}\n\nimpl<T: Clone> Point<T> {\n    pub fn new(x: T, y: T) -> Self {\n        Point { x, y }\n    }\n\n    pub async fn run(&self) {\n        let _ = self.x.clone();\n    }\n}\n";
+    let f = write_rs(src);
+    let o = ProcessOptions { filter: Some(FilterExpr::parse("lines>1").unwrap()), ..opts() };
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert_eq!(output.matches("pub fn new(x: T, y: T) -> Self").count(), 1, "got:\n{output}");
+    assert_eq!(output.matches("pub async fn run(&self)").count(), 1, "got:\n{output}");
+    assert_eq!(output.matches("|     x: T,\n").count(), 1, "got:\n{output}");
+}
+
+// --- Collapse match ---
+
+#[test]
+fn collapse_match_keeps_patterns_and_hides_arm_bodies() {
+    let f = write_rs("fn describe(n: i32) -> &'static str {\n    let label = match n {\n        0 => {\n            let zero = \"zero\";\n            zero\n        }\n        1 | 2 => \"small\",\n        x if x < 0 => {\n            \"negative\"\n        }\n        _ => \"large\",\n    };\n    label\n}\n");
+    let mut o = opts();
+    o.symbols = vec!["describe".to_string()];
+    o.collapse_match = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("0 => { ... }"), "got:\n{output}");
+    assert!(output.contains("1 | 2 => { ... },"), "got:\n{output}");
+    assert!(output.contains("x if x < 0 => { ... }"), "got:\n{output}");
+    assert!(output.contains("_ => { ... },"), "got:\n{output}");
+    assert!(!output.contains("zero"));
+    assert!(!output.contains("negative"));
+    assert!(output.contains("    label\n"), "code outside the match stays expanded:\n{output}");
+}
+
+#[test]
+fn without_collapse_match_arms_stay_expanded() {
+    let f = write_rs("fn f(n: i32) -> i32 {\n    match n {\n        0 => 1,\n        _ => 2,\n    }\n}\n");
+    let mut o = opts();
+    o.symbols = vec!["f".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("0 => 1,"));
+}
+
+// --- Collapse strings ---
+
+#[test]
+fn collapse_strings_abbreviates_long_literals() {
+    let long = "x".repeat(500);
+    let f = write_rs(&format!("fn banner() -> &'static str {{\n    let short = \"ok\";\n    \"{long}\"\n}}\n"));
+    let mut o = opts();
+    o.symbols = vec!["banner".to_string()];
+    o.collapse_strings = Some(80);
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("    \"...<502 chars>...\"\n"), "got:\n{output}");
+    assert!(output.contains("let short = \"ok\";"), "short literals are kept:\n{output}");
+    assert!(!output.contains(&long));
+}
+
+// --- Body depth ---
+
+#[test]
+fn body_depth_collapses_blocks_past_the_limit() {
+    let f = write_rs("fn walk(items: &[u32], on: bool) {\n    if on {\n        for i in items {\n            visit(i);\n        }\n    }\n    done();\n}\n");
+    let mut o = opts();
+    o.symbols = vec!["walk".to_string()];
+    o.body_depth = Some(1);
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("    if on {\n"), "the if block stays expanded:\n{output}");
+    assert!(output.contains("        for i in items { ... }\n"), "the loop body collapses:\n{output}");
+    assert!(!output.contains("visit(i)"));
+    assert!(output.contains("    done();"));
+}
+
+// --- Qualified expansion ---
+
+#[test]
+fn rs_expand_qualified_method() {
+    let src = "struct User;\nstruct Team;\n\nimpl User {\n    fn new() -> Self {\n        User\n    }\n}\n\nimpl<T> Team<T> {\n    fn new() -> Self {\n        Team\n    }\n}\n";
+    let f = write_rs(src);
+    let mut o = opts();
+    o.symbols = vec!["User::new".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o.clone()).unwrap();
+    assert!(output.contains("        User\n"), "got:\n{output}");
+    assert!(!output.contains("        Team\n"), "other impl's new should not match:\n{output}");
+
+    o.symbols = vec!["Team::new".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    assert!(output.contains("        Team\n") && !output.contains("        User\n"), "got:\n{output}");
+}
+
+// --- Dedent ---
+
+#[test]
+fn rs_dedent_left_aligns_nested_method() {
+    let src = "mod a {\n    impl S {\n        fn deep(&self) {\n            if ready {\n                go();\n            }\n        }\n    }\n}\n";
+    let f = write_rs(src);
+    let mut o = opts();
+    o.symbols = vec!["deep".to_string()];
+    o.dedent = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    assert!(
+        output.contains("3 | fn deep(&self) {\n4 |     if ready {\n5 |         go();\n6 |     }\n7 | }\n"),
+        "got:\n{output}"
+    );
+}
+
+// --- Module expansion ---
+
+#[test]
+fn rs_expand_module_shows_inner_bodies() {
+    let src = "mod outer {\n    pub fn run() {\n        let x = 1;\n        helper(x);\n    }\n\n    mod inner {\n        fn deep() {\n            body();\n        }\n    }\n}\n";
+    let f = write_rs(src);
+    let mut o = opts();
+    o.symbols = vec!["outer".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o.clone()).unwrap();
+    assert!(output.contains(" 3 |         let x = 1;\n 4 |         helper(x);\n"), "got:\n{output}");
+    assert!(output.contains(" 9 |             body();"), "submodules are dumped by default:\n{output}");
+
+    o.recursive_expand = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    assert!(output.contains(" 4 |         helper(x);"), "own functions stay in full:\n{output}");
+    assert!(output.contains(" 8 |         fn deep() { ... }"), "submodules are summarized:\n{output}");
+    assert!(!output.contains("body();"));
+}
+
+// --- No collapse ---
+
+#[test]
+fn rs_no_collapse_shows_full_bodies() {
+    let src = "pub fn run(x: u32) -> u32 {\n    let a = x + 1;\n    a * 2\n}\n\nimpl S {\n    fn m(&self) {\n        one();\n    }\n}\n";
+    let f = write_rs(src);
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { no_collapse: true, ..opts() },
+    )
+    .unwrap();
+
+    assert!(output.contains("2 |     let a = x + 1;"), "got:\n{output}");
+    assert!(output.contains("3 |     a * 2"));
+    assert!(output.contains("8 |         one();"));
+    assert!(!output.contains("{ ... }"));
+    assert!(!output.contains("..."));
+}
+
+// --- Group consts ---
+
+#[test]
+fn rs_group_consts_puts_run_under_one_heading() {
+    let f = write_rs("pub const A: u32 = 1;\nconst B: &str = \"b\";\nstatic C: u8 = 3;\n\nfn f() {}\n");
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { group_consts: true, ..opts() },
+    )
+    .unwrap();
+
+    assert_eq!(output.matches("// constants").count(), 1);
+    assert!(
+        output.contains("// constants\n1 | pub const A: u32 = 1;\n2 | const B: &str = \"b\";\n3 | static C: u8 = 3;\n\n"),
+        "got:\n{output}"
+    );
+    assert!(output.contains("5 | fn f() { ... }"));
+}
+
+#[test]
+fn rs_group_consts_leaves_single_const_alone() {
+    let f = write_rs("const A: u32 = 1;\n\nfn f() {}\n");
+    let output = process_path(
+        f.path().to_str().unwrap(),
+        ProcessOptions { group_consts: true, ..opts() },
+    )
+    .unwrap();
+
+    assert!(!output.contains("// constants"));
+}
+
+// --- Contract ---
+
+const CONTRACT_SRC: &str = "pub trait Greeter {
+    fn greet(&self) -> String;
+    fn name(&self) -> &str;
+    fn farewell(&self) -> String {
+        \"bye\".to_string()
+    }
+}
+
+pub struct User;
+
+impl Greeter for User {
+    fn greet(&self) -> String {
+        \"hi\".to_string()
+    }
+}
+";
+
+#[test]
+fn contract_shows_trait_and_impl_methods() {
+    let f = write_rs(CONTRACT_SRC);
+    let output = codeview::contract::format_contract(f.path().to_str().unwrap(), "User", "Greeter").unwrap();
+    let (trait_part, impl_part) = output.split_once("impl Greeter for User").unwrap();
+
+    assert!(trait_part.contains("fn greet(&self) -> String"));
+    assert!(trait_part.contains("fn name(&self) -> &str"));
+    assert!(trait_part.contains("fn farewell(&self) -> String"));
+    assert!(impl_part.contains("fn greet(&self) -> String"));
+    assert!(!impl_part.contains("fn name"));
+}
+
+#[test]
+fn contract_marks_missing_required_methods() {
+    let f = write_rs(CONTRACT_SRC);
+    let output = codeview::contract::format_contract(f.path().to_str().unwrap(), "User", "Greeter").unwrap();
+
+    let name_line = output.lines().find(|l| l.contains("fn name")).unwrap();
+    assert!(name_line.ends_with("<- missing"), "got: {name_line}");
+    let farewell_line = output.lines().find(|l| l.contains("fn farewell")).unwrap();
+    assert!(farewell_line.ends_with("(default)"), "got: {farewell_line}");
+    assert!(output.ends_with("missing: name\n"));
+}
+
+#[test]
+fn contract_without_impl_is_an_error() {
+    let f = write_rs(CONTRACT_SRC);
+    assert!(codeview::contract::format_contract(f.path().to_str().unwrap(), "Admin", "Greeter").is_err());
+}
+
+// --- Modifiers ---
+
+#[test]
+fn rs_method_modifiers_kept_in_signature_and_json() {
+    let f = write_rs("pub struct Conn;\n\nimpl Conn {\n    pub async unsafe fn open() -> Conn {\n        Conn\n    }\n}\n\npub const fn limit() -> usize {\n    8\n}\n");
+    let output = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+    assert!(output.contains("pub async unsafe fn open() -> Conn"), "got:\n{output}");
+
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    o.fns_only = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let open = items.iter().find(|i| i["name"] == "open").unwrap();
+    assert_eq!(open["is_async"], true);
+    assert_eq!(open["is_unsafe"], true);
+    let limit = items.iter().find(|i| i["name"] == "limit").unwrap();
+    assert_eq!(limit["is_const"], true);
+}
+
+// --- Re-exports ---
+
+#[test]
+fn rs_pub_use_kept_under_pub_only() {
+    let f = write_rs("pub use crate::model::User;\nuse std::fmt;\npub(crate) use crate::util::helper;\n\npub fn run() {}\n");
+    let mut o = opts();
+    o.pub_only = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("pub use crate::model::User;"), "got:\n{output}");
+    assert!(!output.contains("use std::fmt;"));
+    assert!(!output.contains("pub(crate) use"));
+}
+
+// --- Enum variants ---
+
+const SHAPE_SRC: &str = "pub enum Shape {\n    Circle(f64),\n    Rect {\n        w: f64,\n        h: f64,\n    },\n    Empty,\n}\n";
+
+#[test]
+fn rs_enum_variants_in_json_with_data() {
+    let f = write_rs(SHAPE_SRC);
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let variant = |name: &str| items.iter().find(|i| i["kind"] == "variant" && i["name"] == name).unwrap();
+    assert_eq!(variant("Circle")["signature"], "Circle(f64)");
+    assert_eq!(variant("Rect")["signature"], "Rect { w: f64, h: f64 }");
+    assert_eq!(variant("Rect")["parent"], "Shape");
+    assert_eq!(variant("Empty")["signature"], "Empty");
+}
+
+#[test]
+fn rs_enum_variants_listed_but_not_repeated_in_plain() {
+    let f = write_rs(SHAPE_SRC);
+    let mut o = opts();
+    o.list_symbols = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    assert!(output.lines().any(|l| l.trim_start().starts_with("variant Circle")), "got:\n{output}");
+    assert!(output.lines().any(|l| l.trim_start().starts_with("variant Rect")));
+
+    let output = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+    assert_eq!(output.matches("Circle(f64)").count(), 1, "got:\n{output}");
+}
+
+// --- Parameter kinds ---
+
+#[test]
+fn rs_parameter_kind_hints_in_json() {
+    let f = write_rs("pub fn apply<T, F>(items: &[T], f: F, log: impl Fn(&str), sink: &mut dyn Write, item: T) where F: FnMut(&T) {}\n");
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let params = parsed["files"][0]["items"][0]["parameters"].as_array().unwrap();
+
+    let kind = |name: &str| params.iter().find(|p| p["name"] == name).unwrap()["kind"].clone();
+    assert_eq!(kind("items"), "concrete");
+    assert_eq!(kind("f"), "closure");
+    assert_eq!(kind("log"), "closure");
+    assert_eq!(kind("sink"), "impl_trait");
+    assert_eq!(kind("item"), "generic");
+    assert_eq!(params[2]["type"], "impl Fn(&str)");
+}
+
+// --- Type parameters ---
+
+#[test]
+fn rs_generic_struct_type_parameters_in_json() {
+    let f = write_rs("pub struct Cache<'a, K, V: Clone + 'a, const N: usize> {\n    slots: [&'a V; N],\n    key: K,\n}\n\npub struct Plain;\n");
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = &parsed["files"][0]["items"];
+
+    assert_eq!(items[0]["type_parameters"], serde_json::json!(["'a", "K", "V: Clone + 'a", "const N: usize"]));
+    assert!(items[1].get("type_parameters").is_none(), "omitted when not generic");
+}
+
+// --- Lifetimes ---
+
+#[test]
+fn rs_lifetime_parameters_kept_in_signatures() {
+    let f = write_rs("pub fn first<'a>(x: &'a str) -> &'a str {\n    x\n}\n\npub struct P;\n\nimpl P {\n    pub fn pick<'a, 'b: 'a, T>(&self, x: &'a str, t: T) -> &'a str\n    where\n        T: Into<&'b str> + 'a,\n    {\n        x\n    }\n}\n");
+    let output = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+    assert!(output.contains("pub fn first<'a>(x: &'a str) -> &'a str { ... }"), "got:\n{output}");
+
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    o.fns_only = true;
+    let parsed: serde_json::Value = serde_json::from_str(&process_path(f.path().to_str().unwrap(), o).unwrap()).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+    let pick = items.iter().find(|i| i["name"] == "pick").unwrap();
+    assert_eq!(
+        pick["signature"],
+        "pub fn pick<'a, 'b: 'a, T>(&self, x: &'a str, t: T) -> &'a str where T: Into<&'b str> + 'a,"
+    );
+    assert_eq!(pick["type_parameters"], serde_json::json!(["'a", "'b: 'a", "T"]));
+}
+
+// --- Doc comments ---
+
+#[test]
+fn rs_docs_keeps_doc_comments_in_interface() {
+    let src = "//! Crate docs.\n\n/// Adds one.\n#[inline]\npub fn add(x: u8) -> u8 {\n    x + 1\n}\n\n// plain comment\nfn plain() {}\n";
+    let f = write_rs(src);
+    let terse = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+    assert!(!terse.contains("/// Adds one."), "docs shown without --docs:\n{terse}");
+
+    let mut o = opts();
+    o.docs = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    assert!(output.contains("3 | /// Adds one.\n4 | #[inline]\n5 | pub fn add(x: u8) -> u8 { ... }"), "got:\n{output}");
+    assert!(!output.contains("Crate docs") && !output.contains("plain comment"), "got:\n{output}");
+}
+
+// --- Test detection ---
+
+#[test]
+fn rs_cfg_all_test_marks_module_as_test() {
+    let f = write_rs("#[cfg(all(test, feature = \"x\"))]\nmod gated {\n    fn helper() {}\n}\n\n#[cfg(not(test))]\nmod real {}\n");
+    let mut o = opts();
+    o.format = codeview::OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed["files"][0]["items"].as_array().unwrap();
+
+    let gated = items.iter().find(|i| i["name"] == "gated").unwrap();
+    assert_eq!(gated["is_test"], true);
+    let real = items.iter().find(|i| i["name"] == "real").unwrap();
+    assert_eq!(real["is_test"], false);
+}
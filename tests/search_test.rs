@@ -134,13 +134,26 @@ fn search_no_matches_empty_output() {
 
 #[test]
 fn search_with_json_flag() {
-    // --json may not affect search output (search has its own formatter).
-    // This test verifies the command doesn't error out with both flags.
-    let (stdout, _stderr, success) = run_codeview(&[
-        "tests/fixtures/sample.rs", "--search", "User", "--json",
-    ]);
-    assert!(success, "should not error with --search --json");
-    assert!(stdout.contains("User"), "should still find User");
+    let stdout = run_ok(&["tests/fixtures/sample.rs", "--search", "fn greeting", "--json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("search --json should be valid JSON");
+
+    let results = parsed["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["path"], "tests/fixtures/sample.rs");
+    let m = &results[0]["matches"][0];
+    assert_eq!(m["line_number"], 16);
+    assert_eq!(m["line_content"], "    pub fn greeting(&self) -> String {");
+    assert_eq!(m["symbol_path"], serde_json::json!(["impl User", "greeting"]));
+    assert!(m.get("blame").is_none());
+}
+
+#[test]
+fn search_json_reports_omitted_matches() {
+    let stdout = run_ok(&["tests/fixtures/sample.rs", "--search", "User", "--json", "--max-results", "1"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    assert_eq!(parsed["results"][0]["matches"].as_array().unwrap().len(), 1);
+    assert!(parsed["omitted_matches"].as_u64().unwrap() > 0);
 }
 
 // ---------------------------------------------------------------------------
@@ -258,3 +271,254 @@ fn search_top_level_annotation() {
     let out = run_ok(&[&path, "--search", "std::io"]);
     assert!(out.contains("(top-level)"), "top-level matches should be annotated");
 }
+
+// ---------------------------------------------------------------------------
+// Blame annotation
+// ---------------------------------------------------------------------------
+
+fn git(dir: &TempDir, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir.path())
+        .args(["-c", "user.name=Blame Tester", "-c", "user.email=blame@example.com"])
+        .args(args)
+        .output()
+        .expect("failed to run git");
+    assert!(status.status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn search_blame_annotates_author() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "lib.rs", "fn alpha() {\n    marker();\n}\n");
+    git(&dir, &["init", "-q"]);
+    git(&dir, &["add", "lib.rs"]);
+    git(&dir, &["commit", "-q", "-m", "init"]);
+
+    let out = run_ok(&[&path, "--search", "marker", "--blame"]);
+    let match_line = out.lines().find(|l| l.contains("marker()")).unwrap();
+    assert!(match_line.contains("(Blame Tester, "), "missing blame annotation: {}", out);
+}
+
+#[test]
+fn search_blame_with_max_results_annotates_shown_matches() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "a.rs", "fn alpha() {\n    marker();\n}\n");
+    write_file(&dir, "b.rs", "fn beta() {\n    marker();\n}\n");
+    git(&dir, &["init", "-q"]);
+    git(&dir, &["add", "."]);
+    git(&dir, &["commit", "-q", "-m", "init"]);
+
+    let out = run_ok(&[dir.path().to_str().unwrap(), "--search", "marker", "--blame", "--max-results", "1"]);
+    let match_lines: Vec<&str> = out.lines().filter(|l| l.contains("marker()")).collect();
+    assert_eq!(match_lines.len(), 1, "got:\n{}", out);
+    assert!(match_lines[0].contains("(Blame Tester, "), "missing blame annotation: {}", out);
+    assert!(out.contains("... and 1 more matches across 1 files"), "got:\n{}", out);
+}
+
+#[test]
+fn search_blame_outside_repo_degrades() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "lib.rs", "fn alpha() {\n    marker();\n}\n");
+    let out = run_ok(&[&path, "--search", "marker", "--blame"]);
+    let match_line = out.lines().find(|l| l.contains("marker()")).unwrap();
+    assert!(match_line.ends_with("L2:    marker();"), "no annotation expected outside git: {}", out);
+}
+
+// ---------------------------------------------------------------------------
+// Expand matches — print whole enclosing symbols
+// ---------------------------------------------------------------------------
+
+#[test]
+fn search_expand_matches_prints_whole_function() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "lib.rs", r#"fn alpha() {
+    let a = 1;
+    marker(a);
+    let b = 2;
+}
+
+fn beta() {
+    quiet();
+}
+"#);
+    let out = run_ok(&[&path, "--search", "marker", "--expand-matches"]);
+    assert!(out.contains("alpha [1:5]"), "missing symbol header: {}", out);
+    assert!(out.contains("1 | fn alpha() {"), "missing signature line: {}", out);
+    assert!(out.contains("let a = 1;"), "missing unmatched body line: {}", out);
+    assert!(out.contains("let b = 2;"), "missing unmatched body line: {}", out);
+    assert!(out.contains("> 3 |     marker(a);"), "match line should be marked: {}", out);
+    assert!(out.contains("  2 |     let a = 1;"), "other lines should be unmarked: {}", out);
+    assert!(!out.contains("beta"), "functions without matches should be omitted: {}", out);
+}
+
+#[test]
+fn search_expand_matches_dedupes_symbol() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "lib.rs", "fn alpha() {\n    marker();\n    marker();\n}\n");
+    let out = run_ok(&[&path, "--search", "marker", "--expand-matches"]);
+    assert_eq!(out.matches("alpha [1:4]").count(), 1, "symbol printed once: {}", out);
+    assert_eq!(out.lines().filter(|l| l.trim_start().starts_with('>')).count(), 2);
+}
+
+// ---------------------------------------------------------------------------
+// --next-symbol
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_next_symbol_after_last_method() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(
+        &dir,
+        "svc.ts",
+        "export class Service {\n  start() {\n    return 1;\n  }\n\n  stop() {\n    return MARKER;\n  }\n}\n\nexport function helper(): number {\n  return 2;\n}\n",
+    );
+    let stdout = run_ok(&[&path, "--search", "MARKER", "--next-symbol"]);
+
+    assert!(stdout.contains("Service > stop()"), "got:\n{stdout}");
+    assert!(stdout.contains("L7:    return MARKER;  (next: helper())"), "got:\n{stdout}");
+}
+
+#[test]
+fn test_next_symbol_omitted_for_last_item() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "a.rs", "fn first() {}\n\nfn last() {\n    target();\n}\n");
+    let stdout = run_ok(&[&path, "--search", "target", "--next-symbol"]);
+
+    assert!(stdout.contains("L4:    target();\n"), "got:\n{stdout}");
+    assert!(!stdout.contains("(next:"));
+}
+
+// ---------------------------------------------------------------------------
+// Context lines (-A / -B / -C)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn search_context_lines_use_grep_gutters() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "lib.rs", "fn alpha() {\n    let a = 1;\n    marker(a);\n    let b = 2;\n}\n");
+    let out = run_ok(&[&path, "--search", "marker", "-B", "1", "-A", "2"]);
+
+    assert!(out.contains("    L2-    let a = 1;\n    L3:    marker(a);\n    L4-    let b = 2;\n    L5-}\n"), "got:\n{out}");
+    assert!(!out.contains("L1-"), "only one line before: {out}");
+}
+
+#[test]
+fn search_overlapping_context_is_printed_once() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(
+        &dir,
+        "lib.rs",
+        "fn alpha() {\n    marker(1);\n    let x = 0;\n    marker(2);\n    one();\n    two();\n    three();\n    four();\n    marker(3);\n}\n",
+    );
+    let out = run_ok(&[&path, "--search", "marker", "-C", "1"]);
+
+    assert_eq!(out.matches("L3-").count(), 1, "shared context line printed once: {out}");
+    assert!(out.contains("    L4:    marker(2);\n"), "match lines keep the `:` gutter: {out}");
+    assert!(!out.contains("L4-"));
+    assert!(out.contains("    L5-    one();\n    --\n    L8-    four();\n"), "gaps are separated: {out}");
+}
+
+// ---------------------------------------------------------------------------
+// --symbol-depth
+// ---------------------------------------------------------------------------
+
+#[test]
+fn search_symbol_depth_keeps_innermost_symbols() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(
+        &dir,
+        "lib.rs",
+        "mod outer {\n    mod inner {\n        impl Thing {\n            fn deep() {\n                marker();\n            }\n        }\n    }\n}\n",
+    );
+    let out = run_ok(&[&path, "--search", "marker"]);
+    assert!(out.contains("  outer > inner > impl Thing > deep\n"), "full path by default: {out}");
+
+    let out = run_ok(&[&path, "--search", "marker", "--symbol-depth", "1"]);
+    assert!(out.contains("\n  deep\n    L5:"), "got:\n{out}");
+    assert!(!out.contains("outer"));
+
+    let out = run_ok(&[&path, "--search", "marker", "--symbol-depth", "2"]);
+    assert!(out.contains("\n  impl Thing > deep\n"), "got:\n{out}");
+}
+
+// ---------------------------------------------------------------------------
+// --in-kind
+// ---------------------------------------------------------------------------
+
+#[test]
+fn search_in_kind_keeps_matches_in_matching_symbols() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(
+        &dir,
+        "lib.rs",
+        "// marker at top level\nstruct Marker { marker: u8 }\nfn free() {\n    marker();\n}\nimpl Thing {\n    fn method() {\n        marker();\n    }\n}\n",
+    );
+    let out = run_ok(&[&path, "--search", "marker", "--in-kind", "function"]);
+    assert!(out.contains("L4:"), "got:\n{out}");
+    assert!(!out.contains("L1:") && !out.contains("L2:") && !out.contains("L8:"), "got:\n{out}");
+
+    let out = run_ok(&[&path, "--search", "marker", "--in-kind", "function,method"]);
+    assert!(out.contains("L4:") && out.contains("L8:"), "got:\n{out}");
+    assert!(!out.contains("L2:"), "struct fields are excluded: {out}");
+
+    let out = run_ok(&[&path, "--search", "marker", "--in-kind", "struct"]);
+    assert!(out.contains("L2:") && !out.contains("L4:"), "got:\n{out}");
+}
+
+#[test]
+fn search_in_kind_treats_python_defs_in_classes_as_methods() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "app.py", "def helper():\n    marker()\n\nclass A:\n    @staticmethod\n    def run():\n        marker()\n");
+    let out = run_ok(&[&path, "--search", "marker", "--in-kind", "method"]);
+    assert!(out.contains("L7:") && !out.contains("L2:"), "got:\n{out}");
+}
+
+#[test]
+fn search_in_kind_rejects_unknown_kinds() {
+    let dir = TempDir::new().unwrap();
+    let path = write_file(&dir, "lib.rs", "fn f() {}\n");
+    let (_, stderr, success) = run_codeview(&[&path, "--search", "f", "--in-kind", "fucntion"]);
+    assert!(!success);
+    assert!(stderr.contains("unknown kind 'fucntion'"), "{stderr}");
+    assert!(stderr.contains("use, const") && stderr.contains("class, variant, field"), "lists every kind: {stderr}");
+}
+
+// ---------------------------------------------------------------------------
+// --count
+// ---------------------------------------------------------------------------
+
+#[test]
+fn search_count_prints_per_file_counts_and_total() {
+    let dir = TempDir::new().unwrap();
+    let a = write_file(&dir, "a.rs", "// TODO one\nfn f() {\n    // TODO two\n}\n");
+    let b = write_file(&dir, "b.rs", "// TODO three\n");
+    write_file(&dir, "c.rs", "fn clean() {}\n");
+    let root = dir.path().to_str().unwrap();
+
+    let out = run_ok(&[root, "--search", "TODO", "--count"]);
+    assert_eq!(out, format!("{a}: 2\n{b}: 1\ntotal: 3 matches in 2 files\n"));
+
+    let out = run_ok(&[root, "--search", "TODO", "-c", "--json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(parsed["results"][0]["path"], a.as_str());
+    assert_eq!(parsed["results"][0]["count"], 2);
+    assert_eq!(parsed["results"][1]["count"], 1);
+    assert_eq!(parsed["total"], 3);
+}
+
+#[test]
+fn search_lossy_reads_non_utf8_files() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("latin1.rs");
+    fs::write(&path, b"// caf\xe9\nfn needle() {}\n").unwrap();
+    let root = dir.path().to_str().unwrap();
+
+    let out = run_ok(&[root, "--search", "needle"]);
+    assert!(!out.contains("needle"), "non-UTF-8 files are skipped without --lossy");
+
+    let out = run_ok(&[root, "--search", "needle", "--lossy"]);
+    assert!(out.contains("fn needle() {}"), "{}", out);
+    let out = run_ok(&[root, "--search", "caf", "--lossy", "--expand-matches"]);
+    assert!(out.contains("caf\u{FFFD}"), "{}", out);
+}
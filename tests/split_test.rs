@@ -0,0 +1,79 @@
+use codeview::split_symbols;
+use std::fs;
+use tempfile::TempDir;
+
+const SOURCE: &str = r#"pub struct Foo {
+    a: u32,
+}
+
+impl Foo {
+    pub fn new() -> Self {
+        Foo { a: 0 }
+    }
+}
+
+pub fn bar() -> u32 {
+    42
+}
+
+fn untouched() {}
+"#;
+
+#[test]
+fn split_writes_each_symbol_to_own_file() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("lib.rs");
+    fs::write(&src, SOURCE).unwrap();
+    let out_dir = dir.path().join("out");
+
+    let written = split_symbols(
+        src.to_str().unwrap(),
+        &["Foo".to_string(), "bar".to_string()],
+        out_dir.to_str().unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(written, vec![out_dir.join("Foo.rs"), out_dir.join("bar.rs")]);
+
+    let foo = fs::read_to_string(out_dir.join("Foo.rs")).unwrap();
+    assert!(foo.starts_with("pub struct Foo {"), "struct first: {}", foo);
+    assert!(foo.contains("impl Foo {\n    pub fn new() -> Self {"), "impl included: {}", foo);
+    assert!(!foo.contains("fn bar"));
+
+    let bar = fs::read_to_string(out_dir.join("bar.rs")).unwrap();
+    assert_eq!(bar, "pub fn bar() -> u32 {\n    42\n}\n");
+
+    assert_eq!(fs::read_to_string(&src).unwrap(), SOURCE, "source must be untouched");
+}
+
+#[test]
+fn split_missing_symbol_writes_nothing() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("lib.rs");
+    fs::write(&src, SOURCE).unwrap();
+    let out_dir = dir.path().join("out");
+
+    let result = split_symbols(
+        src.to_str().unwrap(),
+        &["bar".to_string(), "missing".to_string()],
+        out_dir.to_str().unwrap(),
+    );
+
+    assert!(result.is_err());
+    assert!(!out_dir.exists(), "no partial output expected");
+}
+
+#[test]
+fn split_qualified_method_names_file_after_method() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("lib.rs");
+    fs::write(&src, SOURCE).unwrap();
+    let out_dir = dir.path().join("out");
+
+    let written = split_symbols(src.to_str().unwrap(), &["Foo::new".to_string()], out_dir.to_str().unwrap()).unwrap();
+
+    assert_eq!(written, vec![out_dir.join("new.rs")]);
+    let new = fs::read_to_string(out_dir.join("new.rs")).unwrap();
+    assert!(new.contains("pub fn new() -> Self {"), "method body: {}", new);
+    assert!(!new.contains("impl Foo"));
+}
@@ -0,0 +1,67 @@
+use codeview::stubs::{self, StubOptions};
+use std::fs;
+use tempfile::TempDir;
+
+fn write_file(dir: &TempDir, name: &str, content: &str) {
+    fs::write(dir.path().join(name), content).unwrap();
+}
+
+fn names(dir: &TempDir) -> Vec<String> {
+    let found = stubs::find_stubs(dir.path().to_str().unwrap(), &StubOptions::default()).unwrap();
+    found.into_iter().map(|s| s.name).collect()
+}
+
+#[test]
+fn test_rust_todo_is_a_stub() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "a.rs", "fn foo() { todo!() }\n\nfn real() -> u32 {\n    1\n}\n");
+    let found = stubs::find_stubs(dir.path().to_str().unwrap(), &StubOptions::default()).unwrap();
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "foo");
+    assert_eq!(found[0].line_start, 1);
+    assert_eq!(found[0].marker.as_deref(), Some("todo!()"));
+}
+
+#[test]
+fn test_empty_bodies_ignore_comments() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "a.rs", "fn later() {\n    // TODO\n}\n\ntrait T {\n    fn required(&self);\n}\n");
+    let found = stubs::find_stubs(dir.path().to_str().unwrap(), &StubOptions::default()).unwrap();
+
+    assert_eq!(found.len(), 1, "bodiless trait methods are declarations, not stubs");
+    assert_eq!(found[0].marker, None);
+    assert!(stubs::format_stubs(&found).contains(&format!("  fn {:<30} L1  (empty)\n", "later")));
+}
+
+#[test]
+fn test_python_markers_after_docstring() {
+    let dir = TempDir::new().unwrap();
+    write_file(
+        &dir,
+        "a.py",
+        "class Repo:\n    def load(self):\n        \"\"\"Load it.\"\"\"\n        raise NotImplementedError\n\n    def save(self):\n        pass\n\n    def size(self): ...\n\n    def name(self):\n        return 'repo'\n",
+    );
+    assert_eq!(names(&dir), ["load", "save", "size"]);
+}
+
+#[test]
+fn test_ts_not_implemented_throw_and_named_arrows() {
+    let dir = TempDir::new().unwrap();
+    write_file(
+        &dir,
+        "a.ts",
+        "class Api {\n  fetch() {\n    throw new Error('not implemented');\n  }\n  fail() {\n    throw new Error('bad input');\n  }\n}\nconst noop = () => {};\nitems.forEach(() => {});\n",
+    );
+    assert_eq!(names(&dir), ["fetch", "noop"]);
+}
+
+#[test]
+fn test_ts_expression_bodied_arrows_are_not_stubs() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "a.ts", "const one = () => 1;
+const id = (x: number) => x;
+const empty = () => {};
+");
+    assert_eq!(names(&dir), ["empty"]);
+}
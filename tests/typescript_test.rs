@@ -16,6 +16,7 @@ fn opts() -> ProcessOptions {
         signatures: false,
         max_lines: None,
         list_symbols: false,
+        ..Default::default()
     }
 
 }
@@ -103,6 +104,17 @@ fn ts_interface_mode_basic() {
     assert!(output.contains("{ ... }"), "Missing collapsed bodies");
 }
 
+#[test]
+fn ts_interface_shows_enum_member_values() {
+    let f = write_ts(SAMPLE_TS);
+    let output = process_path(f.path().to_str().unwrap(), opts()).unwrap();
+
+    assert!(output.contains("export enum Role {\n"), "Enum body should not be collapsed: {}", output);
+    for member in ["Admin = \"ADMIN\",", "User = \"USER\",", "Guest = \"GUEST\","] {
+        assert!(output.contains(member), "Missing enum member {}: {}", member, output);
+    }
+}
+
 // --- Expand mode ---
 
 #[test]
@@ -127,6 +139,17 @@ fn ts_expand_class() {
     assert!(output.contains("new Map()") || output.contains("this.db"), "Missing class body");
 }
 
+#[test]
+fn ts_expand_qualified_method() {
+    let f = write_ts("class UserService {\n  getUser(id: number) {\n    return lookup(id);\n  }\n}\n\nclass TeamService {\n  getUser() {\n    return first();\n  }\n}\n");
+    let mut o = opts();
+    o.symbols = vec!["UserService.getUser".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("return lookup(id);"), "got:\n{output}");
+    assert!(!output.contains("first()"), "TeamService.getUser should not match:\n{output}");
+}
+
 // --- --pub filter ---
 
 #[test]
@@ -143,6 +166,22 @@ fn ts_pub_filter() {
     assert!(!output.contains("helperFunction"), "Should not contain non-exported helperFunction");
 }
 
+#[test]
+fn ts_include_private_keeps_private_methods_under_pub() {
+    let f = write_ts("export class Vault {\n  private unlock() {\n    return 1;\n  }\n  public open() {\n    return 2;\n  }\n}\n\nfunction helper() {}\n");
+    let mut o = opts();
+    o.pub_only = true;
+    o.fns_only = true;
+    let output = process_path(f.path().to_str().unwrap(), o.clone()).unwrap();
+    assert!(!output.contains("unlock"), "--pub should hide private methods: {}", output);
+
+    o.include_private = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    assert!(output.contains("private unlock()"), "Missing private method: {}", output);
+    assert!(output.contains("public open()"), "Missing public method: {}", output);
+    assert!(!output.contains("helper"), "Private free functions stay hidden: {}", output);
+}
+
 // --- --fns filter ---
 
 #[test]
@@ -386,3 +425,289 @@ fn ts_pub_fns_combined() {
     assert!(!output.contains("helperFunction"), "Should not contain non-exported fn");
     assert!(!output.contains("interface User"), "Should not contain types");
 }
+
+// --- JSDoc parameter docs ---
+
+const JSDOC_TS: &str = r#"/**
+ * Build a greeting.
+ * @param {string} name - who to greet
+ * @param [excited] whether to shout
+ * @returns the greeting text
+ */
+export function greet(name: string, excited?: boolean): string {
+  return name;
+}
+
+class Mailer {
+  /**
+   * @param to recipient address
+   */
+  send(to: string, body: string): void {}
+}
+"#;
+
+fn json_items(output: &str) -> Vec<serde_json::Value> {
+    let parsed: serde_json::Value = serde_json::from_str(output).unwrap();
+    parsed["files"][0]["items"].as_array().unwrap().clone()
+}
+
+#[test]
+fn ts_jsdoc_param_maps_to_parameter() {
+    let f = write_ts(JSDOC_TS);
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+    let greet = items.iter().find(|i| i["name"] == "greet").unwrap();
+
+    let params = greet["parameters"].as_array().unwrap();
+    assert_eq!(params.len(), 2);
+    assert_eq!(params[0]["name"], "name");
+    assert_eq!(params[0]["type"], "string");
+    assert_eq!(params[0]["doc"], "who to greet");
+    assert_eq!(params[1]["name"], "excited");
+    assert_eq!(params[1]["doc"], "whether to shout");
+    assert_eq!(greet["return_doc"], "the greeting text");
+}
+
+#[test]
+fn ts_jsdoc_method_params() {
+    let f = write_ts(JSDOC_TS);
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    o.fns_only = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+    let send = items.iter().find(|i| i["name"] == "send").unwrap();
+
+    let params = send["parameters"].as_array().unwrap();
+    assert_eq!(params[0]["doc"], "recipient address");
+    assert!(params[1].get("doc").is_none(), "undocumented param should have no doc");
+}
+
+// --- Re-exports ---
+
+const BARREL_TS: &str = r#"export { Button, Icon as Glyph } from './components';
+export * from './hooks';
+export * as utils from './utils';
+export type { Theme } from './theme';
+"#;
+
+#[test]
+fn ts_barrel_reexports_listed() {
+    let f = write_ts(BARREL_TS);
+    let mut o = opts();
+    o.list_symbols = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("use Button, Glyph"), "Missing named re-export: {}", output);
+    assert!(output.contains("use *"), "Missing star re-export: {}", output);
+    assert!(output.contains("use utils"), "Missing namespace re-export: {}", output);
+    assert!(output.contains("use Theme"), "Missing type re-export: {}", output);
+}
+
+#[test]
+fn ts_reexport_source_module_in_json() {
+    let f = write_ts(BARREL_TS);
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    assert_eq!(items.len(), 4);
+    assert_eq!(items[0]["kind"], "use");
+    assert_eq!(items[0]["source_module"], "./components");
+    assert_eq!(items[1]["source_module"], "./hooks");
+}
+
+#[test]
+fn ts_resolve_reexports_reports_defining_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("hooks")).unwrap();
+    std::fs::write(dir.path().join("user.ts"), "export interface User { id: number }\n").unwrap();
+    std::fs::write(dir.path().join("hooks/index.ts"), "export function useUser(): void {}\n").unwrap();
+    let barrel = dir.path().join("index.ts");
+    std::fs::write(&barrel, "export { User } from './user';\nexport * from './hooks';\nexport { x } from './missing';\n").unwrap();
+
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    o.resolve_reexports = true;
+    let output = process_path(barrel.to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    let expected = |rel: &str| dir.path().join(rel).to_string_lossy().to_string();
+    assert_eq!(items[0]["resolved"], expected("user.ts"));
+    assert_eq!(items[1]["resolved"], expected("hooks/index.ts"));
+    assert!(items[2].get("resolved").is_none());
+}
+
+// --- Debug node kinds ---
+
+#[test]
+fn ts_debug_kinds_reports_abstract_class_node_kind() {
+    let f = write_ts("export abstract class Shape {\n    abstract area(): number;\n}\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    o.debug_kinds = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    assert_eq!(items[0]["kind"], "class");
+    assert_eq!(items[0]["node_kind"], "abstract_class_declaration");
+}
+
+#[test]
+fn ts_node_kind_omitted_without_debug_kinds() {
+    let f = write_ts("export abstract class Shape {\n    abstract area(): number;\n}\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    assert!(items[0].get("node_kind").is_none());
+}
+
+// --- Test detection ---
+
+#[test]
+fn ts_spec_file_items_flagged_as_tests() {
+    let mut f = tempfile::Builder::new().suffix(".spec.ts").tempfile().unwrap();
+    f.write_all(b"export function setup(): void {}\n").unwrap();
+    f.flush().unwrap();
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    assert_eq!(items[0]["is_test"], true);
+}
+
+#[test]
+fn ts_no_tests_keeps_spec_file_items() {
+    let mut f = tempfile::Builder::new().suffix(".spec.ts").tempfile().unwrap();
+    f.write_all(b"export function setup(): void {}\n").unwrap();
+    f.flush().unwrap();
+    let mut o = opts();
+    o.no_tests = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+
+    assert!(output.contains("function setup"), "--no-tests only drops `mod tests`: {}", output);
+}
+
+#[test]
+fn ts_function_inside_describe_flagged_as_test() {
+    let f = write_ts("describe('math', () => {\n    function helper(): number {\n        return 1;\n    }\n});\n\nfunction real(): number {\n    return 2;\n}\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    o.symbols = vec!["helper".to_string(), "real".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    let helper = items.iter().find(|i| i["name"] == "helper").unwrap();
+    assert_eq!(helper["is_test"], true);
+    let real = items.iter().find(|i| i["name"] == "real").unwrap();
+    assert_eq!(real["is_test"], false);
+}
+
+// --- Modifiers ---
+
+#[test]
+fn ts_modifier_flags_in_json() {
+    let f = write_ts("export async function load(): Promise<void> {}\n\nexport class Repo {\n    static create(): Repo {\n        return new Repo();\n    }\n    find(): void {}\n}\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    o.fns_only = true;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    let load = items.iter().find(|i| i["name"] == "load").unwrap();
+    assert_eq!(load["is_async"], true);
+    let create = items.iter().find(|i| i["name"] == "create").unwrap();
+    assert_eq!(create["is_static"], true);
+    let find = items.iter().find(|i| i["name"] == "find").unwrap();
+    assert!(find.get("is_static").is_none() && find.get("is_async").is_none());
+}
+
+#[test]
+fn ts_decorators_in_json() {
+    let f = write_ts("@Component({ selector: \"app\" })\nexport class App {\n    @Input() title: string;\n    @HostListener(\"click\")\n    onClick(): void {}\n}\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    o.symbols = vec!["App".to_string(), "App.onClick".to_string()];
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    let find = |name: &str| items.iter().find(|i| i["name"] == name).unwrap();
+    assert_eq!(find("App")["decorators"], serde_json::json!(["Component"]));
+    assert_eq!(find("onClick")["decorators"], serde_json::json!(["HostListener"]));
+}
+
+// --- Constructor parameter properties ---
+
+#[test]
+fn ts_constructor_parameter_properties_become_fields() {
+    let f = write_ts("export class User {\n    constructor(private name: string, public readonly age: number, note: string) {}\n}\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+
+    let fields: Vec<_> = items.iter().filter(|i| i["kind"] == "field").collect();
+    assert_eq!(fields.len(), 2, "plain parameters aren't fields: {fields:?}");
+    assert_eq!(fields[0]["name"], "name");
+    assert_eq!(fields[0]["visibility"], "private");
+    assert_eq!(fields[1]["signature"], "public readonly age: number");
+    assert!(fields.iter().all(|f| f["parent"] == "User"));
+}
+
+#[test]
+fn ts_parameter_kind_hints_in_json() {
+    let f = write_ts("export function each<T>(items: T[], cb: (item: T) => void, first: T, count: number): void {}\n");
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let output = process_path(f.path().to_str().unwrap(), o).unwrap();
+    let items = json_items(&output);
+    let params = items[0]["parameters"].as_array().unwrap();
+
+    let kinds: Vec<&str> = params.iter().map(|p| p["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, ["concrete", "closure", "generic", "concrete"]);
+}
+
+// --- Declaration files ---
+
+#[test]
+fn ts_declaration_file_captures_ambient_declarations() {
+    let mut f = tempfile::Builder::new().suffix(".d.ts").tempfile().unwrap();
+    f.write_all(
+        b"declare function greet(name: string): string;\n\
+declare const VERSION: string;\n\
+declare module \"foo\" {\n  export function bar(): void;\n}\n\
+declare namespace NS {\n  function inner(): void;\n}\n\
+export declare class Widget {\n  render(): void;\n}\n\
+export function plain(): number;\n",
+    )
+    .unwrap();
+    f.flush().unwrap();
+    let path = f.path().to_str().unwrap();
+
+    let mut o = opts();
+    o.format = OutputFormat::Json;
+    let items = json_items(&process_path(path, o).unwrap());
+    let summary: Vec<(&str, &str)> = items
+        .iter()
+        .filter_map(|i| Some((i["kind"].as_str()?, i["name"].as_str()?)))
+        .collect();
+    assert_eq!(summary, [
+        ("function", "greet"),
+        ("const", "VERSION"),
+        ("mod", "foo"),
+        ("mod", "NS"),
+        ("class", "Widget"),
+        ("function", "plain"),
+    ]);
+    assert!(items.iter().all(|i| i["visibility"] == "public"));
+
+    let plain = process_path(path, opts()).unwrap();
+    assert!(plain.contains("export function bar(): void;"), "{plain}");
+    assert!(!plain.contains("{ ... }"), "{plain}");
+}
@@ -0,0 +1,69 @@
+use codeview::xref::{self, UnusedSymbol, XrefOptions};
+use codeview::ItemKind;
+use std::fs;
+use tempfile::TempDir;
+
+fn write_file(dir: &TempDir, name: &str, content: &str) {
+    fs::write(dir.path().join(name), content).unwrap();
+}
+
+fn unused_names(dir: &TempDir) -> Vec<String> {
+    let unused = xref::find_unused(dir.path().to_str().unwrap(), &XrefOptions::default()).unwrap();
+    unused.into_iter().map(|u| u.name).collect()
+}
+
+#[test]
+fn test_unused_helper_is_reported() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "main.rs", "fn main() {\n    used_helper();\n}\n\nfn used_helper() {}\n\nfn forgotten_helper() {}\n");
+    let names = unused_names(&dir);
+    assert_eq!(names, vec!["forgotten_helper".to_string()]);
+}
+
+#[test]
+fn test_cross_file_usage_counts() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "a.ts", "export function shared(): number {\n  return 1;\n}\n");
+    write_file(&dir, "b.ts", "import { shared } from './a';\nexport function caller() {\n  return shared();\n}\n");
+    let names = unused_names(&dir);
+    assert!(!names.contains(&"shared".to_string()));
+    assert!(names.contains(&"caller".to_string()));
+}
+
+#[test]
+fn test_recursion_does_not_count_as_usage() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "lib.rs", "fn recurse(n: u32) -> u32 {\n    if n == 0 { 0 } else { recurse(n - 1) }\n}\n");
+    assert_eq!(unused_names(&dir), vec!["recurse".to_string()]);
+}
+
+#[test]
+fn test_unused_methods_reported() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "lib.rs", "struct S;\n\nimpl S {\n    pub fn used(&self) {}\n    fn dead(&self) {}\n}\n\nfn main() {\n    S.used();\n}\n");
+    assert_eq!(unused_names(&dir), vec!["dead".to_string()]);
+}
+
+#[test]
+fn test_unused_cli_output() {
+    let dir = TempDir::new().unwrap();
+    write_file(&dir, "lib.py", "def live():\n    pass\n\ndef orphan():\n    pass\n\nlive()\n");
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .args(["unused", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("lib.py"));
+    assert!(stdout.contains("fn orphan"));
+    assert!(!stdout.contains("live"));
+}
+
+#[test]
+fn test_format_unused_labels_each_kind() {
+    let symbol = |name: &str, kind| UnusedSymbol { path: "lib.rs".to_string(), name: name.to_string(), kind, line_start: 1, line_end: 1 };
+    let output = xref::format_unused(&[symbol("helper", ItemKind::Function), symbol("Config", ItemKind::Struct), symbol("LIMIT", ItemKind::Const)]);
+    assert!(output.contains("  fn helper"));
+    assert!(output.contains("  struct Config"));
+    assert!(output.contains("  const LIMIT"));
+}
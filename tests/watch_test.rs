@@ -0,0 +1,60 @@
+use std::fs;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Read from `rx` until the accumulated output contains `needle` or the timeout passes.
+fn wait_for(rx: &mpsc::Receiver<String>, output: &mut String, needle: &str, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while !output.contains(needle) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok(chunk) => output.push_str(&chunk),
+            Err(_) => return false,
+        }
+    }
+    true
+}
+
+#[test]
+fn watch_rerenders_on_file_change() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("lib.rs");
+    fs::write(&file, "fn alpha() {}\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_codeview"))
+        .arg(dir.path())
+        .arg("--watch")
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run codeview");
+
+    let mut stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        while let Ok(n) = stdout.read(&mut buf) {
+            if n == 0 || tx.send(String::from_utf8_lossy(&buf[..n]).to_string()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut output = String::new();
+    let first = wait_for(&rx, &mut output, "fn alpha", Duration::from_secs(10));
+
+    // Ensure the new mtime differs even on coarse-grained filesystems
+    thread::sleep(Duration::from_millis(50));
+    fs::write(&file, "fn alpha() {}\n\nfn beta() {}\n").unwrap();
+    let second = wait_for(&rx, &mut output, "fn beta", Duration::from_secs(10));
+
+    child.kill().ok();
+    child.wait().ok();
+
+    assert!(first, "initial render missing: {}", output);
+    assert!(second, "change did not trigger a re-render: {}", output);
+    assert!(output.matches("\x1b[2J").count() >= 2, "expected a screen clear per render");
+}